@@ -0,0 +1,143 @@
+//! Typed attributes of user-defined equipment categories.
+//!
+//! Each `Category` lists its attributes as `AttributeDef`s. Values are typed in
+//! as text, in forms, on the command line and in CSV files, and `AttributeDef::parse`
+//! turns that text into an `AttributeValue` or explains what is wrong with it.
+
+use crate::attribute_kind::AttributeKind;
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Longest attribute key; keys name the values in CSV files and on the command line
+pub const MAX_KEY_LEN: usize = 30;
+
+/// One attribute of a category, e.g. "Skjástærð" as a number of inches from 10 to 100
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttributeDef {
+    /// Short lowercase name, e.g. `skjastaerd`, used in CSV files and `--attr key=value`
+    pub key: String,
+    /// Name shown in forms and reports, e.g. "Skjástærð"
+    pub label: String,
+    pub kind: AttributeKind,
+    pub required: bool,
+    /// Smallest allowed number, for `AttributeKind::Number`
+    pub min: Option<i64>,
+    /// Largest allowed number, for `AttributeKind::Number`
+    pub max: Option<i64>,
+    /// Allowed values, for `AttributeKind::Choice`
+    pub choices: Vec<String>,
+    /// Unit written after numbers, e.g. "tommur"; may be empty
+    pub unit: String,
+}
+
+impl AttributeDef {
+    /// Optional attribute with no range, choices or unit yet
+    pub fn new(key: &str, label: &str, kind: AttributeKind) -> Self {
+        AttributeDef {
+            key: key.trim().to_string(),
+            label: label.trim().to_string(),
+            kind,
+            required: false,
+            min: None,
+            max: None,
+            choices: Vec::new(),
+            unit: String::new(),
+        }
+    }
+
+    /// Check that the definition itself can be used: a valid key, a range that is not
+    /// upside down, and choices for a choice list
+    pub fn check(&self) -> Result<()> {
+        let invalid = |reason: &str| Err(Error::InvalidAttributeDef { key: self.key.clone(), reason: reason.to_string() });
+        let mut chars = self.key.chars();
+        let key_ok = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+            && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            && self.key.len() <= MAX_KEY_LEN;
+        if !key_ok {
+            return invalid("lykill á að vera lágstafir, tölustafir og _, t.d. skjastaerd");
+        }
+        if self.label.is_empty() {
+            return invalid("heiti vantar");
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                return invalid("lágmark er hærra en hámark");
+            }
+        }
+        if self.kind == AttributeKind::Choice && self.choices.iter().all(|c| c.trim().is_empty()) {
+            return invalid("valmöguleika vantar");
+        }
+        Ok(())
+    }
+
+    /// Read a value as typed. Empty text is `None` for optional attributes.
+    pub fn parse(&self, text: &str) -> Result<Option<AttributeValue>> {
+        let text = text.trim();
+        if text.is_empty() {
+            if self.required {
+                return Err(Error::MissingAttribute(self.label.clone()));
+            }
+            return Ok(None);
+        }
+        let invalid = || Error::InvalidAttribute { label: self.label.clone(), value: text.to_string() };
+        let value = match self.kind {
+            AttributeKind::Number => {
+                let n: i64 = text.parse().map_err(|_| invalid())?;
+                if self.min.is_some_and(|min| n < min) || self.max.is_some_and(|max| n > max) {
+                    return Err(Error::AttributeOutOfRange { label: self.label.clone(), value: n, min: self.min, max: self.max });
+                }
+                AttributeValue::Number(n)
+            }
+            AttributeKind::Text => AttributeValue::Text(text.to_string()),
+            AttributeKind::Choice => {
+                let choice = self.choices.iter().find(|c| c.trim().to_lowercase() == text.to_lowercase()).ok_or_else(invalid)?;
+                AttributeValue::Text(choice.trim().to_string())
+            }
+            AttributeKind::YesNo => match text.to_lowercase().as_str() {
+                "já" | "ja" | "yes" | "true" | "1" => AttributeValue::YesNo(true),
+                "nei" | "no" | "false" | "0" => AttributeValue::YesNo(false),
+                _ => return Err(invalid()),
+            },
+        };
+        Ok(Some(value))
+    }
+
+    /// Value with its unit, for lists and reports, e.g. "27 tommur"
+    pub fn format(&self, value: &AttributeValue) -> String {
+        match (value, self.unit.is_empty()) {
+            (AttributeValue::Number(n), false) => format!("{} {}", n, self.unit),
+            _ => value.to_string(),
+        }
+    }
+
+    /// Allowed range as text for forms, e.g. "10–100"; empty when there is none
+    pub fn range_text(&self) -> String {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => format!("{}–{}", min, max),
+            (Some(min), None) => format!("≥ {}", min),
+            (None, Some(max)) => format!("≤ {}", max),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// Value of one attribute; stored as JSON in the `equipment.attributes` column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AttributeValue {
+    YesNo(bool),
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeValue::YesNo(true) => write!(f, "Já"),
+            AttributeValue::YesNo(false) => write!(f, "Nei"),
+            AttributeValue::Number(n) => write!(f, "{}", n),
+            AttributeValue::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What kind of value a category attribute holds, which decides how it is typed in and checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttributeKind {
+    Number, // Whole number, optionally within a range
+    Text,   // Free text
+    Choice, // One of a fixed list
+    YesNo,  // Já / Nei
+}
+
+impl AttributeKind {
+    pub fn all() -> Vec<AttributeKind> {
+        vec![AttributeKind::Number, AttributeKind::Text, AttributeKind::Choice, AttributeKind::YesNo]
+    }
+
+    /// Code stored in the `category_attributes.kind` column
+    pub fn to_code(self) -> &'static str {
+        match self {
+            AttributeKind::Number => "Number",
+            AttributeKind::Text => "Text",
+            AttributeKind::Choice => "Choice",
+            AttributeKind::YesNo => "YesNo",
+        }
+    }
+}
+
+impl fmt::Display for AttributeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AttributeKind::Number => "Tala",
+                AttributeKind::Text => "Texti",
+                AttributeKind::Choice => "Val",
+                AttributeKind::YesNo => "Já/Nei",
+            }
+        )
+    }
+}
+
+impl TryFrom<&str> for AttributeKind {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        // Accept stored codes and Icelandic names, any case
+        match value.trim().to_lowercase().as_str() {
+            "number" | "tala" => Ok(AttributeKind::Number),
+            "text" | "texti" => Ok(AttributeKind::Text),
+            "choice" | "val" => Ok(AttributeKind::Choice),
+            "yesno" | "já/nei" | "ja/nei" | "janei" => Ok(AttributeKind::YesNo),
+            _ => Err(Error::UnknownAttributeKind(value.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for AttributeKind {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        AttributeKind::try_from(value.as_str())
+    }
+}
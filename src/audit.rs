@@ -0,0 +1,156 @@
+//! Stocktake: walk a room, scan or type the IDs of what is there, and compare
+//! that with what is registered in the room.
+//!
+//! Sessions and their scans are stored by `Database` so an audit can be picked
+//! up again later. When a session is finished its `AuditReport` is saved with it,
+//! so the report stays as it was on the day even if items are moved afterwards.
+
+use crate::database::Database;
+use crate::equipment::Equipment;
+use crate::error::Result;
+use crate::location::Location;
+use serde::{Deserialize, Serialize};
+
+/// One scanned or typed entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditScan {
+    pub id: i64,
+    /// Text as scanned, e.g. `12` or a label's `12;Borð;H-2.12`
+    pub text: String,
+    /// ID read from `text`, if any
+    pub equipment_id: Option<i64>,
+    /// Local time, `YYYY-MM-DD HH:MM:SS`
+    pub scanned_at: String,
+}
+
+/// Stocktake of one room
+#[derive(Debug, Clone)]
+pub struct AuditSession {
+    pub id: i64,
+    pub location: Location,
+    pub user: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    /// In the order they were scanned
+    pub scans: Vec<AuditScan>,
+    /// Saved when the session is finished
+    pub report: Option<AuditReport>,
+}
+
+impl AuditSession {
+    pub fn is_finished(&self) -> bool {
+        self.finished_at.is_some()
+    }
+
+    /// The saved report of a finished session, otherwise one compared with the database now
+    pub fn report(&self, db: &Database) -> Result<AuditReport> {
+        match &self.report {
+            Some(report) => Ok(report.clone()),
+            None => AuditReport::new(db, self),
+        }
+    }
+}
+
+/// Differences between what was scanned in a room and what is registered there
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuditReport {
+    /// Registered in the room and scanned
+    pub found: Vec<Equipment>,
+    /// Registered in the room but not scanned
+    pub missing: Vec<Equipment>,
+    /// Scanned in the room but registered somewhere else (their location is the registered one)
+    pub misplaced: Vec<Equipment>,
+    /// Scanned text with no active item behind it
+    pub unknown: Vec<String>,
+}
+
+impl AuditReport {
+    /// Compare the session's scans with the room's equipment. Scanning an item twice counts once.
+    pub fn new(db: &Database, session: &AuditSession) -> Result<Self> {
+        let loc = &session.location;
+        let expected = db.get_equipment_by_room(loc)?;
+        let mut report = AuditReport::default();
+        let mut seen = Vec::new();
+        for scan in &session.scans {
+            let Some(id) = scan.equipment_id else {
+                report.unknown.push(scan.text.clone());
+                continue;
+            };
+            if seen.contains(&id) {
+                continue;
+            }
+            seen.push(id);
+            if let Some(eq) = expected.iter().find(|e| e.get_id() == Some(id)) {
+                report.found.push(eq.clone());
+            } else if let Some(eq) = db.get_equipment_by_id(id)? {
+                report.misplaced.push(eq);
+            } else {
+                report.unknown.push(scan.text.clone());
+            }
+        }
+        report.missing = expected
+            .into_iter()
+            .filter(|e| e.get_id().is_some_and(|id| !seen.contains(&id)))
+            .collect();
+        Ok(report)
+    }
+
+    /// True when everything registered was found and nothing else turned up
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.misplaced.is_empty() && self.unknown.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} fundust, {} vantar, {} skráðir annars staðar, {} óþekkt",
+            self.found.len(),
+            self.missing.len(),
+            self.misplaced.len(),
+            self.unknown.len()
+        )
+    }
+}
+
+/// ID in scanned text: a plain number, `#12`, or the first field of a label's QR code
+pub fn parse_scan(text: &str) -> Option<i64> {
+    let first = text.trim().split(';').next()?.trim();
+    first.strip_prefix('#').unwrap_or(first).parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::building::Building;
+    use crate::table::Table;
+
+    #[test]
+    fn report_sorts_scans_against_the_room() {
+        let db = Database::with_rooms(&["H-212", "S-11"]);
+        let room = Location::new(Building::new("H").unwrap(), 2, 12);
+        let table = Equipment::Table(Table::new(room.clone(), 45000, 4));
+        let here = db.insert_equipment(&table).unwrap();
+        let gone = db.insert_equipment(&table).unwrap();
+        let mut elsewhere = table.clone();
+        elsewhere.set_location(Location::new(Building::new("S").unwrap(), 1, 1));
+        let moved = db.insert_equipment(&elsewhere).unwrap();
+
+        let audit = db.start_audit(&room).unwrap();
+        for text in [format!("{};Borð;H-212", here), format!("#{}", here), moved.to_string(), "999".into(), "kaffi".into()] {
+            db.add_audit_scan(audit, &text).unwrap();
+        }
+        let session = db.get_audit(audit).unwrap().unwrap();
+        let report = session.report(&db).unwrap();
+        assert_eq!(report.found.iter().map(|e| e.get_id()).collect::<Vec<_>>(), [Some(here)]);
+        assert_eq!(report.missing.iter().map(|e| e.get_id()).collect::<Vec<_>>(), [Some(gone)]);
+        assert_eq!(report.misplaced.iter().map(|e| e.get_id()).collect::<Vec<_>>(), [Some(moved)]);
+        assert_eq!(report.unknown, ["999", "kaffi"]);
+        assert!(crate::pdf::audit_report(&session, &report).unwrap().starts_with(b"%PDF"));
+
+        // A finished audit keeps its report even when items move afterwards
+        db.finish_audit(audit, &report).unwrap();
+        db.update_location(moved, &room).unwrap();
+        let session = db.get_audit(audit).unwrap().unwrap();
+        assert_eq!(session.report(&db).unwrap(), report);
+        assert!(db.add_audit_scan(audit, "1").is_err());
+    }
+}
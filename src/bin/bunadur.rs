@@ -0,0 +1,1164 @@
+//! `bunadur` – headless command-line interface to the equipment database.
+//!
+//! Uses the same `Database` methods and `Location` parsing as the GUI, so it
+//! can be run from a terminal or from cron jobs without opening a window.
+
+use bunadarlisti::{
+    AttributeDef, AttributeKind, Building, BuildingInfo, Category, Chair, ChairType, Computer, ComputerKind, Condition, CustomEquipment,
+    Database,
+    Equipment, EquipmentInfo, Error, ImportMode, ImportPlan, Location, Monitor, Projector, Resolution, RetireReason, Room, RoomSeating,
+    RoomType, Statistics, Table, Tally,
+};
+use bunadarlisti::category;
+use bunadarlisti::csv_file;
+use bunadarlisti::equipment::parse_id_list;
+use bunadarlisti::depreciation;
+use bunadarlisti::equipment_info;
+use bunadarlisti::history::describe_changes;
+use bunadarlisti::location;
+use bunadarlisti::monitor;
+use bunadarlisti::pdf;
+use bunadarlisti::print;
+use bunadarlisti::seating;
+use bunadarlisti::xlsx_export;
+use clap::{Args, Parser, Subcommand};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "bunadur", version, about = "Búnaðarlisti Tækniskólans – skipanalína")]
+struct Cli {
+    /// Slóð á SQLite gagnagrunninn
+    #[arg(long, global = true, default_value = "equipment.db")]
+    db: String,
+
+    /// Nafn sem er skráð í sögu breytinga (sjálfgefið notandi stýrikerfisins)
+    #[arg(long, global = true)]
+    user: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Skrá nýjan búnað
+    Add {
+        #[command(subcommand)]
+        kind: AddKind,
+    },
+    /// Flytja búnað á nýja staðsetningu, t.d. `bunadur move 17 S-3.10`
+    Move {
+        id: i64,
+        location: String,
+    },
+    /// Skrá ástand búnaðar, t.d. `bunadur condition 17 "þarf viðgerð"`
+    Condition {
+        id: i64,
+        /// Nýtt, Gott, Slitið, Þarf viðgerð eða Úr notkun (new, good, worn, needsrepair, outofservice)
+        condition: String,
+    },
+    /// Afskrá búnað (fer í ruslafötu og hægt er að endurheimta hann)
+    Delete {
+        id: i64,
+        /// Bilað, Selt, Týnt, Afskrifað eða Annað (broken, sold, lost, writtenoff, other)
+        #[arg(long, default_value = "other")]
+        reason: String,
+    },
+    /// Endurheimta búnað úr ruslafötu
+    Restore {
+        id: i64,
+    },
+    /// Eyða búnaði varanlega (líka úr ruslafötu)
+    Purge {
+        id: i64,
+    },
+    /// Sýna innihald ruslafötu
+    Bin,
+    /// Sýna einn búnað
+    Show {
+        id: i64,
+    },
+    /// Lista búnað, valfrjálst síað eftir húsi, hæð, stofu, tegund eða ástandi
+    List {
+        /// Kóði eða nafn húss, t.d. H eða Háteigsvegur
+        #[arg(long)]
+        building: Option<String>,
+        /// Hæð, t.d. 2 eða K1 fyrir kjallara (krefst --building)
+        #[arg(long, requires = "building", value_parser = floor_arg)]
+        floor: Option<i8>,
+        /// Herbergisnúmer (krefst --building og --floor)
+        #[arg(long, requires = "floor")]
+        room: Option<u16>,
+        /// Heil staðsetning, t.d. H-2.02 eða H-2.02a
+        #[arg(long, conflicts_with_all = ["building", "floor", "room"])]
+        location: Option<String>,
+        /// Tegund búnaðar: table, chair, projector, computer, monitor eða kóði/nafn flokks
+        #[arg(long = "type")]
+        kind: Option<String>,
+        /// Aðeins búnaður í þessu ástandi, t.d. "úr notkun"
+        #[arg(long)]
+        condition: Option<String>,
+        /// Skrifa út sem JSON í stað töflu
+        #[arg(long)]
+        json: bool,
+        /// Skrifa út sem CSV (fyrir töflureikni) í stað töflu
+        #[arg(long, conflicts_with = "json")]
+        csv: bool,
+        /// Vista listann sem PDF skýrslu í þessa skrá
+        #[arg(long, value_name = "SKRÁ", conflicts_with_all = ["json", "csv"])]
+        pdf: Option<String>,
+        /// Vista QR merkimiða fyrir listann í þessa PDF skrá (A4, 3 x 8)
+        #[arg(long, value_name = "SKRÁ", conflicts_with_all = ["json", "csv", "pdf"])]
+        labels: Option<String>,
+        /// Senda PDF skýrslu listans beint á prentara (lpr/CUPS)
+        #[arg(long, conflicts_with_all = ["json", "csv", "pdf", "labels"])]
+        print: bool,
+        /// Prentari fyrir --print (sjálfgefinn prentari ef sleppt)
+        #[arg(long, requires = "print")]
+        printer: Option<String>,
+    },
+    /// Sýna sögu eins búnaðar (skráning, flutningar, breytingar, eyðing)
+    History {
+        id: i64,
+    },
+    /// Finna færslur í gagnagrunni sem ekki er hægt að lesa
+    Check,
+    /// Leita í öllum dálkum (ID, tegund, staðsetningu, verðmæti, lýsingu, eignanúmeri, raðnúmeri, gerð, ástandi, athugasemdum)
+    Search {
+        query: String,
+        /// Skrifa út sem JSON í stað töflu
+        #[arg(long)]
+        json: bool,
+    },
+    /// Vista allan búnað í Excel skjal (yfirlit og eitt blað á hvert hús)
+    Xlsx {
+        file: String,
+    },
+    /// Prenta QR merkimiða (A4, 3 x 8) fyrir valin ID, t.d. `bunadur labels m.pdf --ids 1-24`
+    Labels {
+        file: String,
+        /// ID listi, t.d. 1-5,12
+        #[arg(long)]
+        ids: String,
+        /// Fjöldi merkimiða sem þegar hafa verið notaðir af fyrstu örkinni
+        #[arg(long, default_value_t = 0)]
+        skip: usize,
+    },
+    /// Úttekt (talning) á stofu: skanna ID og bera saman við skráðan búnað
+    Audit {
+        #[command(subcommand)]
+        action: AuditAction,
+    },
+    /// Prenta stofublöð (PDF) fyrir allar stofur í húsi eða á einni hæð
+    Sheets {
+        /// Kóði eða nafn húss, t.d. H eða Háteigsvegur
+        #[arg(long)]
+        building: String,
+        /// Aðeins þessi hæð, t.d. 2 eða K1
+        #[arg(long, value_parser = floor_arg)]
+        floor: Option<i8>,
+        /// Öll blöðin í eina PDF skrá
+        #[arg(long, value_name = "SKRÁ", required_unless_present = "dir", conflicts_with = "dir")]
+        out: Option<String>,
+        /// Ein PDF skrá á hverja stofu í þessa möppu, t.d. H-2.12.pdf
+        #[arg(long, value_name = "MAPPA")]
+        dir: Option<String>,
+    },
+    /// Skoða og breyta húsum sem búnaður getur verið skráður í
+    Buildings {
+        #[command(subcommand)]
+        action: Option<BuildingAction>,
+    },
+    /// Skoða og breyta stofum sem búnaður getur verið skráður í
+    Rooms {
+        #[command(subcommand)]
+        action: Option<RoomAction>,
+    },
+    /// Skoða og breyta flokkum búnaðar og eiginleikum þeirra
+    Categories {
+        #[command(subcommand)]
+        action: Option<CategoryAction>,
+    },
+    /// Bera saman sæti við borð og stóla í hverri stofu
+    Seating {
+        /// Kóði eða nafn húss
+        #[arg(long)]
+        building: Option<String>,
+        /// Sýna líka stofur sem eru í lagi
+        #[arg(long)]
+        all: bool,
+    },
+    /// Innkaupsverð og bókfært verð eftir tegund og húsi á tilteknum degi, t.d. `bunadur value --date 31.12.2025`
+    Value {
+        /// Dagsetning, t.d. 31.12.2025 (sjálfgefið í dag)
+        #[arg(long)]
+        date: Option<String>,
+        /// Sýna líka bókfært verð hvers búnaðar
+        #[arg(long)]
+        items: bool,
+    },
+    /// Skoða og breyta afskriftartíma hverrar tegundar
+    Depreciation {
+        #[command(subcommand)]
+        action: Option<DepreciationAction>,
+    },
+    /// Flytja inn búnað úr JSON eða CSV skrá og sýna mismun við gagnagrunninn
+    Import {
+        file: String,
+        /// merge (sameina), replace (skipta út) eða append (bæta við sem nýjum)
+        #[arg(long, default_value = "merge")]
+        mode: String,
+        /// Sýna aðeins mismuninn, engu er breytt
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AddKind {
+    /// Borð
+    Table {
+        #[arg(long)]
+        location: String,
+        #[arg(long)]
+        value: u32,
+        #[arg(long)]
+        seats: u8,
+        #[command(flatten)]
+        info: InfoArgs,
+    },
+    /// Stóll
+    Chair {
+        #[arg(long)]
+        location: String,
+        #[arg(long)]
+        value: u32,
+        /// Hægindastóll, Skólastóll, Skrifstofustóll eða Annað
+        #[arg(long = "chair-type", default_value = "Skólastóll")]
+        chair_type: String,
+        #[command(flatten)]
+        info: InfoArgs,
+    },
+    /// Skjávarpi
+    Projector {
+        #[arg(long)]
+        location: String,
+        #[arg(long)]
+        value: u32,
+        #[arg(long)]
+        lumens: u32,
+        #[command(flatten)]
+        info: InfoArgs,
+    },
+    /// Tölva, t.d. `bunadur add computer --location H-2.12 --value 250000 --serial 5CG1234 --ram 16`
+    Computer {
+        #[arg(long)]
+        location: String,
+        #[arg(long)]
+        value: u32,
+        /// Fartölva eða Borðtölva
+        #[arg(long, default_value = "Fartölva")]
+        kind: String,
+        #[arg(long, default_value = "")]
+        cpu: String,
+        /// Vinnsluminni í GB
+        #[arg(long = "ram")]
+        ram_gb: Option<u16>,
+        #[command(flatten)]
+        info: InfoArgs,
+    },
+    /// Skjár, t.d. `bunadur add monitor --location H-2.12 --value 60000 --serial CN0X1 --size 27 --resolution 2560x1440`
+    Monitor {
+        #[arg(long)]
+        location: String,
+        #[arg(long)]
+        value: u32,
+        /// Skjástærð í tommum, t.d. 23.8
+        #[arg(long = "size")]
+        screen_size: String,
+        #[arg(long, default_value = "1920x1080")]
+        resolution: String,
+        #[command(flatten)]
+        info: InfoArgs,
+    },
+    /// Búnaður í flokki, t.d. `bunadur add item --category Tafla --location H-2.12 --value 30000 --attr breidd=240`
+    Item {
+        /// Kóði eða nafn flokks
+        #[arg(long)]
+        category: String,
+        #[arg(long)]
+        location: String,
+        #[arg(long)]
+        value: u32,
+        /// Gildi eiginleika, má endurtaka
+        #[arg(long = "attr", value_name = "LYKILL=GILDI")]
+        attributes: Vec<String>,
+        #[command(flatten)]
+        info: InfoArgs,
+    },
+}
+
+/// Upplýsingar sem allur búnaður getur haft
+#[derive(Args)]
+struct InfoArgs {
+    /// Eignanúmer skólans, t.d. TS-004211
+    #[arg(long = "asset-tag", default_value = "")]
+    asset_tag: String,
+    /// Raðnúmer framleiðanda
+    #[arg(long = "serial", default_value = "")]
+    serial_number: String,
+    #[arg(long, default_value = "")]
+    manufacturer: String,
+    #[arg(long, default_value = "")]
+    model: String,
+    /// Innkaupsdagur, t.d. 15.08.2024
+    #[arg(long = "purchased", value_name = "DAGSETNING")]
+    purchase_date: Option<String>,
+    #[arg(long, default_value = "")]
+    supplier: String,
+    /// Reikningsnúmer birgis
+    #[arg(long = "invoice", default_value = "")]
+    invoice_number: String,
+    /// Nýtt, Gott, Slitið, Þarf viðgerð eða Úr notkun
+    #[arg(long, default_value = "gott")]
+    condition: String,
+    /// Athugasemdir, t.d. "vinstri fótur laus"
+    #[arg(long, default_value = "")]
+    notes: String,
+}
+
+impl TryFrom<InfoArgs> for EquipmentInfo {
+    type Error = Error;
+
+    fn try_from(args: InfoArgs) -> Result<Self, Self::Error> {
+        let purchase_date = args.purchase_date.as_deref().map(equipment_info::parse_date).transpose()?;
+        Ok(EquipmentInfo {
+            asset_tag: args.asset_tag,
+            serial_number: args.serial_number,
+            manufacturer: args.manufacturer,
+            model: args.model,
+            purchase_date,
+            supplier: args.supplier,
+            invoice_number: args.invoice_number,
+            condition: Condition::try_from(args.condition)?,
+            notes: args.notes,
+        }
+        .trimmed())
+    }
+}
+
+#[derive(Subcommand)]
+enum AuditAction {
+    /// Hefja úttekt á stofu, t.d. `bunadur audit start H-2.12`
+    Start {
+        location: String,
+    },
+    /// Skrá ID sem fundust; án ID er lesin ein lína í einu af stdin (t.d. frá skanna)
+    Scan {
+        audit: i64,
+        ids: Vec<String>,
+    },
+    /// Sýna mismunaskýrslu úttektar
+    Report {
+        audit: i64,
+        /// Vista skýrsluna sem PDF í þessa skrá
+        #[arg(long, value_name = "SKRÁ")]
+        pdf: Option<String>,
+    },
+    /// Ljúka úttekt og vista skýrsluna
+    Finish {
+        audit: i64,
+    },
+    /// Lista allar úttektir
+    List,
+}
+
+#[derive(Subcommand)]
+enum BuildingAction {
+    /// Lista öll hús (sjálfgefið)
+    List,
+    /// Bæta við húsi, t.d. `bunadur buildings add EK --name Eiðistorg`
+    Add {
+        /// 1-5 hástafir, fremst í staðsetningu (EK-101)
+        code: String,
+        #[arg(long)]
+        name: String,
+        #[arg(long, default_value = "")]
+        address: String,
+    },
+    /// Breyta nafni eða heimilisfangi húss
+    Edit {
+        code: String,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        address: Option<String>,
+    },
+    /// Eyða húsi sem enginn búnaður er skráður í
+    Remove {
+        code: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum RoomAction {
+    /// Lista stofur (sjálfgefið)
+    List {
+        /// Aðeins þetta hús
+        #[arg(long)]
+        building: Option<String>,
+        /// Leita í stofu, nafni, tegund eða ábyrgðarmanni
+        #[arg(long)]
+        search: Option<String>,
+    },
+    /// Skrá stofu, t.d. `bunadur rooms add H-2.12 --name "Tölvuver 3" --type Tölvuver --capacity 24`
+    Add {
+        location: String,
+        #[arg(long, default_value = "")]
+        name: String,
+        /// Kennslustofa, Tölvuver, Verkleg stofa, Fyrirlestrasalur, Verkstæði, Skrifstofa eða Annað
+        #[arg(long = "type", default_value = "Kennslustofa")]
+        room_type: String,
+        /// Fjöldi sæta
+        #[arg(long, default_value_t = 0)]
+        capacity: u16,
+        /// Kennari eða starfsmaður sem ber ábyrgð á stofunni
+        #[arg(long, default_value = "")]
+        responsible: String,
+    },
+    /// Breyta upplýsingum um stofu
+    Edit {
+        location: String,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long = "type")]
+        room_type: Option<String>,
+        #[arg(long)]
+        capacity: Option<u16>,
+        #[arg(long)]
+        responsible: Option<String>,
+    },
+    /// Eyða stofu sem enginn búnaður er skráður í
+    Remove {
+        location: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CategoryAction {
+    /// Lista flokka og eiginleika þeirra (sjálfgefið)
+    List,
+    /// Bæta við flokki, t.d. `bunadur categories add Tafla --name "Tafla"`
+    Add {
+        /// Kóði sem er geymdur með búnaðinum, t.d. Tafla eða Hatalari
+        code: String,
+        #[arg(long)]
+        name: String,
+    },
+    /// Breyta heiti flokks
+    Edit {
+        category: String,
+        #[arg(long)]
+        name: String,
+    },
+    /// Bæta eiginleika við flokk eða breyta honum, t.d. `bunadur categories attr Tafla breidd --label Breidd --kind tala --min 50 --unit cm`
+    Attr {
+        category: String,
+        /// Lágstafir, tölustafir og _, notað í --attr og CSV skrám
+        key: String,
+        #[arg(long)]
+        label: String,
+        /// Tala, Texti, Val eða Já/Nei (number, text, choice, yesno)
+        #[arg(long, default_value = "texti")]
+        kind: String,
+        /// Það verður að fylla eiginleikann út
+        #[arg(long)]
+        required: bool,
+        #[arg(long, allow_negative_numbers = true)]
+        min: Option<i64>,
+        #[arg(long, allow_negative_numbers = true)]
+        max: Option<i64>,
+        /// Valmöguleikar aðskildir með kommu, fyrir --kind val
+        #[arg(long, value_delimiter = ',')]
+        choices: Vec<String>,
+        /// Eining á eftir tölum, t.d. cm
+        #[arg(long, default_value = "")]
+        unit: String,
+    },
+    /// Fjarlægja eiginleika úr flokki
+    RemoveAttr {
+        category: String,
+        key: String,
+    },
+    /// Eyða flokki sem enginn búnaður er skráður í
+    Remove {
+        category: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum DepreciationAction {
+    /// Lista afskriftartíma allra tegunda (sjálfgefið)
+    List,
+    /// Afskrifa tegund línulega á þessum árafjölda, t.d. `bunadur depreciation set tölva 3`
+    Set {
+        /// table, chair, projector, computer, monitor eða kóði/nafn flokks
+        kind: String,
+        years: String,
+    },
+    /// Hætta að afskrifa tegund; búnaðurinn heldur innkaupsverði
+    Clear {
+        kind: String,
+    },
+}
+
+/// Type code and plural label for `list --type`: a built-in type, or a category by code or name
+fn resolve_kind(db: &Database, text: &str) -> Result<(String, String), Error> {
+    let builtin = match text.trim().to_lowercase().as_str() {
+        "table" | "borð" | "bord" => Some(("Table", "Borð")),
+        "chair" | "stóll" | "stoll" => Some(("Chair", "Stólar")),
+        "projector" | "skjávarpi" | "skjavarpi" => Some(("Projector", "Skjávarpar")),
+        "computer" | "tölva" | "tolva" => Some(("Computer", "Tölvur")),
+        "monitor" | "skjár" | "skjar" => Some(("Monitor", "Skjáir")),
+        _ => None,
+    };
+    match builtin {
+        Some((code, label)) => Ok((code.to_string(), label.to_string())),
+        None => db.find_category(text).map(|c| (c.code, c.name)),
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut db = match Database::new(&cli.db) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Villa: gat ekki opnað gagnagrunn {}: {}", cli.db, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(user) = &cli.user {
+        db.set_user(user);
+    }
+    match run(&db, cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Villa: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(db: &Database, command: Command) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        Command::Add { kind } => {
+            let equipment = build_equipment(db, kind)?;
+            let id = db.insert_equipment(&equipment)?;
+            println!("Búnaður skráður með ID: {}", id);
+        }
+        Command::Move { id, location } => {
+            let location = Location::try_from(location.as_str())?;
+            require_existing(db, id)?;
+            db.update_location(id, &location)?;
+            println!("Staðsetning uppfærð fyrir búnað með ID {}: {}", id, location);
+        }
+        Command::Condition { id, condition } => {
+            let condition = Condition::try_from(condition)?;
+            let mut equipment = require_existing(db, id)?;
+            let mut info = equipment.info().clone();
+            info.condition = condition;
+            equipment.set_info(info);
+            db.update_equipment(id, &equipment)?;
+            println!("Ástand búnaðar með ID {} skráð: {}", id, condition);
+        }
+        Command::Delete { id, reason } => {
+            let reason = RetireReason::try_from(reason.as_str())?;
+            db.retire_equipment(id, reason)?;
+            println!("Búnaður með ID {} færður í ruslafötu ({})", id, reason);
+        }
+        Command::Restore { id } => {
+            db.restore_equipment(id)?;
+            println!("Búnaður með ID {} endurheimtur", id);
+        }
+        Command::Purge { id } => {
+            db.delete_equipment(id)?;
+            println!("Búnaði með ID {} eytt varanlega", id);
+        }
+        Command::Bin => {
+            let items = db.get_retired_equipment()?;
+            for item in &items {
+                println!("{}  [{}, {}]", item.equipment, item.reason, item.retired_at);
+            }
+            println!("Fjöldi: {} atriði", items.len());
+        }
+        Command::Show { id } => {
+            let equipment = require_existing(db, id)?;
+            println!("{}", equipment);
+            for (label, value) in equipment.info().details() {
+                println!("  {}: {}", label, value);
+            }
+        }
+        Command::List { building, floor, room, location, kind, condition, json, csv, pdf, labels, print, printer } => {
+            // Description of the filters for the PDF header
+            let mut filter = Vec::new();
+            let kind = kind.map(|k| resolve_kind(db, &k)).transpose()?;
+            let condition = condition.map(Condition::try_from).transpose()?;
+            let mut items = match (location, building) {
+                (Some(loc), _) => {
+                    let loc = Location::try_from(loc.as_str())?;
+                    filter.push(format!("Stofa: {}", loc));
+                    db.get_equipment_by_room(&loc)
+                }
+                (None, Some(text)) => {
+                    let info = db.find_building(&text)?;
+                    let building = info.building.clone();
+                    match (floor, room) {
+                        (Some(f), Some(r)) => {
+                            let loc = Location::try_from((building, f, r))?;
+                            filter.push(format!("Stofa: {}", loc));
+                            db.get_equipment_by_room(&loc)
+                        }
+                        (Some(f), None) => {
+                            filter.push(format!("Hæð: {}, {}", info.name, location::floor_name(f)));
+                            db.get_equipment_by_floor(&building, f)
+                        }
+                        _ => {
+                            filter.push(format!("Hús: {}", info.name));
+                            db.get_equipment_by_building(&building)
+                        }
+                    }
+                }
+                (None, None) => match (&kind, condition) {
+                    (Some((code, _)), _) => db.get_equipment_by_type(code),
+                    (None, Some(condition)) => db.get_equipment_by_condition(condition),
+                    (None, None) => db.get_all_equipment(),
+                },
+            }?;
+            if let Some((code, label)) = kind {
+                items.retain(|e| e.type_code() == code);
+                filter.push(format!("Tegund: {}", label));
+            }
+            if let Some(condition) = condition {
+                items.retain(|e| e.info().condition == condition);
+                filter.push(format!("Ástand: {}", condition));
+            }
+            let filter = if filter.is_empty() { "Allur búnaður".to_string() } else { filter.join(", ") };
+            if let Some(path) = pdf {
+                std::fs::write(&path, pdf::equipment_report(&items, &filter)?)?;
+                println!("{} atriði vistuð í {}", items.len(), path);
+                return Ok(());
+            }
+            if print {
+                let path = std::env::temp_dir().join("bunadur_prenta.pdf");
+                std::fs::write(&path, pdf::equipment_report(&items, &filter)?)?;
+                print::print_file(&path, printer.as_deref())?;
+                println!("{} atriði send á {}", items.len(), printer.as_deref().unwrap_or("sjálfgefinn prentara"));
+                return Ok(());
+            }
+            if let Some(path) = labels {
+                std::fs::write(&path, pdf::equipment_labels(&items, 0)?)?;
+                println!("{} merkimiðar vistaðir í {}", items.len(), path);
+                return Ok(());
+            }
+            if csv {
+                csv_file::write_csv(std::io::stdout().lock(), &items)?;
+                return Ok(());
+            }
+            print_items(&items, json)?;
+        }
+        Command::History { id } => {
+            let history = db.get_history(id)?;
+            if history.is_empty() {
+                return Err(Error::NotFound(id).into());
+            }
+            for entry in &history {
+                println!("{}", entry);
+            }
+        }
+        Command::Check => {
+            let corrupt = db.find_corrupt_rows()?;
+            for e in &corrupt {
+                println!("{}", e);
+            }
+            if !corrupt.is_empty() {
+                return Err(format!("{} skemmdar færslur fundust", corrupt.len()).into());
+            }
+            println!("Engar skemmdar færslur");
+        }
+        Command::Search { query, json } => {
+            let items: Vec<Equipment> = db
+                .get_all_equipment()?
+                .into_iter()
+                .filter(|e| e.matches_query(&query))
+                .collect();
+            print_items(&items, json)?;
+        }
+        Command::Xlsx { file } => {
+            let items = db.get_all_equipment()?;
+            xlsx_export::write_xlsx(std::path::Path::new(&file), &items, &db.get_buildings()?)?;
+            println!("{} atriði vistuð í {}", items.len(), file);
+        }
+        Command::Labels { file, ids, skip } => {
+            let mut items = Vec::new();
+            for id in parse_id_list(&ids)? {
+                items.push(require_existing(db, id)?);
+            }
+            if items.is_empty() {
+                return Err(Error::InvalidIdList(ids).into());
+            }
+            std::fs::write(&file, pdf::equipment_labels(&items, skip)?)?;
+            println!("{} merkimiðar vistaðir í {}", items.len(), file);
+        }
+        Command::Audit { action } => run_audit(db, action)?,
+        Command::Buildings { action } => run_buildings(db, action.unwrap_or(BuildingAction::List))?,
+        Command::Seating { building, all } => {
+            let building = building.map(|b| db.find_building(&b)).transpose()?.map(|info| info.building);
+            let report: Vec<RoomSeating> = seating::seating_report(&db.get_all_equipment()?, &db.get_rooms()?)
+                .into_iter()
+                .filter(|r| building.as_ref().is_none_or(|b| &r.location.building == b))
+                .collect();
+            println!("{:<8}  {:<20}  {:>5}  {:>8}  {:>6}  Staða", "Stofa", "Nafn", "Rúmar", "Borðsæti", "Stólar");
+            for room in report.iter().filter(|r| all || !r.is_ok()) {
+                let capacity = if room.capacity > 0 { room.capacity.to_string() } else { "–".to_string() };
+                println!(
+                    "{:<8}  {:<20}  {:>5}  {:>8}  {:>6}  {}",
+                    room.location.to_string(),
+                    room.name,
+                    capacity,
+                    room.seats,
+                    room.chairs,
+                    room.status()
+                );
+            }
+            let moves = seating::suggest_moves(&report);
+            if !moves.is_empty() {
+                println!("\nTillögur að flutningi stóla:");
+                for m in moves {
+                    println!("  {}", m);
+                }
+            }
+        }
+        Command::Value { date, items } => {
+            let date = match date {
+                Some(text) => equipment_info::parse_date(&text)?,
+                None => chrono::Local::now().date_naive(),
+            };
+            let equipment = db.get_all_equipment()?;
+            let depreciation = db.get_depreciation()?;
+            if items {
+                println!("{:>6}  {:<10}  {:<8}  {:>10}  {:>16}  {:>16}", "ID", "Tegund", "Staður", "Keypt", "Innkaupsverð", "Bókfært verð");
+                for eq in &equipment {
+                    println!(
+                        "{:>6}  {:<10}  {:<8}  {:>10}  {:>16}  {:>16}",
+                        eq.get_id().unwrap_or(0),
+                        eq.get_type_name(),
+                        eq.get_location().to_string(),
+                        eq.info().purchase_date.map(equipment_info::format_date).unwrap_or_default(),
+                        pdf::format_isk(u64::from(eq.get_value())),
+                        pdf::format_isk(u64::from(depreciation.book_value(eq, date)))
+                    );
+                }
+                println!();
+            }
+            let stats = Statistics::as_of(&equipment, &db.get_buildings()?, &depreciation, date);
+            println!("Bókfært verð þann {}", equipment_info::format_date(date));
+            let row = |label: &str, tally: &Tally| {
+                println!(
+                    "{:<24}  {:>6}  {:>18}  {:>18}",
+                    label,
+                    tally.count,
+                    pdf::format_isk(tally.value),
+                    pdf::format_isk(tally.book_value)
+                )
+            };
+            println!("{:<24}  {:>6}  {:>18}  {:>18}", "Tegund", "Fjöldi", "Innkaupsverð", "Bókfært verð");
+            for (type_name, tally) in stats.by_type.iter().filter(|(_, t)| t.count > 0) {
+                row(type_name, tally);
+            }
+            println!("\n{:<24}  {:>6}  {:>18}  {:>18}", "Hús", "Fjöldi", "Innkaupsverð", "Bókfært verð");
+            for (building, tally) in stats.by_building.iter().filter(|(_, t)| t.count > 0) {
+                row(&building.name, tally);
+            }
+            println!();
+            row("Samtals", &stats.total);
+        }
+        Command::Depreciation { action } => run_depreciation(db, action.unwrap_or(DepreciationAction::List))?,
+        Command::Categories { action } => run_categories(db, action.unwrap_or(CategoryAction::List))?,
+        Command::Rooms { action } => run_rooms(db, action.unwrap_or(RoomAction::List { building: None, search: None }))?,
+        Command::Sheets { building, floor, out, dir } => {
+            let building = db.find_building(&building)?.building;
+            let buildings = db.get_buildings()?;
+            let mut rooms = Vec::new();
+            for loc in db.get_occupied_rooms(&building, floor)? {
+                let items = db.get_equipment_by_room(&loc)?;
+                rooms.push((loc, items));
+            }
+            if rooms.is_empty() {
+                return Err("Enginn búnaður er skráður á þessum stað".into());
+            }
+            match (out, dir) {
+                (Some(path), _) => {
+                    std::fs::write(&path, pdf::room_sheets(&rooms, &buildings)?)?;
+                    println!("{} stofublöð vistuð í {}", rooms.len(), path);
+                }
+                (None, Some(dir)) => {
+                    let files = pdf::write_room_sheets_to_dir(std::path::Path::new(&dir), &rooms, &buildings)?;
+                    println!("{} stofublöð vistuð í {}", files.len(), dir);
+                }
+                (None, None) => unreachable!("clap requires --out or --dir"),
+            }
+        }
+        Command::Import { file, mode, dry_run } => {
+            let mode = ImportMode::try_from(mode.as_str())?;
+            let items: Vec<Equipment> = if file.to_lowercase().ends_with(".csv") {
+                let (items, errors) = csv_file::read_csv(std::fs::File::open(&file)?, &db.get_categories()?)?;
+                for e in &errors {
+                    eprintln!("✗ {}", e);
+                }
+                items
+            } else {
+                serde_json::from_str(&std::fs::read_to_string(&file)?)?
+            };
+            let plan = ImportPlan::new(db, items)?;
+            for e in &plan.invalid {
+                eprintln!("✗ {}", e);
+            }
+            for eq in &plan.new {
+                println!("+ {}", eq);
+            }
+            for (old, new) in &plan.changed {
+                println!("~ ID {}: {}", new.get_id().unwrap_or(0), describe_changes(old, new));
+            }
+            for c in &plan.conflicts {
+                println!("! ID {} – {}: {}", c.id, c.kind, c.incoming);
+            }
+            for eq in &plan.missing {
+                println!("- {}", eq);
+            }
+            println!("{}", plan.summary());
+            if !dry_run {
+                let changes = plan.apply(db, mode)?;
+                println!("{}: {} breytingar vistaðar", mode, changes.len());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_audit(db: &Database, action: AuditAction) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        AuditAction::Start { location } => {
+            let location = Location::try_from(location.as_str())?;
+            let id = db.start_audit(&location)?;
+            let expected = db.get_equipment_by_room(&location)?;
+            println!("Úttekt {} hafin á {} ({} atriði skráð þar)", id, location, expected.len());
+        }
+        AuditAction::Scan { audit, ids } => {
+            let scans = if ids.is_empty() {
+                std::io::stdin().lines().collect::<Result<Vec<_>, _>>()?
+            } else {
+                ids
+            };
+            let mut count = 0;
+            for text in scans.iter().filter(|t| !t.trim().is_empty()) {
+                db.add_audit_scan(audit, text)?;
+                count += 1;
+            }
+            println!("{} skráð í úttekt {}", count, audit);
+        }
+        AuditAction::Report { audit, pdf } => {
+            let session = db.get_audit(audit)?.ok_or(Error::AuditNotFound(audit))?;
+            let report = session.report(db)?;
+            if let Some(path) = pdf {
+                std::fs::write(&path, pdf::audit_report(&session, &report)?)?;
+                println!("Skýrsla vistuð í {}", path);
+                return Ok(());
+            }
+            println!("Úttekt {} á {} ({}, hafin {})", session.id, session.location, session.user, session.started_at);
+            for eq in &report.missing {
+                println!("- vantar: {}", eq);
+            }
+            for eq in &report.misplaced {
+                println!("→ skráð í {}: {}", eq.get_location(), eq);
+            }
+            for text in &report.unknown {
+                println!("? óþekkt: {}", text);
+            }
+            for eq in &report.found {
+                println!("✓ {}", eq);
+            }
+            println!("{}", report.summary());
+        }
+        AuditAction::Finish { audit } => {
+            let session = db.get_audit(audit)?.ok_or(Error::AuditNotFound(audit))?;
+            let report = session.report(db)?;
+            db.finish_audit(audit, &report)?;
+            println!("Úttekt {} lokið: {}", audit, report.summary());
+        }
+        AuditAction::List => {
+            for session in db.get_audits()? {
+                let status = session.finished_at.as_deref().map(|f| format!("lokið {}", f)).unwrap_or_else(|| "opin".to_string());
+                println!("{:>4}  {:<8}  {}  {} ({}, {} skannanir)", session.id, session.location.to_string(), session.started_at, status, session.user, session.scans.len());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_buildings(db: &Database, action: BuildingAction) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        BuildingAction::List => {
+            for info in db.get_buildings()? {
+                let count = db.get_equipment_by_building(&info.building)?.len();
+                println!("{:<5}  {:<20}  {:<30}  {} atriði", info.building.to_code(), info.name, info.address, count);
+            }
+        }
+        BuildingAction::Add { code, name, address } => {
+            let info = BuildingInfo { building: Building::new(&code)?, name, address };
+            db.add_building(&info)?;
+            println!("Húsi bætt við: {} ({})", info.name, info.building);
+        }
+        BuildingAction::Edit { code, name, address } => {
+            let building = Building::new(&code)?;
+            let mut info = db.get_building(&building)?.ok_or(Error::UnknownBuilding(code))?;
+            if let Some(name) = name {
+                info.name = name;
+            }
+            if let Some(address) = address {
+                info.address = address;
+            }
+            db.update_building(&info)?;
+            println!("Hús uppfært: {} ({}), {}", info.name, info.building, info.address);
+        }
+        BuildingAction::Remove { code } => {
+            let building = Building::new(&code)?;
+            db.delete_building(&building)?;
+            println!("Húsi {} eytt", building);
+        }
+    }
+    Ok(())
+}
+
+fn run_rooms(db: &Database, action: RoomAction) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        RoomAction::List { building, search } => {
+            let building = building.map(|b| db.find_building(&b)).transpose()?.map(|info| info.building);
+            let search = search.unwrap_or_default();
+            let rooms: Vec<Room> = db
+                .get_rooms()?
+                .into_iter()
+                .filter(|r| building.as_ref().is_none_or(|b| &r.location.building == b) && r.matches_query(&search))
+                .collect();
+            for room in &rooms {
+                let loc = &room.location;
+                let count = db.get_equipment_by_room(loc)?.len();
+                println!(
+                    "{:<8}  {:<20}  {:<16}  {:>4} sæti  {:<20}  {} atriði",
+                    loc.to_string(),
+                    room.name,
+                    room.room_type.to_string(),
+                    room.capacity,
+                    room.responsible,
+                    count
+                );
+            }
+            println!("Fjöldi: {} stofur", rooms.len());
+        }
+        RoomAction::Add { location, name, room_type, capacity, responsible } => {
+            let room = Room {
+                location: Location::try_from(location.as_str())?,
+                name,
+                room_type: RoomType::try_from(room_type.as_str())?,
+                capacity,
+                responsible,
+            };
+            db.add_room(&room)?;
+            println!("Stofa skráð: {}", room);
+        }
+        RoomAction::Edit { location, name, room_type, capacity, responsible } => {
+            let loc = Location::try_from(location.as_str())?;
+            let mut room = db.get_room(&loc)?.ok_or(Error::UnknownRoom(loc.to_string()))?;
+            if let Some(name) = name {
+                room.name = name;
+            }
+            if let Some(room_type) = room_type {
+                room.room_type = RoomType::try_from(room_type.as_str())?;
+            }
+            if let Some(capacity) = capacity {
+                room.capacity = capacity;
+            }
+            if let Some(responsible) = responsible {
+                room.responsible = responsible;
+            }
+            db.update_room(&room)?;
+            println!("Stofa uppfærð: {}", room);
+        }
+        RoomAction::Remove { location } => {
+            let loc = Location::try_from(location.as_str())?;
+            db.delete_room(&loc)?;
+            println!("Stofu {} eytt", loc);
+        }
+    }
+    Ok(())
+}
+
+fn run_depreciation(db: &Database, action: DepreciationAction) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        DepreciationAction::List => {
+            let depreciation = db.get_depreciation()?;
+            let mut kinds = Vec::new();
+            for code in category::BUILT_IN_TYPES {
+                kinds.push(resolve_kind(db, code)?);
+            }
+            kinds.extend(db.get_categories()?.into_iter().map(|c| (c.code, c.name)));
+            for (code, label) in kinds {
+                let years = depreciation.years_for(&code).map_or_else(|| "ekki afskrifað".to_string(), |y| format!("{} ár", y));
+                println!("{:<16}  {:<20}  {}", code, label, years);
+            }
+        }
+        DepreciationAction::Set { kind, years } => {
+            let (code, label) = resolve_kind(db, &kind)?;
+            let years = depreciation::parse_years(&years)?;
+            db.set_depreciation_years(&code, Some(years))?;
+            println!("{} afskrifast á {} árum", label, years);
+        }
+        DepreciationAction::Clear { kind } => {
+            let (code, label) = resolve_kind(db, &kind)?;
+            db.set_depreciation_years(&code, None)?;
+            println!("{} eru ekki lengur afskrifuð", label);
+        }
+    }
+    Ok(())
+}
+
+fn run_categories(db: &Database, action: CategoryAction) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        CategoryAction::List => {
+            let categories = db.get_categories()?;
+            for category in &categories {
+                let count = db.get_equipment_by_type(&category.code)?.len();
+                println!("{:<16}  {:<20}  {} atriði", category.code, category.name, count);
+                for a in &category.attributes {
+                    let mut notes = vec![a.kind.to_string()];
+                    if a.required {
+                        notes.push("skylda".to_string());
+                    }
+                    notes.extend([a.range_text(), a.choices.join("/"), a.unit.clone()].into_iter().filter(|n| !n.is_empty()));
+                    println!("    {:<14}  {:<20}  {}", a.key, a.label, notes.join(", "));
+                }
+            }
+            println!("Fjöldi: {} flokkar", categories.len());
+        }
+        CategoryAction::Add { code, name } => {
+            let category = Category::new(&code, &name);
+            db.add_category(&category)?;
+            println!("Flokki bætt við: {} ({})", category.name, category.code);
+        }
+        CategoryAction::Edit { category, name } => {
+            let mut category = db.find_category(&category)?;
+            category.name = name.trim().to_string();
+            db.update_category(&category)?;
+            println!("Flokkur uppfærður: {} ({})", category.name, category.code);
+        }
+        CategoryAction::Attr { category, key, label, kind, required, min, max, choices, unit } => {
+            let mut category = db.find_category(&category)?;
+            let mut def = AttributeDef::new(&key, &label, AttributeKind::try_from(kind.as_str())?);
+            def.required = required;
+            def.min = min;
+            def.max = max;
+            def.choices = choices.iter().map(|c| c.trim().to_string()).filter(|c| !c.is_empty()).collect();
+            def.unit = unit.trim().to_string();
+            match category.attributes.iter_mut().find(|a| a.key == def.key) {
+                Some(existing) => *existing = def,
+                None => category.attributes.push(def),
+            }
+            db.update_category(&category)?;
+            println!("Eiginleiki {} vistaður í flokknum {}", key, category.name);
+        }
+        CategoryAction::RemoveAttr { category, key } => {
+            let mut category = db.find_category(&category)?;
+            if category.attribute(&key).is_none() {
+                return Err(Error::UnknownAttribute(key).into());
+            }
+            category.attributes.retain(|a| a.key != key);
+            db.update_category(&category)?;
+            println!("Eiginleiki {} fjarlægður úr flokknum {}", key, category.name);
+        }
+        CategoryAction::Remove { category } => {
+            let category = db.find_category(&category)?;
+            db.delete_category(&category.code)?;
+            println!("Flokki {} eytt", category.name);
+        }
+    }
+    Ok(())
+}
+
+fn floor_arg(text: &str) -> Result<i8, String> {
+    location::parse_floor(text).map_err(|e| e.to_string())
+}
+
+fn build_equipment(db: &Database, kind: AddKind) -> Result<Equipment, Error> {
+    let (mut equipment, info) = match kind {
+        AddKind::Table { location, value, seats, info } => {
+            let location = Location::try_from(location.as_str())?;
+            (Equipment::Table(Table::try_from((location, value, seats))?), info)
+        }
+        AddKind::Chair { location, value, chair_type, info } => {
+            let location = Location::try_from(location.as_str())?;
+            let chair_type = ChairType::try_from(chair_type.as_str())?;
+            (Equipment::Chair(Chair::try_from((location, value, chair_type))?), info)
+        }
+        AddKind::Projector { location, value, lumens, info } => {
+            let location = Location::try_from(location.as_str())?;
+            (Equipment::Projector(Projector::try_from((location, value, lumens))?), info)
+        }
+        AddKind::Computer { location, value, kind, cpu, ram_gb, info } => {
+            let location = Location::try_from(location.as_str())?;
+            let mut computer = Computer::new(location, value, ComputerKind::try_from(kind.as_str())?);
+            computer.cpu = cpu.trim().to_string();
+            computer.ram_gb = ram_gb;
+            (Equipment::Computer(computer), info)
+        }
+        AddKind::Monitor { location, value, screen_size, resolution, info } => {
+            let location = Location::try_from(location.as_str())?;
+            let screen_size = monitor::parse_screen_size(&screen_size)?;
+            let monitor = Monitor::new(location, value, screen_size, Resolution::try_from(resolution.as_str())?);
+            (Equipment::Monitor(monitor), info)
+        }
+        AddKind::Item { category, location, value, attributes, info } => {
+            let location = Location::try_from(location.as_str())?;
+            let category = db.find_category(&category)?;
+            let raw = category::parse_attribute_pairs(&attributes.join(";"))?;
+            let attributes = category.parse_attributes(&raw)?;
+            (Equipment::Custom(CustomEquipment::new(location, value, category, attributes)), info)
+        }
+    };
+    equipment.set_info(EquipmentInfo::try_from(info)?);
+    equipment.check()?;
+    Ok(equipment)
+}
+
+fn require_existing(db: &Database, id: i64) -> Result<Equipment, Error> {
+    db.get_equipment_by_id(id)?.ok_or(Error::NotFound(id))
+}
+
+fn print_items(items: &[Equipment], json: bool) -> Result<(), serde_json::Error> {
+    if json {
+        let out = serde_json::to_string_pretty(items)?;
+        println!("{}", out);
+        return Ok(());
+    }
+    println!("{:>6}  {:<10}  {:<8}  {:>12}  Lýsing", "ID", "Tegund", "Staður", "Verðmæti");
+    for eq in items {
+        println!(
+            "{:>6}  {:<10}  {:<8}  {:>12}  {}",
+            eq.get_id().unwrap_or(0),
+            eq.get_type_name(),
+            eq.get_location().to_string(),
+            format!("{} kr.", eq.get_value()),
+            eq
+        );
+    }
+    println!("Fjöldi: {} atriði", items.len());
+    Ok(())
+}
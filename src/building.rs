@@ -0,0 +1,124 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// A building or site, identified by its short code (e.g. `H`, `HA`).
+/// Names and addresses are data in the `buildings` table, see `BuildingInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Building {
+    code: String,
+}
+
+/// Names the three original sites had as enum variants; still accepted so
+/// JSON files and CSV columns written by older versions can be read
+const LEGACY_NAMES: [(&str, &str); 6] = [
+    ("Hafnarfjordur", "HA"),
+    ("Hafnarfjörður", "HA"),
+    ("Hateigssvegur", "H"),
+    ("Háteigsvegur", "H"),
+    ("Skolavorduhollt", "S"),
+    ("Skólavörðuholt", "S"),
+];
+
+impl Building {
+    /// Longest code allowed; it is printed on labels and in every location
+    pub const MAX_CODE_LEN: usize = 5;
+
+    /// Code of one to five capital letters, so it cannot run into the floor in `H-2.02` or the older `H-202`
+    pub fn new(code: &str) -> Result<Self> {
+        let code = code.trim();
+        if code.is_empty() || code.len() > Self::MAX_CODE_LEN || !code.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(Error::InvalidBuildingCode(code.to_string()));
+        }
+        Ok(Building { code: code.to_string() })
+    }
+
+    pub fn to_code(&self) -> &str {
+        &self.code
+    }
+}
+
+impl fmt::Display for Building {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl TryFrom<&str> for Building {
+    type Error = Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let value = value.trim();
+        match LEGACY_NAMES.iter().find(|(name, _)| *name == value) {
+            Some((_, code)) => Building::new(code),
+            None => Building::new(value).map_err(|_| Error::UnknownBuilding(value.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for Building {
+    type Error = Error;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        Building::try_from(value.as_str())
+    }
+}
+
+// Stored as the bare code in JSON, e.g. `"building": "H"`
+impl Serialize for Building {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.code)
+    }
+}
+
+impl<'de> Deserialize<'de> for Building {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Building::try_from(text.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// One row of the `buildings` table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildingInfo {
+    pub building: Building,
+    /// Full name shown in lists and reports, e.g. "Háteigsvegur"
+    pub name: String,
+    pub address: String,
+}
+
+impl BuildingInfo {
+    /// Name of `building` from `buildings`, or its code if it is not in the list
+    pub fn name_of(buildings: &[BuildingInfo], building: &Building) -> String {
+        buildings
+            .iter()
+            .find(|b| &b.building == building)
+            .map(|b| b.name.clone())
+            .unwrap_or_else(|| building.to_string())
+    }
+}
+
+impl fmt::Display for BuildingInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_capital_letters_and_old_names_still_parse() {
+        assert_eq!(Building::new("HA").unwrap().to_code(), "HA");
+        assert!(Building::new("H2").is_err());
+        assert!(Building::new("h").is_err());
+        assert!(Building::new("ABCDEF").is_err());
+        assert_eq!(Building::try_from("Hateigssvegur").unwrap(), Building::new("H").unwrap());
+
+        let json = serde_json::to_string(&Building::new("S").unwrap()).unwrap();
+        assert_eq!(json, "\"S\"");
+        let old: Building = serde_json::from_str("\"Skolavorduhollt\"").unwrap();
+        assert_eq!(old.to_code(), "S");
+    }
+}
@@ -0,0 +1,154 @@
+use crate::attribute::{AttributeDef, AttributeValue};
+use crate::error::{Error, Result};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Type codes of the equipment kinds built into `Equipment`; categories cannot use them
+pub const BUILT_IN_TYPES: [&str; 5] = ["Table", "Chair", "Projector", "Computer", "Monitor"];
+
+/// Longest category code; it is stored in the `equipment.type` column
+pub const MAX_CODE_LEN: usize = 30;
+
+/// A user-defined kind of equipment, e.g. whiteboards or speakers, with its own attributes.
+/// Categories are rows in the `categories` table and items in them are `Equipment::Custom`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Category {
+    /// Stored in `equipment.type`, e.g. `Whiteboard`; fixed once items use it
+    pub code: String,
+    /// Name shown in lists and reports, e.g. "Tafla"
+    pub name: String,
+    /// In the order they are shown in forms
+    pub attributes: Vec<AttributeDef>,
+}
+
+impl Category {
+    /// Category with no attributes yet
+    pub fn new(code: &str, name: &str) -> Self {
+        Category { code: code.trim().to_string(), name: name.trim().to_string(), attributes: Vec::new() }
+    }
+
+    pub fn attribute(&self, key: &str) -> Option<&AttributeDef> {
+        self.attributes.iter().find(|a| a.key == key)
+    }
+
+    /// Check the code, the name and every attribute definition
+    pub fn check(&self) -> Result<()> {
+        let mut chars = self.code.chars();
+        let code_ok = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && self.code.len() <= MAX_CODE_LEN
+            && !BUILT_IN_TYPES.iter().any(|t| t.eq_ignore_ascii_case(&self.code));
+        if !code_ok {
+            return Err(Error::InvalidCategoryCode(self.code.clone()));
+        }
+        if self.name.is_empty() {
+            return Err(Error::InvalidCategoryCode(format!("{} (heiti vantar)", self.code)));
+        }
+        for (i, attribute) in self.attributes.iter().enumerate() {
+            attribute.check()?;
+            if self.attributes[..i].iter().any(|a| a.key == attribute.key) {
+                return Err(Error::InvalidAttributeDef { key: attribute.key.clone(), reason: "lykillinn er tvískráður".into() });
+            }
+        }
+        Ok(())
+    }
+
+    /// Typed values from text keyed by attribute key, as typed in a form or a CSV file.
+    /// Every required attribute must be given, and no key the category does not have.
+    pub fn parse_attributes(&self, raw: &BTreeMap<String, String>) -> Result<BTreeMap<String, AttributeValue>> {
+        if let Some(key) = raw.keys().find(|k| self.attribute(k).is_none()) {
+            return Err(Error::UnknownAttribute(key.clone()));
+        }
+        let mut values = BTreeMap::new();
+        for def in &self.attributes {
+            let text = raw.get(&def.key).map(String::as_str).unwrap_or_default();
+            if let Some(value) = def.parse(text)? {
+                values.insert(def.key.clone(), value);
+            }
+        }
+        Ok(values)
+    }
+
+    /// Check values that did not come from `parse_attributes`, e.g. from JSON, against the
+    /// definitions as they are now. Returns them normalised, e.g. choices spelled as listed.
+    pub fn check_attributes(&self, values: &BTreeMap<String, AttributeValue>) -> Result<BTreeMap<String, AttributeValue>> {
+        let raw = values.iter().map(|(k, v)| (k.clone(), v.to_string())).collect();
+        self.parse_attributes(&raw)
+    }
+
+    /// Labelled values in attribute order, e.g. `("Skjástærð", "27 tommur")`
+    pub fn details(&self, values: &BTreeMap<String, AttributeValue>) -> Vec<(String, String)> {
+        self.attributes
+            .iter()
+            .filter_map(|def| values.get(&def.key).map(|v| (def.label.clone(), def.format(v))))
+            .collect()
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Values as `key=value; key=value`, the form used in CSV files
+pub fn format_attributes(values: &BTreeMap<String, AttributeValue>) -> String {
+    values.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join("; ")
+}
+
+/// Read `key=value` pairs separated by `;`, as written by `format_attributes` or typed on the command line
+pub fn parse_attribute_pairs(text: &str) -> Result<BTreeMap<String, String>> {
+    let mut raw = BTreeMap::new();
+    for pair in text.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| Error::InvalidAttribute { label: "Eiginleikar".into(), value: pair.to_string() })?;
+        raw.insert(key.trim().to_lowercase(), value.trim().to_string());
+    }
+    Ok(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute_kind::AttributeKind;
+
+    fn monitor() -> Category {
+        let mut size = AttributeDef::new("skjastaerd", "Skjástærð", AttributeKind::Number);
+        size.required = true;
+        size.min = Some(10);
+        size.max = Some(100);
+        size.unit = "tommur".into();
+        let mut panel = AttributeDef::new("panell", "Panell", AttributeKind::Choice);
+        panel.choices = vec!["IPS".into(), "VA".into()];
+        let mut category = Category::new("Skjar", "Skjár");
+        category.attributes = vec![size, panel, AttributeDef::new("hatalari", "Hátalari", AttributeKind::YesNo)];
+        category
+    }
+
+    #[test]
+    fn attributes_are_typed_and_checked() {
+        let category = monitor();
+        category.check().unwrap();
+        let raw = parse_attribute_pairs("skjastaerd=27; panell=ips; hatalari=já").unwrap();
+        let values = category.parse_attributes(&raw).unwrap();
+        assert_eq!(values["skjastaerd"], AttributeValue::Number(27));
+        assert_eq!(values["panell"], AttributeValue::Text("IPS".into()));
+        assert_eq!(format_attributes(&values), "hatalari=Já; panell=IPS; skjastaerd=27");
+        assert_eq!(category.details(&values)[0], ("Skjástærð".to_string(), "27 tommur".to_string()));
+        assert_eq!(category.check_attributes(&values).unwrap(), values);
+
+        let parse = |text: &str| category.parse_attributes(&parse_attribute_pairs(text).unwrap());
+        assert!(matches!(parse("panell=VA"), Err(Error::MissingAttribute(_))));
+        assert!(matches!(parse("skjastaerd=120"), Err(Error::AttributeOutOfRange { value: 120, .. })));
+        assert!(matches!(parse("skjastaerd=stór"), Err(Error::InvalidAttribute { .. })));
+        assert!(matches!(parse("skjastaerd=24; panell=OLED"), Err(Error::InvalidAttribute { .. })));
+        assert!(matches!(parse("skjastaerd=24; litur=svartur"), Err(Error::UnknownAttribute(_))));
+
+        assert!(matches!(Category::new("chair", "Stóll").check(), Err(Error::InvalidCategoryCode(_))));
+        assert!(matches!(Category::new("2D", "Tafla").check(), Err(Error::InvalidCategoryCode(_))));
+        let mut twice = monitor();
+        twice.attributes.push(AttributeDef::new("panell", "Panell 2", AttributeKind::Text));
+        assert!(matches!(twice.check(), Err(Error::InvalidAttributeDef { .. })));
+    }
+}
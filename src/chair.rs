@@ -1,19 +1,23 @@
 use crate::chair_type::ChairType;
+use crate::equipment_info::EquipmentInfo;
+use crate::error::Error;
 use crate::location::Location;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Chair {
     pub id: Option<i64>,
     pub location: Location,
     pub value: u32,         // Value in ISK
     pub chair_type: ChairType,
+    #[serde(flatten)]
+    pub info: EquipmentInfo,
 }
 
 impl Chair {
     pub fn new(location: Location, value: u32, chair_type: ChairType) -> Self {
-        Chair { id: None, location, value, chair_type }
+        Chair { id: None, location, value, chair_type, info: EquipmentInfo::default() }
     }
 
     pub fn with_id(mut self, id: i64) -> Self {
@@ -23,7 +27,7 @@ impl Chair {
 }
 
 impl TryFrom<(Location, u32, ChairType)> for Chair {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(value: (Location, u32, ChairType)) -> Result<Self, Self::Error> {
         let (location, value_isk, chair_type) = value;
@@ -1,3 +1,4 @@
+use crate::error::Error;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -36,21 +37,22 @@ impl fmt::Display for ChairType {
 }
 
 impl TryFrom<&str> for ChairType {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "Hægindastóll" => Ok(ChairType::Haegindastoll),
-            "Skólastóll" => Ok(ChairType::Skolastoll),
-            "Skrifstofustóll" => Ok(ChairType::Skrifstofustoll),
-            "Annað" => Ok(ChairType::Annad),
-            _ => Err(format!("Óþekkt stólategund: {}", value)),
+        // Accept display names and ASCII spellings (handy from a terminal)
+        match value.trim() {
+            "Hægindastóll" | "Haegindastoll" => Ok(ChairType::Haegindastoll),
+            "Skólastóll" | "Skolastoll" => Ok(ChairType::Skolastoll),
+            "Skrifstofustóll" | "Skrifstofustoll" => Ok(ChairType::Skrifstofustoll),
+            "Annað" | "Annad" => Ok(ChairType::Annad),
+            _ => Err(Error::UnknownChairType(value.to_string())),
         }
     }
 }
 
 impl TryFrom<String> for ChairType {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         ChairType::try_from(value.as_str())
@@ -0,0 +1,63 @@
+use crate::computer_kind::ComputerKind;
+use crate::equipment_info::EquipmentInfo;
+use crate::error::{Error, Result};
+use crate::location::Location;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Computer {
+    pub id: Option<i64>,
+    pub location: Location,
+    pub value: u32, // Value in ISK
+    pub kind: ComputerKind,
+    pub cpu: String,         // e.g. "Intel Core i5-1335U"; may be empty
+    pub ram_gb: Option<u16>, // Memory in GB, if known
+    /// The serial number in it is required
+    #[serde(flatten)]
+    pub info: EquipmentInfo,
+}
+
+impl Computer {
+    /// Computer with no specs yet; the serial number goes in `info` before it is saved
+    pub fn new(location: Location, value: u32, kind: ComputerKind) -> Self {
+        Computer { id: None, location, value, kind, cpu: String::new(), ram_gb: None, info: EquipmentInfo::default() }
+    }
+
+    pub fn with_id(mut self, id: i64) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Every computer needs a serial number, and memory, if given, cannot be 0
+    pub fn check(&self) -> Result<()> {
+        if self.info.serial_number.trim().is_empty() {
+            return Err(Error::MissingSerialNumber);
+        }
+        if self.ram_gb == Some(0) {
+            return Err(Error::ZeroRam);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Computer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(id) = self.id {
+            write!(f, " með ID: {}", id)?;
+        }
+        let name = self.info.product_name();
+        if !name.is_empty() {
+            write!(f, ", {}", name)?;
+        }
+        write!(f, ", raðnúmer {}, kostar {} kr.", self.info.serial_number, self.value)?;
+        if !self.cpu.is_empty() {
+            write!(f, ", {}", self.cpu)?;
+        }
+        if let Some(ram) = self.ram_gb {
+            write!(f, ", {} GB vinnsluminni", ram)?;
+        }
+        write!(f, " og er staðsett í {}", self.location)
+    }
+}
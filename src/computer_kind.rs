@@ -0,0 +1,58 @@
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Form factor of a computer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComputerKind {
+    Laptop,  // Fartölva
+    Desktop, // Borðtölva
+}
+
+impl ComputerKind {
+    pub fn all() -> Vec<ComputerKind> {
+        vec![ComputerKind::Laptop, ComputerKind::Desktop]
+    }
+
+    /// Code stored in the `equipment.computer_kind` column
+    pub fn to_code(self) -> &'static str {
+        match self {
+            ComputerKind::Laptop => "Laptop",
+            ComputerKind::Desktop => "Desktop",
+        }
+    }
+}
+
+impl fmt::Display for ComputerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ComputerKind::Laptop => "Fartölva",
+                ComputerKind::Desktop => "Borðtölva",
+            }
+        )
+    }
+}
+
+impl TryFrom<&str> for ComputerKind {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        // Accept stored codes, Icelandic names and ASCII spellings, any case
+        match value.trim().to_lowercase().as_str() {
+            "laptop" | "fartölva" | "fartolva" => Ok(ComputerKind::Laptop),
+            "desktop" | "borðtölva" | "bordtolva" => Ok(ComputerKind::Desktop),
+            _ => Err(Error::UnknownComputerKind(value.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for ComputerKind {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        ComputerKind::try_from(value.as_str())
+    }
+}
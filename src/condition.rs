@@ -0,0 +1,73 @@
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// State an item is in, e.g. a chair that needs repair or a projector out of service until it gets a new lamp
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Condition {
+    New,          // Nýtt
+    #[default]
+    Good,         // Gott
+    Worn,         // Slitið
+    NeedsRepair,  // Þarf viðgerð
+    OutOfService, // Úr notkun
+}
+
+impl Condition {
+    pub fn all() -> Vec<Condition> {
+        vec![Condition::New, Condition::Good, Condition::Worn, Condition::NeedsRepair, Condition::OutOfService]
+    }
+
+    /// Code stored in the `condition` column
+    pub fn to_code(self) -> &'static str {
+        match self {
+            Condition::New => "New",
+            Condition::Good => "Good",
+            Condition::Worn => "Worn",
+            Condition::NeedsRepair => "NeedsRepair",
+            Condition::OutOfService => "OutOfService",
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Condition::New => "Nýtt",
+                Condition::Good => "Gott",
+                Condition::Worn => "Slitið",
+                Condition::NeedsRepair => "Þarf viðgerð",
+                Condition::OutOfService => "Úr notkun",
+            }
+        )
+    }
+}
+
+impl TryFrom<&str> for Condition {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        // Accept stored codes and Icelandic names, any case
+        match value.trim().to_lowercase().as_str() {
+            "new" | "nýtt" | "nytt" => Ok(Condition::New),
+            "good" | "gott" => Ok(Condition::Good),
+            "worn" | "slitið" | "slitid" => Ok(Condition::Worn),
+            "needsrepair" | "needs-repair" | "needs repair" | "þarf viðgerð" | "tharf vidgerd" | "viðgerð" | "vidgerd" => {
+                Ok(Condition::NeedsRepair)
+            }
+            "outofservice" | "out-of-service" | "out of service" | "úr notkun" | "ur notkun" => Ok(Condition::OutOfService),
+            _ => Err(Error::UnknownCondition(value.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for Condition {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Condition::try_from(value.as_str())
+    }
+}
@@ -0,0 +1,324 @@
+//! CSV export and import of equipment lists, for spreadsheets.
+//!
+//! Files are written with `;` as separator and a UTF-8 byte order mark, which is
+//! what Excel with Icelandic settings expects. Reading accepts `;` or `,`, and
+//! headers in Icelandic or English, in any order.
+
+use crate::chair::Chair;
+use crate::chair_type::ChairType;
+use crate::computer::Computer;
+use crate::computer_kind::ComputerKind;
+use crate::condition::Condition;
+use crate::equipment::Equipment;
+use crate::equipment_info::{self, EquipmentInfo};
+use crate::error::{Error, Result};
+use crate::building::Building;
+use crate::category::{self, Category};
+use crate::custom_equipment::CustomEquipment;
+use crate::location::{self, Location};
+use crate::monitor::{self, Monitor};
+use crate::projector::Projector;
+use crate::resolution::Resolution;
+use crate::table::Table;
+use std::io::{Read, Write};
+
+const HEADER: [&str; 25] = [
+    "ID", "Tegund", "Staðsetning", "Hús", "Hæð", "Herbergi", "Verðmæti", "Sæti", "Stólategund", "Lúmens", "Eiginleikar",
+    "Tegund tölvu", "Örgjörvi", "Vinnsluminni", "Skjástærð", "Upplausn",
+    "Eignanúmer", "Raðnúmer", "Framleiðandi", "Gerð", "Innkaupsdagur", "Birgir", "Reikningsnúmer", "Ástand",
+    "Athugasemdir",
+];
+
+const BOM: &str = "\u{feff}";
+
+/// Write `items` as CSV, one row per item; per-type columns are empty for other types.
+/// Attributes of items in a category go in one column as `key=value; key=value`.
+pub fn write_csv<W: Write>(mut writer: W, items: &[Equipment]) -> Result<()> {
+    writer.write_all(BOM.as_bytes())?;
+    let mut out = csv::WriterBuilder::new().delimiter(b';').from_writer(writer);
+    out.write_record(HEADER)?;
+    for eq in items {
+        let location = eq.get_location();
+        let (seats, chair_type, lumens, attributes) = match eq {
+            Equipment::Table(t) => (t.seats.to_string(), String::new(), String::new(), String::new()),
+            Equipment::Chair(c) => (String::new(), c.chair_type.to_string(), String::new(), String::new()),
+            Equipment::Projector(p) => (String::new(), String::new(), p.lumens.to_string(), String::new()),
+            Equipment::Custom(x) => (String::new(), String::new(), String::new(), category::format_attributes(&x.attributes)),
+            Equipment::Computer(_) | Equipment::Monitor(_) => (String::new(), String::new(), String::new(), String::new()),
+        };
+        let [computer_kind, cpu, ram_gb, screen_size, resolution] = computer_fields(eq);
+        let mut record = vec![
+            eq.get_id().map(|id| id.to_string()).unwrap_or_default(),
+            eq.get_type_name().to_string(),
+            location.to_string(),
+            location.building.to_code().to_string(),
+            location::floor_code(location.floor),
+            location.room.to_string(),
+            eq.get_value().to_string(),
+            seats,
+            chair_type,
+            lumens,
+            attributes,
+            computer_kind,
+            cpu,
+            ram_gb,
+            screen_size,
+            resolution,
+        ];
+        // Eignanúmer to Athugasemdir, in the same order as the header
+        record.extend(eq.info().fields().into_iter().map(|(_, value)| value));
+        out.write_record(record)?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Values of the columns from Tegund tölvu to Upplausn; empty for types other than computers and monitors
+fn computer_fields(eq: &Equipment) -> [String; 5] {
+    match eq {
+        Equipment::Computer(c) => [
+            c.kind.to_string(),
+            c.cpu.clone(),
+            c.ram_gb.map(|n| n.to_string()).unwrap_or_default(),
+            String::new(),
+            String::new(),
+        ],
+        Equipment::Monitor(m) => [String::new(), String::new(), String::new(), m.screen_size.to_string(), m.resolution.to_string()],
+        _ => Default::default(),
+    }
+}
+
+/// Read equipment from CSV. Every row is validated on its own: valid rows are
+/// returned, and each invalid one gives an `Error::CsvLine` with its line number.
+/// Only an unreadable file or a missing column is a hard error.
+/// Types other than the built-in ones are looked up in `categories` by code or name.
+pub fn read_csv<R: Read>(mut reader: R, categories: &[Category]) -> Result<(Vec<Equipment>, Vec<Error>)> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let text = text.strip_prefix(BOM).unwrap_or(&text);
+    let first_line = text.lines().next().unwrap_or_default();
+    let delimiter = if first_line.matches(';').count() >= first_line.matches(',').count() { b';' } else { b',' };
+
+    let mut rows = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+    let columns = Columns::find(rows.headers()?)?;
+
+    let mut items = Vec::new();
+    let mut errors = Vec::new();
+    for record in rows.records() {
+        let record = record?;
+        // Line numbers as in a text editor; the header is line 1
+        let line = record.position().map(|p| p.line() as usize).unwrap_or(0);
+        if record.iter().all(|field| field.is_empty()) {
+            continue;
+        }
+        match columns.parse(&record, categories) {
+            Ok(eq) => items.push(eq),
+            Err(reason) => errors.push(Error::CsvLine { line, reason }),
+        }
+    }
+    Ok((items, errors))
+}
+
+/// Index of each known column in the file's header
+struct Columns {
+    id: Option<usize>,
+    kind: usize,
+    location: Option<usize>,
+    building: Option<usize>,
+    floor: Option<usize>,
+    room: Option<usize>,
+    value: usize,
+    seats: Option<usize>,
+    chair_type: Option<usize>,
+    lumens: Option<usize>,
+    attributes: Option<usize>,
+    computer_kind: Option<usize>,
+    cpu: Option<usize>,
+    ram_gb: Option<usize>,
+    screen_size: Option<usize>,
+    resolution: Option<usize>,
+    asset_tag: Option<usize>,
+    serial_number: Option<usize>,
+    manufacturer: Option<usize>,
+    model: Option<usize>,
+    purchase_date: Option<usize>,
+    supplier: Option<usize>,
+    invoice_number: Option<usize>,
+    condition: Option<usize>,
+    notes: Option<usize>,
+}
+
+impl Columns {
+    fn find(header: &csv::StringRecord) -> Result<Self> {
+        let find = |names: &[&str]| {
+            header
+                .iter()
+                .position(|h| names.contains(&h.trim().to_lowercase().as_str()))
+        };
+        let require = |names: &[&str]| find(names).ok_or_else(|| Error::CsvLine { line: 1, reason: format!("Dálk vantar: {}", names[0]) });
+        let columns = Columns {
+            id: find(&["id"]),
+            kind: require(&["tegund", "type"])?,
+            location: find(&["staðsetning", "location"]),
+            building: find(&["hús", "building"]),
+            floor: find(&["hæð", "floor"]),
+            room: find(&["herbergi", "room"]),
+            value: require(&["verðmæti", "value"])?,
+            seats: find(&["sæti", "seats"]),
+            chair_type: find(&["stólategund", "chair_type", "chair type"]),
+            lumens: find(&["lúmens", "lumens"]),
+            attributes: find(&["eiginleikar", "attributes"]),
+            computer_kind: find(&["tegund tölvu", "computer_kind", "computer kind"]),
+            cpu: find(&["örgjörvi", "cpu"]),
+            ram_gb: find(&["vinnsluminni", "ram_gb", "ram"]),
+            screen_size: find(&["skjástærð", "screen_size", "screen size"]),
+            resolution: find(&["upplausn", "resolution"]),
+            asset_tag: find(&["eignanúmer", "asset_tag", "asset tag"]),
+            serial_number: find(&["raðnúmer", "serial_number", "serial number"]),
+            manufacturer: find(&["framleiðandi", "manufacturer"]),
+            model: find(&["gerð", "model"]),
+            purchase_date: find(&["innkaupsdagur", "purchase_date", "purchase date"]),
+            supplier: find(&["birgir", "supplier"]),
+            invoice_number: find(&["reikningsnúmer", "invoice_number", "invoice number", "invoice"]),
+            condition: find(&["ástand", "condition"]),
+            notes: find(&["athugasemdir", "notes"]),
+        };
+        if columns.location.is_none() && (columns.building.is_none() || columns.floor.is_none() || columns.room.is_none()) {
+            return Err(Error::CsvLine { line: 1, reason: "Dálk vantar: Staðsetning (eða Hús, Hæð og Herbergi)".to_string() });
+        }
+        Ok(columns)
+    }
+
+    fn parse(&self, record: &csv::StringRecord, categories: &[Category]) -> std::result::Result<Equipment, String> {
+        let field = |index: Option<usize>| index.and_then(|i| record.get(i)).unwrap_or_default();
+        let number = |index: Option<usize>, label: &str| {
+            let text = field(index);
+            text.parse::<u32>().map_err(|_| format!("Ógilt gildi í dálki {}: '{}'", label, text))
+        };
+
+        let location = match self.location.map(|i| field(Some(i))).filter(|l| !l.is_empty()) {
+            Some(text) => Location::try_from(text).map_err(|e| e.to_string())?,
+            None => {
+                let building = Building::try_from(field(self.building)).map_err(|e| e.to_string())?;
+                let floor = location::parse_floor(field(self.floor)).map_err(|e| e.to_string())?;
+                let room = u16::try_from(number(self.room, "Herbergi")?).map_err(|_| "Herbergi er of hátt".to_string())?;
+                Location::try_from((building, floor, room)).map_err(|e| e.to_string())?
+            }
+        };
+        let value = number(Some(self.value), "Verðmæti")?;
+        let info = EquipmentInfo {
+            asset_tag: field(self.asset_tag).to_string(),
+            serial_number: field(self.serial_number).to_string(),
+            manufacturer: field(self.manufacturer).to_string(),
+            model: field(self.model).to_string(),
+            purchase_date: equipment_info::parse_optional_date(field(self.purchase_date)).map_err(|e| e.to_string())?,
+            supplier: field(self.supplier).to_string(),
+            invoice_number: field(self.invoice_number).to_string(),
+            condition: match field(self.condition) {
+                "" => Condition::default(),
+                text => Condition::try_from(text).map_err(|e| e.to_string())?,
+            },
+            notes: field(self.notes).to_string(),
+        };
+
+        let mut equipment = match field(Some(self.kind)).to_lowercase().as_str() {
+            "borð" | "bord" | "table" => {
+                let seats = u8::try_from(number(self.seats, "Sæti")?).map_err(|_| "Of mörg sæti".to_string())?;
+                Equipment::Table(Table::try_from((location, value, seats)).map_err(|e| e.to_string())?)
+            }
+            "stóll" | "stoll" | "chair" => {
+                let chair_type = ChairType::try_from(field(self.chair_type)).map_err(|e| e.to_string())?;
+                Equipment::Chair(Chair::try_from((location, value, chair_type)).map_err(|e| e.to_string())?)
+            }
+            "skjávarpi" | "skjavarpi" | "projector" => {
+                let lumens = number(self.lumens, "Lúmens")?;
+                Equipment::Projector(Projector::try_from((location, value, lumens)).map_err(|e| e.to_string())?)
+            }
+            "tölva" | "tolva" | "computer" => {
+                let kind = ComputerKind::try_from(field(self.computer_kind)).map_err(|e| e.to_string())?;
+                let mut computer = Computer::new(location, value, kind);
+                computer.cpu = field(self.cpu).to_string();
+                if !field(self.ram_gb).is_empty() {
+                    computer.ram_gb = Some(u16::try_from(number(self.ram_gb, "Vinnsluminni")?).map_err(|_| "Of mikið vinnsluminni".to_string())?);
+                }
+                Equipment::Computer(computer)
+            }
+            "skjár" | "skjar" | "monitor" => {
+                let screen_size = monitor::parse_screen_size(field(self.screen_size)).map_err(|e| e.to_string())?;
+                let resolution = Resolution::try_from(field(self.resolution)).map_err(|e| e.to_string())?;
+                Equipment::Monitor(Monitor::new(location, value, screen_size, resolution))
+            }
+            other => {
+                let category = categories
+                    .iter()
+                    .find(|c| c.code.to_lowercase() == other || c.name.to_lowercase() == other)
+                    .ok_or_else(|| format!("Óþekkt tegund: '{}'", other))?;
+                let raw = category::parse_attribute_pairs(field(self.attributes)).map_err(|e| e.to_string())?;
+                let attributes = category.parse_attributes(&raw).map_err(|e| e.to_string())?;
+                Equipment::Custom(CustomEquipment::new(location, value, category.clone(), attributes))
+            }
+        };
+        equipment.set_info(info);
+        equipment.check().map_err(|e| e.to_string())?;
+        let id = field(self.id);
+        if !id.is_empty() {
+            equipment.set_id(id.parse::<i64>().map_err(|_| format!("Ógilt ID: '{}'", id))?);
+        }
+        Ok(equipment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn serial(serial_number: &str) -> EquipmentInfo {
+        EquipmentInfo { serial_number: serial_number.into(), ..Default::default() }
+    }
+
+    #[test]
+    fn round_trip_and_per_line_errors() {
+        let loc = Location::new(Building::new("H").unwrap(), 2, 12);
+        let mut table = Equipment::Table(Table::new(loc.clone(), 45000, 4));
+        table.set_id(7);
+        table.set_info(EquipmentInfo {
+            asset_tag: "TS-0042".into(),
+            purchase_date: Some(equipment_info::parse_date("15.08.2024").unwrap()),
+            condition: Condition::NeedsRepair,
+            notes: "Vinstri fótur laus, sjá \"viðgerð\"".into(),
+            ..Default::default()
+        });
+        let items = vec![
+            table,
+            Equipment::Chair(Chair::new(loc.clone(), 9000, ChairType::Skrifstofustoll)),
+            Equipment::Projector(Projector::new(loc.clone(), 90000, 3000)),
+            Equipment::Computer(Computer { ram_gb: Some(16), info: serial("5CG1234"), ..Computer::new(loc.clone(), 250000, ComputerKind::Desktop) }),
+            Equipment::Monitor(Monitor { info: serial("CN0X1"), ..Monitor::new(loc, 45000, 23.8, Resolution::new(1920, 1200)) }),
+        ];
+        let mut out = Vec::new();
+        write_csv(&mut out, &items).unwrap();
+        let (read, errors) = read_csv(out.as_slice(), &[]).unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(read, items);
+
+        let text = "type,location,value,seats,lumens\n\
+                    Table,H-212,100,4,\n\
+                    Table,X-1,100,4,\n\
+                    Projector,S-301,100,,0\n\
+                    Sófi,S-301,100,,\n";
+        let (read, errors) = read_csv(text.as_bytes(), &[]).unwrap();
+        assert_eq!(read.len(), 1);
+        let lines: Vec<usize> = errors
+            .iter()
+            .map(|e| match e {
+                Error::CsvLine { line, .. } => *line,
+                _ => 0,
+            })
+            .collect();
+        assert_eq!(lines, [3, 4, 5]);
+    }
+}
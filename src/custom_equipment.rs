@@ -0,0 +1,74 @@
+use crate::attribute::AttributeValue;
+use crate::category::Category;
+use crate::equipment_info::EquipmentInfo;
+use crate::location::Location;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Item in a user-defined `Category`, with that category's attributes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomEquipment {
+    pub id: Option<i64>,
+    pub location: Location,
+    pub value: u32, // Value in ISK
+    /// Stored as the category code only; `Database` fills in the definition when it reads the item
+    #[serde(serialize_with = "category_code", deserialize_with = "category_stub")]
+    pub category: Category,
+    /// Values keyed by `AttributeDef::key`; optional attributes left empty are absent
+    #[serde(default)]
+    pub attributes: BTreeMap<String, AttributeValue>,
+    #[serde(flatten)]
+    pub info: EquipmentInfo,
+}
+
+impl CustomEquipment {
+    pub fn new(location: Location, value: u32, category: Category, attributes: BTreeMap<String, AttributeValue>) -> Self {
+        CustomEquipment { id: None, location, value, category, attributes, info: EquipmentInfo::default() }
+    }
+
+    pub fn with_id(mut self, id: i64) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Labelled attribute values in the category's order
+    pub fn details(&self) -> Vec<(String, String)> {
+        self.category.details(&self.attributes)
+    }
+}
+
+// Two items are the same if they are in the same category, whatever its definition looks like now
+impl PartialEq for CustomEquipment {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.location == other.location
+            && self.value == other.value
+            && self.category.code == other.category.code
+            && self.attributes == other.attributes
+            && self.info == other.info
+    }
+}
+
+fn category_code<S: Serializer>(category: &Category, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&category.code)
+}
+
+fn category_stub<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Category, D::Error> {
+    let code = String::deserialize(deserializer)?;
+    Ok(Category::new(&code, &code))
+}
+
+impl fmt::Display for CustomEquipment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.category.name)?;
+        if let Some(id) = self.id {
+            write!(f, " með ID: {}", id)?;
+        }
+        write!(f, ", kostar {} kr.", self.value)?;
+        for (label, value) in self.details() {
+            write!(f, ", {}: {}", label, value)?;
+        }
+        write!(f, " og er staðsett í {}", self.location)
+    }
+}
@@ -1,220 +1,323 @@
+use crate::attribute::{AttributeDef, AttributeValue};
+use crate::attribute_kind::AttributeKind;
+use crate::audit::{self, AuditReport, AuditScan, AuditSession};
+use crate::building::{Building, BuildingInfo};
+use crate::category::{self, Category};
 use crate::chair::Chair;
 use crate::chair_type::ChairType;
+use crate::computer::Computer;
+use crate::computer_kind::ComputerKind;
+use crate::condition::Condition;
+use crate::custom_equipment::CustomEquipment;
+use crate::depreciation::{self, Depreciation};
 use crate::equipment::Equipment;
-use crate::location::{Building, Location};
+use crate::equipment_info::EquipmentInfo;
+use crate::history::{self, HistoryAction, HistoryEntry};
+use crate::location::Location;
+use crate::migrations;
+use crate::monitor::Monitor;
 use crate::projector::Projector;
+use crate::resolution::Resolution;
+use crate::retire_reason::{RetireReason, RetiredEquipment};
+use crate::room::Room;
+use crate::room_type::RoomType;
 use crate::table::Table;
-use rusqlite::{params, Connection, Result};
+use crate::error::{Error, Result};
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, Params, Row, ToSql};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+// Columns read by `RawRow::read`, in order
+macro_rules! equipment_columns {
+    () => {
+        "id, type, building, floor, room, suffix, value, seats, chair_type, lumens, attributes, \
+         serial_number, manufacturer, model, computer_kind, cpu, ram_gb, screen_size, resolution, asset_tag, notes, \
+         purchase_date, supplier, invoice_number, condition"
+    };
+}
+
+/// Columns written from `TypedColumns::values`, in order
+const TYPED_COLUMNS: [&str; 24] = [
+    "type", "building", "floor", "room", "suffix", "value", "seats", "chair_type", "lumens", "attributes",
+    "serial_number", "manufacturer", "model", "computer_kind", "cpu", "ram_gb", "screen_size", "resolution",
+    "asset_tag", "notes", "purchase_date", "supplier", "invoice_number", "condition",
+];
+
+/// Active (not retired) equipment; callers append `AND ...` / `ORDER BY ...`
+const SELECT_EQUIPMENT: &str = concat!(
+    "SELECT ", equipment_columns!(), " FROM equipment WHERE retired_reason IS NULL"
+);
+
+/// Every row, including retired ones
+const SELECT_ALL_ROWS: &str = concat!("SELECT ", equipment_columns!(), " FROM equipment");
+
+/// Rows in one room, bound as building, floor, room, suffix (see `suffix_column`)
+const SAME_ROOM: &str = "building = ?1 AND floor = ?2 AND room = ?3 AND suffix = ?4";
+
+/// The `suffix` column is `''` for rooms without a letter
+fn suffix_column(location: &Location) -> String {
+    location.suffix.map(String::from).unwrap_or_default()
+}
+
+const SELECT_RETIRED: &str = concat!(
+    "SELECT ", equipment_columns!(), ", retired_reason, retired_at FROM equipment
+     WHERE retired_reason IS NOT NULL"
+);
+
+const NOW: &str = "strftime('%Y-%m-%d %H:%M:%S', 'now', 'localtime')";
+
+const SELECT_HISTORY: &str =
+    "SELECT id, equipment_id, action, timestamp, user, details FROM equipment_history";
 
 pub struct Database {
     conn: Connection,
+    /// Name recorded in the history log for changes made through this connection
+    user: String,
 }
 
 impl Database {
     pub fn new(path: &str) -> Result<Self> {
-        let conn = Connection::open(path)?;
-        let db = Database { conn };
-        db.create_tables()?;
-        Ok(db)
+        let mut conn = Connection::open(path)?;
+        migrations::run(&mut conn)?;
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "óþekktur".to_string());
+        Ok(Database { conn, user })
     }
 
-    fn create_tables(&self) -> Result<()> {
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS equipment (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                type TEXT NOT NULL,
-                building TEXT NOT NULL,
-                floor INTEGER NOT NULL,
-                room INTEGER NOT NULL,
-                value INTEGER NOT NULL,
-                extra_data TEXT NOT NULL
-            )",
-            [],
-        )?;
-        Ok(())
+    /// Version of the schema this database has been migrated to
+    pub fn schema_version(&self) -> Result<u32> {
+        Ok(migrations::current_version(&self.conn)?)
     }
 
-    pub fn insert_equipment(&self, equipment: &Equipment) -> Result<i64> {
-        let (type_name, building, floor, room, value, extra_data) = match equipment {
-            Equipment::Table(t) => (
-                "Table",
-                t.location.building.to_code(),
-                t.location.floor,
-                t.location.room,
-                t.value,
-                t.seats.to_string(),
-            ),
-            Equipment::Chair(c) => (
-                "Chair",
-                c.location.building.to_code(),
-                c.location.floor,
-                c.location.room,
-                c.value,
-                format!("{}", c.chair_type),
-            ),
-            Equipment::Projector(p) => (
-                "Projector",
-                p.location.building.to_code(),
-                p.location.floor,
-                p.location.room,
-                p.value,
-                p.lumens.to_string(),
-            ),
-        };
+    pub fn user(&self) -> &str {
+        &self.user
+    }
 
-        self.conn.execute(
-            "INSERT INTO equipment (type, building, floor, room, value, extra_data) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![type_name, building, floor, room, value, extra_data],
+    /// Set the user name recorded in history (defaults to the OS user)
+    pub fn set_user(&mut self, user: &str) {
+        self.user = user.to_string();
+    }
+
+    /// Run `f` as one unit: if it returns an error, every change it made is rolled back.
+    /// Calls can be nested.
+    pub fn transaction<T>(&self, f: impl FnOnce(&Database) -> Result<T>) -> Result<T> {
+        let sp = self.savepoint()?;
+        let value = f(self)?;
+        sp.commit()?;
+        Ok(value)
+    }
+
+    /// Savepoints instead of BEGIN so that single-change methods also work inside `transaction`
+    fn savepoint(&self) -> Result<Savepoint<'_>> {
+        self.conn.execute_batch("SAVEPOINT change")?;
+        Ok(Savepoint { conn: &self.conn, done: false })
+    }
+
+    pub fn insert_equipment(&self, equipment: &Equipment) -> Result<i64> {
+        let equipment = self.check_equipment(equipment)?;
+        self.require_room(equipment.get_location())?;
+        let c = TypedColumns::from(equipment.as_ref());
+        let tx = self.savepoint()?;
+        let placeholders: Vec<String> = (1..=TYPED_COLUMNS.len()).map(|i| format!("?{}", i)).collect();
+        tx.execute(
+            &format!("INSERT INTO equipment ({}) VALUES ({})", TYPED_COLUMNS.join(", "), placeholders.join(", ")),
+            c.values().as_slice(),
         )?;
+        let id = tx.last_insert_rowid();
+        self.log(&tx, id, HistoryAction::Created, &Self::describe(&equipment))?;
+        tx.commit()?;
 
-        Ok(self.conn.last_insert_rowid())
+        Ok(id)
     }
 
+    /// Insert with a fixed ID (JSON import). If the ID already exists, active or in the
+    /// recycle bin, that row is overwritten and becomes active.
     pub fn insert_equipment_with_id(&self, id: i64, equipment: &Equipment) -> Result<()> {
-        let (type_name, building, floor, room, value, extra_data) = match equipment {
-            Equipment::Table(t) => (
-                "Table",
-                t.location.building.to_code(),
-                t.location.floor,
-                t.location.room,
-                t.value,
-                t.seats.to_string(),
-            ),
-            Equipment::Chair(c) => (
-                "Chair",
-                c.location.building.to_code(),
-                c.location.floor,
-                c.location.room,
-                c.value,
-                format!("{}", c.chair_type),
-            ),
-            Equipment::Projector(p) => (
-                "Projector",
-                p.location.building.to_code(),
-                p.location.floor,
-                p.location.room,
-                p.value,
-                p.lumens.to_string(),
-            ),
+        let equipment = self.check_equipment(equipment)?;
+        self.require_room(equipment.get_location())?;
+        // A corrupt row may be overwritten, the import repairs it; other read errors stop the write
+        let old = match self.get_any_by_id(id) {
+            Ok(old) => old.map(Ok),
+            Err(e @ Error::CorruptRow { .. }) => Some(Err(e)),
+            Err(e) => return Err(e),
         };
-
-        self.conn.execute(
-            "INSERT INTO equipment (id, type, building, floor, room, value, extra_data)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![id, type_name, building, floor, room, value, extra_data],
+        let c = TypedColumns::from(equipment.as_ref());
+        let tx = self.savepoint()?;
+        // The ID is ?1 and the typed columns follow it
+        let placeholders: Vec<String> = (2..=TYPED_COLUMNS.len() + 1).map(|i| format!("?{}", i)).collect();
+        let updates: Vec<String> = TYPED_COLUMNS.iter().map(|col| format!("{0} = excluded.{0}", col)).collect();
+        let mut values: Vec<&dyn ToSql> = vec![&id];
+        values.extend(c.values());
+        tx.execute(
+            &format!(
+                "INSERT INTO equipment (id, {}) VALUES (?1, {})
+                 ON CONFLICT(id) DO UPDATE SET {}, retired_reason = NULL, retired_at = NULL",
+                TYPED_COLUMNS.join(", "),
+                placeholders.join(", "),
+                updates.join(", ")
+            ),
+            values.as_slice(),
         )?;
+        match old {
+            Some(Ok(old)) => self.log(&tx, id, HistoryAction::Edited, &history::describe_changes(&old, &equipment))?,
+            Some(Err(e)) => self.log(&tx, id, HistoryAction::Edited, &format!("{} → {}", e, Self::describe(&equipment)))?,
+            None => self.log(&tx, id, HistoryAction::Created, &Self::describe(&equipment))?,
+        }
+        tx.commit()?;
         Ok(())
     }
 
     pub fn get_all_equipment(&self) -> Result<Vec<Equipment>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, type, building, floor, room, value, extra_data 
-             FROM equipment 
-             ORDER BY building, floor, room, type",
-        )?;
+        self.query_equipment(
+            &format!("{} ORDER BY building, floor, room, suffix, type", SELECT_EQUIPMENT),
+            [],
+        )
+    }
 
-        let equipment_iter = stmt.query_map([], |row| {
-            let id: i64 = row.get(0)?;
-            let type_name: String = row.get(1)?;
-            let building_code: String = row.get(2)?;
-            let floor: u8 = row.get(3)?;
-            let room: u8 = row.get(4)?;
-            let value: u32 = row.get(5)?;
-            let extra_data: String = row.get(6)?;
-
-            let building = Building::try_from(building_code.as_str()).unwrap();
-            let location = Location::try_from((building, floor, room)).unwrap();
-
-            let equipment = match type_name.as_str() {
-                "Table" => {
-                    let seats = extra_data.parse::<u8>().unwrap_or(0);
-                    Equipment::Table(Table::try_from((location.clone(), value, seats)).unwrap().with_id(id))
-                }
-                "Chair" => {
-                    let chair_type = ChairType::try_from(extra_data.as_str())
-                        .unwrap_or(ChairType::Annad);
-                    Equipment::Chair(Chair::try_from((location.clone(), value, chair_type)).unwrap().with_id(id))
-                }
-                "Projector" => {
-                    let lumens = extra_data.parse::<u32>().unwrap_or(0);
-                    Equipment::Projector(Projector::try_from((location.clone(), value, lumens)).unwrap().with_id(id))
-                }
-                _ => return Err(rusqlite::Error::InvalidQuery),
-            };
+    /// Active item by ID; retired items are only reachable through the recycle bin
+    pub fn get_equipment_by_id(&self, id: i64) -> Result<Option<Equipment>> {
+        self.query_one(&format!("{} AND id = ?1", SELECT_EQUIPMENT), id)
+    }
 
-            Ok(equipment)
-        })?;
+    fn get_any_by_id(&self, id: i64) -> Result<Option<Equipment>> {
+        self.query_one(&format!("{} WHERE id = ?1", SELECT_ALL_ROWS), id)
+    }
 
-        let mut result = Vec::new();
-        for equipment in equipment_iter {
-            result.push(equipment?);
+    fn query_one(&self, sql: &str, id: i64) -> Result<Option<Equipment>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let mut rows = stmt.query_map([id], RawRow::read)?;
+        match rows.next() {
+            Some(raw) => Ok(Some(raw?.into_equipment(&self.get_categories()?)?)),
+            None => Ok(None),
         }
-
-        Ok(result)
     }
 
-    pub fn get_equipment_by_id(&self, id: i64) -> Result<Option<Equipment>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, type, building, floor, room, value, extra_data 
-             FROM equipment 
-             WHERE id = ?1",
+    pub fn update_location(&self, id: i64, location: &Location) -> Result<()> {
+        let old = self.get_equipment_by_id(id)?.ok_or(Error::NotFound(id))?;
+        self.require_room(location)?;
+        let tx = self.savepoint()?;
+        tx.execute(
+            "UPDATE equipment SET building = ?1, floor = ?2, room = ?3, suffix = ?4 WHERE id = ?5",
+            params![location.building.to_code(), location.floor, location.room, suffix_column(location), id],
         )?;
+        let details = format!("{} → {}", old.get_location(), location);
+        self.log(&tx, id, HistoryAction::Moved, &details)?;
+        tx.commit()?;
+        Ok(())
+    }
 
-        let mut equipment_iter = stmt.query_map([id], |row| {
-            let id: i64 = row.get(0)?;
-            let type_name: String = row.get(1)?;
-            let building_code: String = row.get(2)?;
-            let floor: u8 = row.get(3)?;
-            let room: u8 = row.get(4)?;
-            let value: u32 = row.get(5)?;
-            let extra_data: String = row.get(6)?;
-
-            let building = Building::try_from(building_code.as_str()).unwrap();
-            let location = Location::try_from((building, floor, room)).unwrap();
-
-            let equipment = match type_name.as_str() {
-                "Table" => {
-                    let seats = extra_data.parse::<u8>().unwrap_or(0);
-                    Equipment::Table(Table::try_from((location.clone(), value, seats)).unwrap().with_id(id))
-                }
-                "Chair" => {
-                    let chair_type = ChairType::try_from(extra_data.as_str())
-                        .unwrap_or(ChairType::Annad);
-                    Equipment::Chair(Chair::try_from((location.clone(), value, chair_type)).unwrap().with_id(id))
-                }
-                "Projector" => {
-                    let lumens = extra_data.parse::<u32>().unwrap_or(0);
-                    Equipment::Projector(Projector::try_from((location.clone(), value, lumens)).unwrap().with_id(id))
-                }
-                _ => return Err(rusqlite::Error::InvalidQuery),
-            };
-
-            Ok(equipment)
-        })?;
+    /// Rewrite every field of an item, including its type and type-specific attributes
+    pub fn update_equipment(&self, id: i64, equipment: &Equipment) -> Result<()> {
+        let old = self.get_equipment_by_id(id)?.ok_or(Error::NotFound(id))?;
+        let equipment = self.check_equipment(equipment)?;
+        self.require_room(equipment.get_location())?;
+        let c = TypedColumns::from(equipment.as_ref());
+        let tx = self.savepoint()?;
+        let updates: Vec<String> = TYPED_COLUMNS.iter().enumerate().map(|(i, col)| format!("{} = ?{}", col, i + 1)).collect();
+        let mut values = c.values();
+        values.push(&id);
+        tx.execute(
+            &format!("UPDATE equipment SET {} WHERE id = ?{}", updates.join(", "), TYPED_COLUMNS.len() + 1),
+            values.as_slice(),
+        )?;
+        self.log(&tx, id, HistoryAction::Edited, &history::describe_changes(&old, &equipment))?;
+        tx.commit()?;
+        Ok(())
+    }
 
-        if let Some(equipment) = equipment_iter.next() {
-            Ok(Some(equipment?))
-        } else {
-            Ok(None)
-        }
+    /// Move an item to the recycle bin. It disappears from all lists and reports
+    /// but can be brought back with `restore_equipment`.
+    pub fn retire_equipment(&self, id: i64, reason: RetireReason) -> Result<()> {
+        let old = self.get_equipment_by_id(id)?.ok_or(Error::NotFound(id))?;
+        let tx = self.savepoint()?;
+        tx.execute(
+            &format!("UPDATE equipment SET retired_reason = ?1, retired_at = {} WHERE id = ?2", NOW),
+            params![reason.to_code(), id],
+        )?;
+        let details = format!("{}: {}", reason, Self::describe(&old));
+        self.log(&tx, id, HistoryAction::Retired, &details)?;
+        tx.commit()?;
+        Ok(())
     }
 
-    pub fn update_location(&self, id: i64, location: &Location) -> Result<()> {
-        self.conn.execute(
-            "UPDATE equipment SET building = ?1, floor = ?2, room = ?3 WHERE id = ?4",
-            params![location.building.to_code(), location.floor, location.room, id],
+    /// Bring an item back from the recycle bin
+    pub fn restore_equipment(&self, id: i64) -> Result<()> {
+        let tx = self.savepoint()?;
+        let changed = tx.execute(
+            "UPDATE equipment SET retired_reason = NULL, retired_at = NULL
+             WHERE id = ?1 AND retired_reason IS NOT NULL",
+            params![id],
         )?;
+        if changed == 0 {
+            return Err(Error::NotFound(id));
+        }
+        let details = match self.get_any_by_id(id) {
+            Ok(Some(eq)) => Self::describe(&eq),
+            Ok(None) => String::new(),
+            Err(e) => e.to_string(),
+        };
+        self.log(&tx, id, HistoryAction::Restored, &details)?;
+        tx.commit()?;
         Ok(())
     }
 
+    /// Contents of the recycle bin, most recently retired first
+    pub fn get_retired_equipment(&self) -> Result<Vec<RetiredEquipment>> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!("{} ORDER BY retired_at DESC, id", SELECT_RETIRED))?;
+        let rows = stmt.query_map([], |row| {
+            let raw = RawRow::read(row)?;
+            let reason: String = row.get(RawRow::COLUMNS)?;
+            let retired_at: String = row.get(RawRow::COLUMNS + 1)?;
+            Ok((raw, reason, retired_at))
+        })?;
+        let categories = self.get_categories()?;
+        let mut result = Vec::new();
+        for row in rows {
+            let (raw, reason, retired_at) = row?;
+            // Corrupt rows are reported by find_corrupt_rows, same as the active lists
+            if let Ok(equipment) = raw.into_equipment(&categories) {
+                let reason = RetireReason::try_from(reason.as_str()).unwrap_or(RetireReason::Other);
+                result.push(RetiredEquipment { equipment, reason, retired_at });
+            }
+        }
+        Ok(result)
+    }
+
+    /// Permanently remove an item, active or retired. Prefer `retire_equipment`.
     pub fn delete_equipment(&self, id: i64) -> Result<()> {
-        self.conn.execute("DELETE FROM equipment WHERE id = ?1", params![id])?;
+        // Snapshot what is being deleted; a corrupt row can still be deleted
+        let details = match self.get_any_by_id(id) {
+            Ok(Some(old)) => Self::describe(&old),
+            Ok(None) => return Ok(()),
+            Err(e) => e.to_string(),
+        };
+        let tx = self.savepoint()?;
+        tx.execute("DELETE FROM equipment WHERE id = ?1", params![id])?;
+        self.log(&tx, id, HistoryAction::Deleted, &details)?;
+        tx.commit()?;
         Ok(())
     }
 
+    /// Permanently remove every item, including the recycle bin
     pub fn clear_all_equipment(&self) -> Result<()> {
-        self.conn.execute("DELETE FROM equipment", [])?;
+        let tx = self.savepoint()?;
+        tx.execute(
+            &format!(
+                "INSERT INTO equipment_history (equipment_id, action, timestamp, user, details)
+             SELECT id, ?1, {}, ?2,
+                    type || ' í ' || building || '-' || IIF(floor < 0, 'K' || -floor, floor) || '.'
+                         || printf('%02d', room) || suffix || ' (allt tæmt)'
+             FROM equipment",
+                NOW
+            ),
+            params![HistoryAction::Deleted.to_code(), self.user],
+        )?;
+        tx.execute("DELETE FROM equipment", [])?;
+        tx.commit()?;
         Ok(())
     }
 
@@ -232,195 +335,1287 @@ impl Database {
         Ok(())
     }
 
-    pub fn get_equipment_by_building(&self, building: Building) -> Result<Vec<Equipment>> {
+    pub fn get_equipment_by_building(&self, building: &Building) -> Result<Vec<Equipment>> {
+        self.query_equipment(
+            &format!("{} AND building = ?1 ORDER BY floor, room, suffix, type", SELECT_EQUIPMENT),
+            params![building.to_code()],
+        )
+    }
+
+    pub fn get_equipment_by_type(&self, type_name: &str) -> Result<Vec<Equipment>> {
+        self.query_equipment(
+            &format!("{} AND type = ?1 ORDER BY building, floor, room, suffix", SELECT_EQUIPMENT),
+            params![type_name],
+        )
+    }
+
+    pub fn get_equipment_by_room(&self, location: &Location) -> Result<Vec<Equipment>> {
+        self.query_equipment(
+            &format!("{} AND {} ORDER BY type", SELECT_EQUIPMENT, SAME_ROOM),
+            params![location.building.to_code(), location.floor, location.room, suffix_column(location)],
+        )
+    }
+
+    pub fn get_equipment_by_condition(&self, condition: Condition) -> Result<Vec<Equipment>> {
+        self.query_equipment(
+            &format!("{} AND condition = ?1 ORDER BY building, floor, room, suffix, type", SELECT_EQUIPMENT),
+            params![condition.to_code()],
+        )
+    }
+
+    pub fn get_equipment_by_floor(&self, building: &Building, floor: i8) -> Result<Vec<Equipment>> {
+        self.query_equipment(
+            &format!("{} AND building = ?1 AND floor = ?2 ORDER BY room, suffix, type", SELECT_EQUIPMENT),
+            params![building.to_code(), floor],
+        )
+    }
+
+    /// Rooms with active equipment in a building, or on one floor of it, in floor/room order
+    pub fn get_occupied_rooms(&self, building: &Building, floor: Option<i8>) -> Result<Vec<Location>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, type, building, floor, room, value, extra_data 
-             FROM equipment 
-             WHERE building = ?1
-             ORDER BY floor, room, type",
+            "SELECT DISTINCT floor, room, suffix FROM equipment
+             WHERE retired_reason IS NULL AND building = ?1 AND (?2 IS NULL OR floor = ?2)
+             ORDER BY floor, room, suffix",
         )?;
-
-        let equipment_iter = stmt.query_map([building.to_code()], |row| {
-            let id: i64 = row.get(0)?;
-            let type_name: String = row.get(1)?;
-            let building_code: String = row.get(2)?;
-            let floor: u8 = row.get(3)?;
-            let room: u8 = row.get(4)?;
-            let value: u32 = row.get(5)?;
-            let extra_data: String = row.get(6)?;
-
-            let building = Building::try_from(building_code.as_str()).unwrap();
-            let location = Location::try_from((building, floor, room)).unwrap();
-
-            let equipment = match type_name.as_str() {
-                "Table" => {
-                    let seats = extra_data.parse::<u8>().unwrap_or(0);
-                    Equipment::Table(Table::try_from((location.clone(), value, seats)).unwrap().with_id(id))
-                }
-                "Chair" => {
-                    let chair_type = ChairType::try_from(extra_data.as_str())
-                        .unwrap_or(ChairType::Annad);
-                    Equipment::Chair(Chair::try_from((location.clone(), value, chair_type)).unwrap().with_id(id))
-                }
-                "Projector" => {
-                    let lumens = extra_data.parse::<u32>().unwrap_or(0);
-                    Equipment::Projector(Projector::try_from((location.clone(), value, lumens)).unwrap().with_id(id))
+        let rows = stmt.query_map(params![building.to_code(), floor], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, String>(2)?))
+        })?;
+        let mut rooms = Vec::new();
+        for row in rows {
+            let (floor, room, suffix) = row?;
+            // Out-of-range values are corrupt rows, reported by find_corrupt_rows
+            if let (Ok(floor), Ok(room)) = (i8::try_from(floor), u16::try_from(room)) {
+                if let Ok(location) = Location::try_from((building.clone(), floor, room, suffix.chars().next())) {
+                    rooms.push(location);
                 }
-                _ => return Err(rusqlite::Error::InvalidQuery),
-            };
+            }
+        }
+        Ok(rooms)
+    }
+
+    /// Every registered building, by name
+    pub fn get_buildings(&self) -> Result<Vec<BuildingInfo>> {
+        self.query_buildings("ORDER BY name", [])
+    }
+
+    pub fn get_building(&self, building: &Building) -> Result<Option<BuildingInfo>> {
+        Ok(self.query_buildings("WHERE code = ?1", params![building.to_code()])?.pop())
+    }
+
+    /// Building given by its code or its full name (case-insensitive), as typed on the command line
+    pub fn find_building(&self, text: &str) -> Result<BuildingInfo> {
+        let text = text.trim();
+        let buildings = self.get_buildings()?;
+        buildings
+            .iter()
+            .find(|b| b.building.to_code() == text)
+            .or_else(|| buildings.iter().find(|b| b.name.to_lowercase() == text.to_lowercase()))
+            .or_else(|| {
+                let legacy = Building::try_from(text).ok()?;
+                buildings.iter().find(|b| b.building == legacy)
+            })
+            .cloned()
+            .ok_or_else(|| Error::UnknownBuilding(text.to_string()))
+    }
+
+    pub fn add_building(&self, info: &BuildingInfo) -> Result<()> {
+        if self.get_building(&info.building)?.is_some() {
+            return Err(Error::BuildingExists(info.building.to_string()));
+        }
+        self.conn.execute(
+            "INSERT INTO buildings (code, name, address) VALUES (?1, ?2, ?3)",
+            params![info.building.to_code(), info.name.trim(), info.address.trim()],
+        )?;
+        Ok(())
+    }
+
+    /// Change a building's name and address; the code is fixed since equipment refers to it
+    pub fn update_building(&self, info: &BuildingInfo) -> Result<()> {
+        let changed = self.conn.execute(
+            "UPDATE buildings SET name = ?1, address = ?2 WHERE code = ?3",
+            params![info.name.trim(), info.address.trim(), info.building.to_code()],
+        )?;
+        if changed == 0 {
+            return Err(Error::UnknownBuilding(info.building.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Remove a building, and its rooms, that no equipment refers to, including retired items
+    pub fn delete_building(&self, building: &Building) -> Result<()> {
+        self.require_building(building)?;
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM equipment WHERE building = ?1",
+            params![building.to_code()],
+            |row| row.get(0),
+        )?;
+        if count > 0 {
+            return Err(Error::BuildingInUse { code: building.to_string(), count: count as usize });
+        }
+        let tx = self.savepoint()?;
+        tx.execute("DELETE FROM rooms WHERE building = ?1", params![building.to_code()])?;
+        tx.execute("DELETE FROM buildings WHERE code = ?1", params![building.to_code()])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn require_building(&self, building: &Building) -> Result<()> {
+        match self.get_building(building)? {
+            Some(_) => Ok(()),
+            None => Err(Error::UnknownBuilding(building.to_string())),
+        }
+    }
 
-            Ok(equipment)
+    fn query_buildings<P: Params>(&self, clause: &str, params: P) -> Result<Vec<BuildingInfo>> {
+        let mut stmt = self.conn.prepare(&format!("SELECT code, name, address FROM buildings {}", clause))?;
+        let rows = stmt.query_map(params, |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
         })?;
+        let mut buildings = Vec::new();
+        for row in rows {
+            let (code, name, address) = row?;
+            // Codes are checked when added, so a bad one was typed into the file by hand
+            if let Ok(building) = Building::new(&code) {
+                buildings.push(BuildingInfo { building, name, address });
+            }
+        }
+        Ok(buildings)
+    }
 
-        let mut result = Vec::new();
-        for equipment in equipment_iter {
-            result.push(equipment?);
+    /// Every registered room, in building/floor/room order
+    pub fn get_rooms(&self) -> Result<Vec<Room>> {
+        self.query_rooms("ORDER BY building, floor, room, suffix", [])
+    }
+
+    pub fn get_room(&self, location: &Location) -> Result<Option<Room>> {
+        Ok(self
+            .query_rooms(
+                &format!("WHERE {}", SAME_ROOM),
+                params![location.building.to_code(), location.floor, location.room, suffix_column(location)],
+            )?
+            .pop())
+    }
+
+    /// Register a room in an existing building
+    pub fn add_room(&self, room: &Room) -> Result<()> {
+        self.require_building(&room.location.building)?;
+        if self.get_room(&room.location)?.is_some() {
+            return Err(Error::RoomExists(room.location.to_string()));
         }
+        let loc = &room.location;
+        self.conn.execute(
+            "INSERT INTO rooms (building, floor, room, suffix, name, room_type, capacity, responsible)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                loc.building.to_code(),
+                loc.floor,
+                loc.room,
+                suffix_column(loc),
+                room.name.trim(),
+                room.room_type.to_code(),
+                room.capacity,
+                room.responsible.trim()
+            ],
+        )?;
+        Ok(())
+    }
 
-        Ok(result)
+    /// Change everything about a room except where it is
+    pub fn update_room(&self, room: &Room) -> Result<()> {
+        let loc = &room.location;
+        let changed = self.conn.execute(
+            &format!("UPDATE rooms SET name = ?5, room_type = ?6, capacity = ?7, responsible = ?8 WHERE {}", SAME_ROOM),
+            params![
+                loc.building.to_code(),
+                loc.floor,
+                loc.room,
+                suffix_column(loc),
+                room.name.trim(),
+                room.room_type.to_code(),
+                room.capacity,
+                room.responsible.trim()
+            ],
+        )?;
+        if changed == 0 {
+            return Err(Error::UnknownRoom(loc.to_string()));
+        }
+        Ok(())
     }
 
-    pub fn get_equipment_by_type(&self, type_name: &str) -> Result<Vec<Equipment>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, type, building, floor, room, value, extra_data 
-             FROM equipment 
-             WHERE type = ?1
-             ORDER BY building, floor, room",
+    /// Remove a room that no equipment refers to, including retired items
+    pub fn delete_room(&self, location: &Location) -> Result<()> {
+        self.require_room(location)?;
+        let suffix = suffix_column(location);
+        let params = params![location.building.to_code(), location.floor, location.room, suffix];
+        let count: i64 = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM equipment WHERE {}", SAME_ROOM),
+            params,
+            |row| row.get(0),
         )?;
+        if count > 0 {
+            return Err(Error::RoomInUse { room: location.to_string(), count: count as usize });
+        }
+        self.conn.execute(&format!("DELETE FROM rooms WHERE {}", SAME_ROOM), params)?;
+        Ok(())
+    }
 
-        let equipment_iter = stmt.query_map([type_name], |row| {
-            let id: i64 = row.get(0)?;
-            let type_name: String = row.get(1)?;
-            let building_code: String = row.get(2)?;
-            let floor: u8 = row.get(3)?;
-            let room: u8 = row.get(4)?;
-            let value: u32 = row.get(5)?;
-            let extra_data: String = row.get(6)?;
-
-            let building = Building::try_from(building_code.as_str()).unwrap();
-            let location = Location::try_from((building, floor, room)).unwrap();
-
-            let equipment = match type_name.as_str() {
-                "Table" => {
-                    let seats = extra_data.parse::<u8>().unwrap_or(0);
-                    Equipment::Table(Table::try_from((location.clone(), value, seats)).unwrap().with_id(id))
-                }
-                "Chair" => {
-                    let chair_type = ChairType::try_from(extra_data.as_str())
-                        .unwrap_or(ChairType::Annad);
-                    Equipment::Chair(Chair::try_from((location.clone(), value, chair_type)).unwrap().with_id(id))
-                }
-                "Projector" => {
-                    let lumens = extra_data.parse::<u32>().unwrap_or(0);
-                    Equipment::Projector(Projector::try_from((location.clone(), value, lumens)).unwrap().with_id(id))
-                }
-                _ => return Err(rusqlite::Error::InvalidQuery),
-            };
+    /// Equipment may only be placed in registered rooms of registered buildings
+    fn require_room(&self, location: &Location) -> Result<()> {
+        self.require_building(&location.building)?;
+        match self.get_room(location)? {
+            Some(_) => Ok(()),
+            None => Err(Error::UnknownRoom(location.to_string())),
+        }
+    }
 
-            Ok(equipment)
+    fn query_rooms<P: Params>(&self, clause: &str, params: P) -> Result<Vec<Room>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT building, floor, room, suffix, name, room_type, capacity, responsible FROM rooms {}",
+            clause
+        ))?;
+        let rows = stmt.query_map(params, |row| {
+            Ok((
+                (row.get::<_, String>(0)?, row.get::<_, i8>(1)?, row.get::<_, u16>(2)?, row.get::<_, String>(3)?),
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, u16>(6)?,
+                row.get::<_, String>(7)?,
+            ))
         })?;
+        let mut rooms = Vec::new();
+        for row in rows {
+            let ((building, floor, room, suffix), name, room_type, capacity, responsible) = row?;
+            let location = Location::try_from((Building::try_from(building)?, floor, room, suffix.chars().next()))?;
+            // Unknown codes can only come from a newer version
+            let room_type = RoomType::try_from(room_type).unwrap_or(RoomType::Other);
+            rooms.push(Room { location, name, room_type, capacity, responsible });
+        }
+        Ok(rooms)
+    }
 
-        let mut result = Vec::new();
-        for equipment in equipment_iter {
-            result.push(equipment?);
+    /// Every user-defined category, by name
+    pub fn get_categories(&self) -> Result<Vec<Category>> {
+        self.query_categories("ORDER BY name", [])
+    }
+
+    pub fn get_category(&self, code: &str) -> Result<Option<Category>> {
+        Ok(self.query_categories("WHERE code = ?1", params![code.trim()])?.pop())
+    }
+
+    /// Category given by its code or its name (case-insensitive), as typed on the command line
+    pub fn find_category(&self, text: &str) -> Result<Category> {
+        let text = text.trim().to_lowercase();
+        self.get_categories()?
+            .into_iter()
+            .find(|c| c.code.to_lowercase() == text || c.name.to_lowercase() == text)
+            .ok_or_else(|| Error::UnknownCategory(text.to_string()))
+    }
+
+    pub fn add_category(&self, category: &Category) -> Result<()> {
+        category.check()?;
+        if self.get_category(&category.code)?.is_some() {
+            return Err(Error::CategoryExists(category.code.clone()));
         }
+        let tx = self.savepoint()?;
+        tx.execute("INSERT INTO categories (code, name) VALUES (?1, ?2)", params![category.code, category.name])?;
+        Self::write_attributes(&tx, category)?;
+        tx.commit()?;
+        Ok(())
+    }
 
-        Ok(result)
+    /// Change a category's name and attributes; the code is fixed since equipment refers to it.
+    /// Items already registered keep their values and are checked against the new
+    /// definition the next time they are edited.
+    pub fn update_category(&self, category: &Category) -> Result<()> {
+        category.check()?;
+        let tx = self.savepoint()?;
+        let changed = tx.execute("UPDATE categories SET name = ?1 WHERE code = ?2", params![category.name, category.code])?;
+        if changed == 0 {
+            return Err(Error::UnknownCategory(category.code.clone()));
+        }
+        tx.execute("DELETE FROM category_attributes WHERE category = ?1", params![category.code])?;
+        Self::write_attributes(&tx, category)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Remove a category that no equipment is registered in, including retired items
+    pub fn delete_category(&self, code: &str) -> Result<()> {
+        if self.get_category(code)?.is_none() {
+            return Err(Error::UnknownCategory(code.to_string()));
+        }
+        let count: i64 = self.conn.query_row("SELECT COUNT(*) FROM equipment WHERE type = ?1", params![code], |row| row.get(0))?;
+        if count > 0 {
+            return Err(Error::CategoryInUse { code: code.to_string(), count: count as usize });
+        }
+        let tx = self.savepoint()?;
+        tx.execute("DELETE FROM category_attributes WHERE category = ?1", params![code])?;
+        tx.execute("DELETE FROM depreciation WHERE type = ?1", params![code])?;
+        tx.execute("DELETE FROM categories WHERE code = ?1", params![code])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Depreciation periods of every type that has one
+    pub fn get_depreciation(&self) -> Result<Depreciation> {
+        let mut stmt = self.conn.prepare("SELECT type, years FROM depreciation")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+        let mut depreciation = Depreciation::default();
+        for row in rows {
+            let (type_code, years) = row?;
+            // Out-of-range periods can only come from editing the file by hand; such types are not depreciated
+            if let Ok(years) = u8::try_from(years) {
+                depreciation.years.insert(type_code, years);
+            }
+        }
+        Ok(depreciation)
+    }
+
+    /// Set the period of a built-in type or category, given by code; `None` stops depreciating it
+    pub fn set_depreciation_years(&self, type_code: &str, years: Option<u8>) -> Result<()> {
+        let built_in = category::BUILT_IN_TYPES.contains(&type_code);
+        if !built_in && self.get_category(type_code)?.is_none() {
+            return Err(Error::UnknownCategory(type_code.to_string()));
+        }
+        match years {
+            Some(years) if !(1..=depreciation::MAX_YEARS).contains(&years) => {
+                return Err(Error::InvalidDepreciationYears(years.to_string()));
+            }
+            Some(years) => {
+                self.conn.execute(
+                    "INSERT INTO depreciation (type, years) VALUES (?1, ?2) ON CONFLICT (type) DO UPDATE SET years = excluded.years",
+                    params![type_code, years],
+                )?;
+            }
+            None => {
+                self.conn.execute("DELETE FROM depreciation WHERE type = ?1", params![type_code])?;
+            }
+        }
+        Ok(())
     }
 
-    pub fn get_equipment_by_room(&self, building: Building, floor: u8, room: u8) -> Result<Vec<Equipment>> {
+    fn write_attributes(conn: &Connection, category: &Category) -> Result<()> {
+        for (position, a) in category.attributes.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO category_attributes (category, position, key, label, kind, required, min, max, choices, unit)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    category.code,
+                    position,
+                    a.key,
+                    a.label,
+                    a.kind.to_code(),
+                    a.required,
+                    a.min,
+                    a.max,
+                    serde_json::to_string(&a.choices)?,
+                    a.unit
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn query_categories<P: Params>(&self, clause: &str, params: P) -> Result<Vec<Category>> {
+        let mut stmt = self.conn.prepare(&format!("SELECT code, name FROM categories {}", clause))?;
+        let rows = stmt.query_map(params, |row| Ok(Category::new(&row.get::<_, String>(0)?, &row.get::<_, String>(1)?)))?;
+        let mut categories = Vec::new();
+        for category in rows {
+            categories.push(category?);
+        }
         let mut stmt = self.conn.prepare(
-            "SELECT id, type, building, floor, room, value, extra_data 
-             FROM equipment 
-             WHERE building = ?1 AND floor = ?2 AND room = ?3
-             ORDER BY type",
+            "SELECT key, label, kind, required, min, max, choices, unit FROM category_attributes
+             WHERE category = ?1 ORDER BY position",
         )?;
+        for category in &mut categories {
+            let rows = stmt.query_map(params![category.code], |row| {
+                Ok((
+                    AttributeDef::new(&row.get::<_, String>(0)?, &row.get::<_, String>(1)?, AttributeKind::Text),
+                    row.get::<_, String>(2)?,
+                    row.get::<_, bool>(3)?,
+                    (row.get::<_, Option<i64>>(4)?, row.get::<_, Option<i64>>(5)?),
+                    row.get::<_, String>(6)?,
+                    row.get::<_, String>(7)?,
+                ))
+            })?;
+            for row in rows {
+                let (mut def, kind, required, (min, max), choices, unit) = row?;
+                // Unknown kinds can only come from a newer version; text shows any value
+                def.kind = AttributeKind::try_from(kind).unwrap_or(AttributeKind::Text);
+                def.required = required;
+                def.min = min;
+                def.max = max;
+                def.choices = serde_json::from_str(&choices)?;
+                def.unit = unit;
+                category.attributes.push(def);
+            }
+        }
+        Ok(categories)
+    }
 
-        let equipment_iter = stmt.query_map(params![building.to_code(), floor, room], |row| {
-            let id: i64 = row.get(0)?;
-            let type_name: String = row.get(1)?;
-            let building_code: String = row.get(2)?;
-            let floor: u8 = row.get(3)?;
-            let room: u8 = row.get(4)?;
-            let value: u32 = row.get(5)?;
-            let extra_data: String = row.get(6)?;
-
-            let building = Building::from_code(&building_code).unwrap();
-            let location = Location::new(building, floor, room);
-
-            let equipment = match type_name.as_str() {
-                "Table" => {
-                    let seats = extra_data.parse::<u8>().unwrap_or(0);
-                    Equipment::Table(Table::try_from((location.clone(), value, seats)).unwrap().with_id(id))
-                }
-                "Chair" => {
-                    let chair_type = ChairType::try_from(extra_data.as_str())
-                        .unwrap_or(ChairType::Annad);
-                    Equipment::Chair(Chair::try_from((location.clone(), value, chair_type)).unwrap().with_id(id))
-                }
-                "Projector" => {
-                    let lumens = extra_data.parse::<u32>().unwrap_or(0);
-                    Equipment::Projector(Projector::try_from((location.clone(), value, lumens)).unwrap().with_id(id))
-                }
-                _ => return Err(rusqlite::Error::InvalidQuery),
-            };
+    /// Run `Equipment::check`. Items in a category must also have a stored category and
+    /// attribute values that fit it; they are returned with the stored definition and
+    /// normalised values.
+    fn check_equipment<'a>(&self, equipment: &'a Equipment) -> Result<Cow<'a, Equipment>> {
+        equipment.check()?;
+        let Equipment::Custom(item) = equipment else {
+            return Ok(Cow::Borrowed(equipment));
+        };
+        let category = self.get_category(&item.category.code)?.ok_or_else(|| Error::UnknownCategory(item.category.code.clone()))?;
+        let attributes = category.check_attributes(&item.attributes)?;
+        let mut checked = item.clone();
+        checked.category = category;
+        checked.attributes = attributes;
+        Ok(Cow::Owned(Equipment::Custom(checked)))
+    }
 
-            Ok(equipment)
-        })?;
+    /// Timeline of one item, oldest first. Kept after the item is deleted.
+    pub fn get_history(&self, equipment_id: i64) -> Result<Vec<HistoryEntry>> {
+        self.query_history(
+            &format!("{} WHERE equipment_id = ?1 ORDER BY id", SELECT_HISTORY),
+            params![equipment_id],
+        )
+    }
+
+    /// Most recent changes across all items, newest first
+    pub fn get_recent_history(&self, limit: u32) -> Result<Vec<HistoryEntry>> {
+        self.query_history(
+            &format!("{} ORDER BY id DESC LIMIT ?1", SELECT_HISTORY),
+            params![limit],
+        )
+    }
 
+    fn query_history<P: Params>(&self, sql: &str, params: P) -> Result<Vec<HistoryEntry>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params, |row| {
+            let action: String = row.get(2)?;
+            Ok(HistoryEntry {
+                id: row.get(0)?,
+                equipment_id: row.get(1)?,
+                // Unknown codes can only come from a newer version; show them as edits
+                action: HistoryAction::from_code(&action).unwrap_or(HistoryAction::Edited),
+                timestamp: row.get(3)?,
+                user: row.get(4)?,
+                details: row.get(5)?,
+            })
+        })?;
         let mut result = Vec::new();
-        for equipment in equipment_iter {
-            result.push(equipment?);
+        for entry in rows {
+            result.push(entry?);
         }
-
         Ok(result)
     }
 
-    pub fn get_equipment_by_floor(&self, building: Building, floor: u8) -> Result<Vec<Equipment>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, type, building, floor, room, value, extra_data 
-             FROM equipment 
-             WHERE building = ?1 AND floor = ?2
-             ORDER BY room, type",
+    fn log(&self, conn: &Connection, equipment_id: i64, action: HistoryAction, details: &str) -> Result<()> {
+        conn.execute(
+            &format!("INSERT INTO equipment_history (equipment_id, action, timestamp, user, details)
+             VALUES (?1, ?2, {}, ?3, ?4)", NOW),
+            params![equipment_id, action.to_code(), self.user, details],
         )?;
+        Ok(())
+    }
 
-        let equipment_iter = stmt.query_map(params![building.to_code(), floor], |row| {
-            let id: i64 = row.get(0)?;
-            let type_name: String = row.get(1)?;
-            let building_code: String = row.get(2)?;
-            let floor: u8 = row.get(3)?;
-            let room: u8 = row.get(4)?;
-            let value: u32 = row.get(5)?;
-            let extra_data: String = row.get(6)?;
-
-            let building = Building::from_code(&building_code).unwrap();
-            let location = Location::new(building, floor, room);
-
-            let equipment = match type_name.as_str() {
-                "Table" => {
-                    let seats = extra_data.parse::<u8>().unwrap_or(0);
-                    Equipment::Table(Table::try_from((location.clone(), value, seats)).unwrap().with_id(id))
-                }
-                "Chair" => {
-                    let chair_type = ChairType::try_from(extra_data.as_str())
-                        .unwrap_or(ChairType::Annad);
-                    Equipment::Chair(Chair::try_from((location.clone(), value, chair_type)).unwrap().with_id(id))
-                }
-                "Projector" => {
-                    let lumens = extra_data.parse::<u32>().unwrap_or(0);
-                    Equipment::Projector(Projector::try_from((location.clone(), value, lumens)).unwrap().with_id(id))
-                }
-                _ => return Err(rusqlite::Error::InvalidQuery),
+    /// Short description for history entries: type and location
+    fn describe(equipment: &Equipment) -> String {
+        format!(
+            "{} í {}, {} kr.",
+            equipment.get_type_name(),
+            equipment.get_location(),
+            equipment.get_value()
+        )
+    }
+
+    /// Begin a stocktake of one room. Returns its number.
+    pub fn start_audit(&self, location: &Location) -> Result<i64> {
+        self.require_room(location)?;
+        self.conn.execute(
+            &format!(
+                "INSERT INTO audits (building, floor, room, suffix, user, started_at) VALUES (?1, ?2, ?3, ?4, ?5, {})",
+                NOW
+            ),
+            params![location.building.to_code(), location.floor, location.room, suffix_column(location), self.user],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Record scanned or typed text in an open stocktake. Returns the scan's row ID.
+    pub fn add_audit_scan(&self, audit_id: i64, text: &str) -> Result<i64> {
+        self.require_open_audit(audit_id)?;
+        self.conn.execute(
+            &format!("INSERT INTO audit_scans (audit_id, text, equipment_id, scanned_at) VALUES (?1, ?2, ?3, {})", NOW),
+            params![audit_id, text.trim(), audit::parse_scan(text)],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Take back a scan, e.g. one typed by mistake
+    pub fn remove_audit_scan(&self, audit_id: i64, scan_id: i64) -> Result<()> {
+        self.require_open_audit(audit_id)?;
+        self.conn.execute("DELETE FROM audit_scans WHERE id = ?1 AND audit_id = ?2", params![scan_id, audit_id])?;
+        Ok(())
+    }
+
+    /// Close a stocktake and save its report; it cannot be changed after this
+    pub fn finish_audit(&self, audit_id: i64, report: &AuditReport) -> Result<()> {
+        self.require_open_audit(audit_id)?;
+        self.conn.execute(
+            &format!("UPDATE audits SET finished_at = {}, report = ?1 WHERE id = ?2", NOW),
+            params![serde_json::to_string(report)?, audit_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_audit(&self, audit_id: i64) -> Result<Option<AuditSession>> {
+        Ok(self.query_audits("WHERE id = ?1", params![audit_id])?.pop())
+    }
+
+    /// Every stocktake, newest first
+    pub fn get_audits(&self) -> Result<Vec<AuditSession>> {
+        self.query_audits("ORDER BY id DESC", [])
+    }
+
+    fn require_open_audit(&self, audit_id: i64) -> Result<()> {
+        let session = self.get_audit(audit_id)?.ok_or(Error::AuditNotFound(audit_id))?;
+        if session.is_finished() {
+            return Err(Error::AuditFinished(audit_id));
+        }
+        Ok(())
+    }
+
+    fn query_audits<P: Params>(&self, clause: &str, params: P) -> Result<Vec<AuditSession>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, building, floor, room, suffix, user, started_at, finished_at, report FROM audits {}",
+            clause
+        ))?;
+        let rows = stmt.query_map(params, |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                (row.get::<_, String>(1)?, row.get::<_, i8>(2)?, row.get::<_, u16>(3)?, row.get::<_, String>(4)?),
+                row.get::<_, String>(5)?,
+                row.get::<_, String>(6)?,
+                row.get::<_, Option<String>>(7)?,
+                row.get::<_, Option<String>>(8)?,
+            ))
+        })?;
+        let mut sessions = Vec::new();
+        for row in rows {
+            let (id, (building, floor, room, suffix), user, started_at, finished_at, report) = row?;
+            let location = Location::try_from((Building::try_from(building)?, floor, room, suffix.chars().next()))?;
+            let report = match report {
+                Some(json) => Some(serde_json::from_str(&json)?),
+                None => None,
             };
+            let scans = self.get_audit_scans(id)?;
+            sessions.push(AuditSession { id, location, user, started_at, finished_at, scans, report });
+        }
+        Ok(sessions)
+    }
 
-            Ok(equipment)
+    fn get_audit_scans(&self, audit_id: i64) -> Result<Vec<AuditScan>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, text, equipment_id, scanned_at FROM audit_scans WHERE audit_id = ?1 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![audit_id], |row| {
+            Ok(AuditScan { id: row.get(0)?, text: row.get(1)?, equipment_id: row.get(2)?, scanned_at: row.get(3)? })
         })?;
+        let mut scans = Vec::new();
+        for scan in rows {
+            scans.push(scan?);
+        }
+        Ok(scans)
+    }
 
-        let mut result = Vec::new();
-        for equipment in equipment_iter {
-            result.push(equipment?);
+    /// Scan every row and return the ones that cannot be read as `Equipment`.
+    /// The list queries skip such rows, so this is how they get reported.
+    pub fn find_corrupt_rows(&self) -> Result<Vec<Error>> {
+        let mut stmt = self.conn.prepare(SELECT_ALL_ROWS)?;
+        let rows = stmt.query_map([], RawRow::read)?;
+        let categories = self.get_categories()?;
+        let mut corrupt = Vec::new();
+        for raw in rows {
+            if let Err(e) = raw?.into_equipment(&categories) {
+                corrupt.push(e);
+            }
         }
+        Ok(corrupt)
+    }
 
+    /// Run an equipment SELECT, skipping rows that fail to convert
+    fn query_equipment<P: Params>(&self, sql: &str, params: P) -> Result<Vec<Equipment>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params, RawRow::read)?;
+        let categories = self.get_categories()?;
+        let mut result = Vec::new();
+        for raw in rows {
+            if let Ok(equipment) = raw?.into_equipment(&categories) {
+                result.push(equipment);
+            }
+        }
         Ok(result)
     }
 }
+
+/// Open savepoint; rolled back on drop unless committed
+struct Savepoint<'a> {
+    conn: &'a Connection,
+    done: bool,
+}
+
+impl Savepoint<'_> {
+    fn commit(mut self) -> Result<()> {
+        self.done = true;
+        self.conn.execute_batch("RELEASE change")?;
+        Ok(())
+    }
+}
+
+impl std::ops::Deref for Savepoint<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+    }
+}
+
+impl Drop for Savepoint<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            let _ = self.conn.execute_batch("ROLLBACK TO change; RELEASE change");
+        }
+    }
+}
+
+/// Column values for one `Equipment`; per-type columns are NULL for other types, and
+/// `EquipmentInfo` fields are NULL when they are empty
+struct TypedColumns {
+    type_name: String,
+    building: String,
+    floor: i8,
+    room: u16,
+    suffix: String,
+    value: u32,
+    seats: Option<u8>,
+    chair_type: Option<String>,
+    lumens: Option<u32>,
+    /// JSON object of a category item's attribute values
+    attributes: Option<String>,
+    serial_number: Option<String>,
+    manufacturer: Option<String>,
+    model: Option<String>,
+    computer_kind: Option<&'static str>,
+    cpu: Option<String>,
+    ram_gb: Option<u16>,
+    screen_size: Option<f64>,
+    resolution: Option<String>,
+    asset_tag: Option<String>,
+    notes: Option<String>,
+    /// `YYYY-MM-DD`
+    purchase_date: Option<String>,
+    supplier: Option<String>,
+    invoice_number: Option<String>,
+    condition: &'static str,
+}
+
+impl TypedColumns {
+    /// Values in the order of `TYPED_COLUMNS`
+    fn values(&self) -> Vec<&dyn ToSql> {
+        vec![
+            &self.type_name, &self.building, &self.floor, &self.room, &self.suffix, &self.value, &self.seats,
+            &self.chair_type, &self.lumens, &self.attributes, &self.serial_number, &self.manufacturer, &self.model,
+            &self.computer_kind, &self.cpu, &self.ram_gb, &self.screen_size, &self.resolution, &self.asset_tag, &self.notes,
+            &self.purchase_date, &self.supplier, &self.invoice_number, &self.condition,
+        ]
+    }
+}
+
+impl From<&Equipment> for TypedColumns {
+    fn from(equipment: &Equipment) -> Self {
+        let location = equipment.get_location();
+        let info = equipment.info();
+        let text = |value: &str| Some(value.to_string()).filter(|v| !v.is_empty());
+        let mut c = TypedColumns {
+            type_name: equipment.type_code().to_string(),
+            building: location.building.to_code().to_string(),
+            floor: location.floor,
+            room: location.room,
+            suffix: suffix_column(location),
+            value: equipment.get_value(),
+            seats: None,
+            chair_type: None,
+            lumens: None,
+            attributes: None,
+            serial_number: text(&info.serial_number),
+            manufacturer: text(&info.manufacturer),
+            model: text(&info.model),
+            computer_kind: None,
+            cpu: None,
+            ram_gb: None,
+            screen_size: None,
+            resolution: None,
+            asset_tag: text(&info.asset_tag),
+            notes: text(&info.notes),
+            purchase_date: info.purchase_date.map(|d| d.format("%Y-%m-%d").to_string()),
+            supplier: text(&info.supplier),
+            invoice_number: text(&info.invoice_number),
+            condition: info.condition.to_code(),
+        };
+        match equipment {
+            Equipment::Table(t) => c.seats = Some(t.seats),
+            Equipment::Chair(ch) => c.chair_type = Some(ch.chair_type.to_string()),
+            Equipment::Projector(p) => c.lumens = Some(p.lumens),
+            Equipment::Computer(pc) => {
+                c.computer_kind = Some(pc.kind.to_code());
+                c.cpu = Some(pc.cpu.clone());
+                c.ram_gb = pc.ram_gb;
+            }
+            Equipment::Monitor(m) => {
+                c.screen_size = Some(f64::from(m.screen_size));
+                c.resolution = Some(m.resolution.to_string());
+            }
+            // A map of strings and numbers always serialises
+            Equipment::Custom(x) => c.attributes = serde_json::to_string(&x.attributes).ok(),
+        }
+        c
+    }
+}
+
+/// Untyped equipment row, read without any conversion that could fail on bad data
+struct RawRow {
+    id: i64,
+    type_name: String,
+    building: String,
+    floor: i64,
+    room: i64,
+    suffix: String,
+    value: i64,
+    seats: Option<i64>,
+    chair_type: Option<String>,
+    lumens: Option<i64>,
+    attributes: Option<String>,
+    serial_number: Option<String>,
+    manufacturer: Option<String>,
+    model: Option<String>,
+    computer_kind: Option<String>,
+    cpu: Option<String>,
+    ram_gb: Option<i64>,
+    screen_size: Option<f64>,
+    resolution: Option<String>,
+    asset_tag: Option<String>,
+    notes: Option<String>,
+    purchase_date: Option<String>,
+    supplier: Option<String>,
+    invoice_number: Option<String>,
+    condition: String,
+}
+
+impl RawRow {
+    /// Number of columns in `equipment_columns!`; queries may select more after them
+    const COLUMNS: usize = 25;
+
+    fn read(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(RawRow {
+            id: row.get(0)?,
+            type_name: row.get(1)?,
+            building: row.get(2)?,
+            floor: row.get(3)?,
+            room: row.get(4)?,
+            suffix: row.get(5)?,
+            value: row.get(6)?,
+            seats: row.get(7)?,
+            chair_type: row.get(8)?,
+            lumens: row.get(9)?,
+            attributes: row.get(10)?,
+            serial_number: row.get(11)?,
+            manufacturer: row.get(12)?,
+            model: row.get(13)?,
+            computer_kind: row.get(14)?,
+            cpu: row.get(15)?,
+            ram_gb: row.get(16)?,
+            screen_size: row.get(17)?,
+            resolution: row.get(18)?,
+            asset_tag: row.get(19)?,
+            notes: row.get(20)?,
+            purchase_date: row.get(21)?,
+            supplier: row.get(22)?,
+            invoice_number: row.get(23)?,
+            condition: row.get(24)?,
+        })
+    }
+
+    /// Types other than the built-in ones are looked up in `categories`
+    fn into_equipment(self, categories: &[Category]) -> Result<Equipment> {
+        let id = self.id;
+        let corrupt = |reason: String| Error::CorruptRow { id, reason };
+
+        let building = Building::try_from(self.building.as_str()).map_err(|e| corrupt(e.to_string()))?;
+        let floor = i8::try_from(self.floor).map_err(|_| corrupt(format!("Ógild hæð: {}", self.floor)))?;
+        let room = u16::try_from(self.room).map_err(|_| corrupt(format!("Ógilt herbergi: {}", self.room)))?;
+        let value = u32::try_from(self.value).map_err(|_| corrupt(format!("Ógilt verðmæti: {}", self.value)))?;
+        if self.suffix.chars().count() > 1 {
+            return Err(corrupt(format!("Ógildur bókstafur herbergis: {}", self.suffix)));
+        }
+        let location = Location::try_from((building, floor, room, self.suffix.chars().next()))
+            .map_err(|e| corrupt(e.to_string()))?;
+        let purchase_date = match self.purchase_date.as_deref() {
+            Some(text) => Some(
+                NaiveDate::parse_from_str(text, "%Y-%m-%d").map_err(|_| corrupt(format!("Ógildur innkaupsdagur: {}", text)))?,
+            ),
+            None => None,
+        };
+        let info = EquipmentInfo {
+            asset_tag: self.asset_tag.unwrap_or_default(),
+            serial_number: self.serial_number.unwrap_or_default(),
+            manufacturer: self.manufacturer.unwrap_or_default(),
+            model: self.model.unwrap_or_default(),
+            purchase_date,
+            supplier: self.supplier.unwrap_or_default(),
+            invoice_number: self.invoice_number.unwrap_or_default(),
+            condition: Condition::try_from(self.condition.as_str()).map_err(|e| corrupt(e.to_string()))?,
+            notes: self.notes.unwrap_or_default(),
+        };
+
+        let mut equipment = match self.type_name.as_str() {
+            "Table" => {
+                let seats = self
+                    .seats
+                    .and_then(|n| u8::try_from(n).ok())
+                    .ok_or_else(|| corrupt(format!("Ógildur sætafjöldi: {:?}", self.seats)))?;
+                Equipment::Table(Table::try_from((location, value, seats)).map_err(|e| corrupt(e.to_string()))?)
+            }
+            "Chair" => {
+                let name = self.chair_type.as_deref().unwrap_or_default();
+                let chair_type = ChairType::try_from(name).map_err(|e| corrupt(e.to_string()))?;
+                Equipment::Chair(Chair::try_from((location, value, chair_type)).map_err(|e| corrupt(e.to_string()))?)
+            }
+            "Projector" => {
+                let lumens = self
+                    .lumens
+                    .and_then(|n| u32::try_from(n).ok())
+                    .ok_or_else(|| corrupt(format!("Ógilt lúmens gildi: {:?}", self.lumens)))?;
+                Equipment::Projector(Projector::try_from((location, value, lumens)).map_err(|e| corrupt(e.to_string()))?)
+            }
+            "Computer" => {
+                let kind = ComputerKind::try_from(self.computer_kind.as_deref().unwrap_or_default()).map_err(|e| corrupt(e.to_string()))?;
+                let ram_gb = match self.ram_gb {
+                    Some(n) => Some(u16::try_from(n).map_err(|_| corrupt(format!("Ógilt vinnsluminni: {}", n)))?),
+                    None => None,
+                };
+                let mut computer = Computer::new(location, value, kind);
+                computer.cpu = self.cpu.unwrap_or_default();
+                computer.ram_gb = ram_gb;
+                Equipment::Computer(computer)
+            }
+            "Monitor" => {
+                let screen_size = self.screen_size.ok_or_else(|| corrupt("Skjástærð vantar".to_string()))? as f32;
+                let resolution =
+                    Resolution::try_from(self.resolution.as_deref().unwrap_or_default()).map_err(|e| corrupt(e.to_string()))?;
+                Equipment::Monitor(Monitor::new(location, value, screen_size, resolution))
+            }
+            other => {
+                let category = categories
+                    .iter()
+                    .find(|c| c.code == other)
+                    .ok_or_else(|| corrupt(format!("Óþekkt tegund: {}", other)))?;
+                // Values are not checked against the definition here, it may have changed since they were saved
+                let attributes: BTreeMap<String, AttributeValue> = match self.attributes.as_deref() {
+                    Some(json) => serde_json::from_str(json).map_err(|e| corrupt(format!("Ógildir eiginleikar: {}", e)))?,
+                    None => BTreeMap::new(),
+                };
+                Equipment::Custom(CustomEquipment::new(location, value, category.clone(), attributes))
+            }
+        };
+        equipment.set_id(id);
+        equipment.set_info(info);
+        equipment.check().map_err(|e| corrupt(e.to_string()))?;
+        Ok(equipment)
+    }
+}
+
+#[cfg(test)]
+impl Database {
+    /// In-memory database with these rooms (e.g. `"H-212"`) registered as classrooms
+    pub(crate) fn with_rooms(rooms: &[&str]) -> Database {
+        let db = Database::new(":memory:").unwrap();
+        for code in rooms {
+            let location = Location::try_from(*code).unwrap();
+            db.add_room(&Room::new(location, RoomType::Classroom)).unwrap();
+        }
+        db
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corrupt_rows_are_skipped_and_reported() {
+        let db = Database::with_rooms(&["H-22"]);
+        let loc = Location::new(Building::new("H").unwrap(), 2, 2);
+        db.insert_equipment(&Equipment::Table(Table::new(loc, 45000, 4))).unwrap();
+        db.conn
+            .execute(
+                "INSERT INTO equipment (type, building, floor, room, value, seats)
+                 VALUES ('Table', 'x1', 1, 1, 100, 4)",
+                [],
+            )
+            .unwrap();
+
+        assert_eq!(db.get_all_equipment().unwrap().len(), 1);
+        let corrupt = db.find_corrupt_rows().unwrap();
+        assert!(matches!(corrupt.as_slice(), [Error::CorruptRow { id: 2, .. }]));
+        assert!(matches!(db.get_equipment_by_id(2), Err(Error::CorruptRow { .. })));
+    }
+
+    #[test]
+    fn import_replaces_corrupt_row_and_logs_it() {
+        let db = Database::with_rooms(&["H-22"]);
+        let loc = Location::new(Building::new("H").unwrap(), 2, 2);
+        db.conn
+            .execute(
+                "INSERT INTO equipment (id, type, building, floor, room, value, seats)
+                 VALUES (5, 'Table', 'x1', 1, 1, 100, 4)",
+                [],
+            )
+            .unwrap();
+
+        db.insert_equipment_with_id(5, &Equipment::Table(Table::new(loc, 45000, 4))).unwrap();
+        assert!(db.get_equipment_by_id(5).unwrap().is_some());
+        let history = db.get_history(5).unwrap();
+        let last = history.last().unwrap();
+        assert_eq!(last.action, HistoryAction::Edited);
+        assert!(last.details.starts_with("Skemmd færsla með ID 5"));
+    }
+
+    #[test]
+    fn migrates_legacy_extra_data_to_typed_columns() {
+        let path = std::env::temp_dir().join(format!("bunadur_migrate_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE equipment (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT, type TEXT NOT NULL, building TEXT NOT NULL,
+                    floor INTEGER NOT NULL, room INTEGER NOT NULL, value INTEGER NOT NULL,
+                    extra_data TEXT NOT NULL);
+                 INSERT INTO equipment VALUES (1, 'Table', 'H', 2, 2, 45000, '6');
+                 INSERT INTO equipment VALUES (2, 'Chair', 'HA', 1, 1, 9000, 'Skólastóll');
+                 INSERT INTO equipment VALUES (5, 'Projector', 'S', 3, 10, 90000, '3500');",
+            )
+            .unwrap();
+        }
+
+        let db = Database::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(db.schema_version().unwrap(), migrations::LATEST_VERSION);
+        // Rooms in use are registered by the migration
+        assert_eq!(db.get_rooms().unwrap().len(), 3);
+        assert_eq!(db.get_all_equipment().unwrap().len(), 3);
+        let bright: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM equipment WHERE lumens > 3000", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(bright, 1);
+        // Deleted IDs must not be reused after the migration
+        let id = db.insert_equipment(&Equipment::Table(Table::new(Location::new(Building::new("H").unwrap(), 2, 2), 1, 2))).unwrap();
+        assert_eq!(id, 6);
+        drop(db);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn writes_are_checked_like_constructors() {
+        let db = Database::with_rooms(&["H-2.12"]);
+        let loc = Location::try_from("H-2.12").unwrap();
+        let id = db.insert_equipment(&Equipment::Table(Table::new(loc.clone(), 1000, 4))).unwrap();
+
+        // Values serde accepts but `TryFrom` does not
+        let mut far = loc.clone();
+        far.room = 5000;
+        let mut high = loc.clone();
+        high.floor = 120;
+        let mut odd = loc.clone();
+        odd.suffix = Some('7');
+        let bad = [
+            (Equipment::Table(Table::new(loc.clone(), 1000, 0)), Error::ZeroSeats),
+            (Equipment::Projector(Projector::new(loc, 1000, 0)), Error::ZeroLumens),
+            (Equipment::Table(Table::new(far, 1000, 4)), Error::RoomOutOfRange(5000)),
+            (Equipment::Table(Table::new(high, 1000, 4)), Error::FloorOutOfRange(120)),
+            (Equipment::Table(Table::new(odd, 1000, 4)), Error::InvalidRoomSuffix('7')),
+        ];
+        for (equipment, expected) in bad {
+            let expected = expected.to_string();
+            assert_eq!(db.insert_equipment(&equipment).unwrap_err().to_string(), expected);
+            assert_eq!(db.insert_equipment_with_id(50, &equipment).unwrap_err().to_string(), expected);
+            assert_eq!(db.update_equipment(id, &equipment).unwrap_err().to_string(), expected);
+        }
+        assert_eq!(db.get_all_equipment().unwrap().len(), 1);
+        assert!(db.find_corrupt_rows().unwrap().is_empty());
+    }
+
+    #[test]
+    fn update_equipment_can_change_type() {
+        let db = Database::with_rooms(&["HA-11"]);
+        let loc = Location::new(Building::new("HA").unwrap(), 1, 1);
+        let id = db.insert_equipment(&Equipment::Table(Table::new(loc.clone(), 1000, 4))).unwrap();
+
+        db.update_equipment(id, &Equipment::Projector(Projector::new(loc, 2000, 3200))).unwrap();
+        match db.get_equipment_by_id(id).unwrap() {
+            Some(Equipment::Projector(p)) => {
+                assert_eq!((p.id, p.value, p.lumens), (Some(id), 2000, 3200));
+            }
+            other => panic!("expected projector, got {:?}", other),
+        }
+        assert!(matches!(
+            db.update_equipment(999, &Equipment::Table(Table::new(Location::new(Building::new("HA").unwrap(), 1, 1), 1, 1))),
+            Err(Error::NotFound(999))
+        ));
+    }
+
+    #[test]
+    fn computers_and_monitors_keep_their_specs() {
+        let db = Database::with_rooms(&["H-2.12"]);
+        let loc = Location::try_from("H-2.12").unwrap();
+        let mut computer = Computer::new(loc.clone(), 250000, ComputerKind::Laptop);
+        computer.info.serial_number = "5CG1234XYZ".into();
+        computer.info.manufacturer = "HP".into();
+        computer.info.model = "EliteBook 840".into();
+        computer.cpu = "Intel Core i5-1335U".into();
+        computer.ram_gb = Some(16);
+        let mut monitor = Monitor::new(loc.clone(), 60000, 23.8, Resolution::new(2560, 1440));
+        monitor.info.serial_number = "CN0X1".into();
+        monitor.info.manufacturer = "Dell".into();
+        let computer_id = db.insert_equipment(&Equipment::Computer(computer.clone())).unwrap();
+        let monitor_id = db.insert_equipment(&Equipment::Monitor(monitor.clone())).unwrap();
+
+        assert_eq!(db.get_equipment_by_id(computer_id).unwrap(), Some(Equipment::Computer(computer.with_id(computer_id))));
+        db.retire_equipment(monitor_id, RetireReason::Broken).unwrap();
+        let retired = db.get_retired_equipment().unwrap();
+        assert_eq!(retired[0].equipment, Equipment::Monitor(monitor.with_id(monitor_id)));
+        assert_eq!(retired[0].reason, RetireReason::Broken);
+
+        let no_serial = Equipment::Computer(Computer::new(loc, 1000, ComputerKind::Desktop));
+        assert!(matches!(db.insert_equipment(&no_serial), Err(Error::MissingSerialNumber)));
+    }
+
+    #[test]
+    fn every_type_keeps_its_info() {
+        let db = Database::with_rooms(&["H-2.12"]);
+        let mut chair = Chair::new(Location::try_from("H-2.12").unwrap(), 9000, ChairType::Skolastoll);
+        chair.info = EquipmentInfo {
+            asset_tag: "TS-004211".into(),
+            manufacturer: "Kinnarps".into(),
+            purchase_date: NaiveDate::from_ymd_opt(2024, 8, 15),
+            invoice_number: "R-10233".into(),
+            ..Default::default()
+        };
+        let id = db.insert_equipment(&Equipment::Chair(chair.clone())).unwrap();
+        let mut chair = chair.with_id(id);
+        assert_eq!(db.get_equipment_by_id(id).unwrap(), Some(Equipment::Chair(chair.clone())));
+
+        chair.info.notes = "Vinstri fótur laus".into();
+        db.update_equipment(id, &Equipment::Chair(chair.clone())).unwrap();
+        assert_eq!(db.get_equipment_by_id(id).unwrap(), Some(Equipment::Chair(chair)));
+        assert_eq!(db.get_history(id).unwrap()[1].details, "Athugasemdir: – → Vinstri fótur laus");
+        assert!(db.get_all_equipment().unwrap()[0].matches_query("ts-0042"));
+    }
+
+    #[test]
+    fn equipment_by_condition() {
+        let db = Database::with_rooms(&["H-2.12"]);
+        let loc = Location::try_from("H-2.12").unwrap();
+        let id = db.insert_equipment(&Equipment::Projector(Projector::new(loc.clone(), 90000, 3000))).unwrap();
+        db.insert_equipment(&Equipment::Chair(Chair::new(loc, 9000, ChairType::Skolastoll))).unwrap();
+        assert_eq!(db.get_equipment_by_condition(Condition::Good).unwrap().len(), 2);
+
+        let mut projector = db.get_equipment_by_id(id).unwrap().unwrap();
+        let mut info = projector.info().clone();
+        info.condition = Condition::OutOfService;
+        projector.set_info(info);
+        db.update_equipment(id, &projector).unwrap();
+        let out = db.get_equipment_by_condition(Condition::OutOfService).unwrap();
+        assert_eq!(out, vec![projector]);
+        assert!(out[0].is_out_of_service());
+        assert_eq!(db.get_history(id).unwrap()[1].details, "Ástand: Gott → Úr notkun");
+        assert!(db.get_equipment_by_condition(Condition::NeedsRepair).unwrap().is_empty());
+    }
+
+    #[test]
+    fn depreciation_periods_by_type() {
+        let db = Database::with_rooms(&[]);
+        db.set_depreciation_years("Computer", Some(3)).unwrap();
+        db.set_depreciation_years("Chair", Some(10)).unwrap();
+        db.set_depreciation_years("Computer", Some(4)).unwrap();
+        db.set_depreciation_years("Chair", None).unwrap();
+        assert_eq!(db.get_depreciation().unwrap().years, BTreeMap::from([("Computer".to_string(), 4)]));
+        assert!(matches!(db.set_depreciation_years("Sofi", Some(5)), Err(Error::UnknownCategory(_))));
+        assert!(matches!(db.set_depreciation_years("Table", Some(0)), Err(Error::InvalidDepreciationYears(_))));
+
+        // Removing a category removes its period
+        db.add_category(&Category::new("Tafla", "Tafla")).unwrap();
+        db.set_depreciation_years("Tafla", Some(8)).unwrap();
+        db.delete_category("Tafla").unwrap();
+        assert_eq!(db.get_depreciation().unwrap().years.len(), 1);
+    }
+
+    #[test]
+    fn history_records_moves_and_survives_delete() {
+        let mut db = Database::with_rooms(&["S-310", "H-22"]);
+        db.set_user("kennari");
+        let id = db
+            .insert_equipment(&Equipment::Projector(Projector::new(Location::new(Building::new("S").unwrap(), 3, 10), 90000, 3500)))
+            .unwrap();
+        db.update_location(id, &Location::new(Building::new("H").unwrap(), 2, 2)).unwrap();
+        db.delete_equipment(id).unwrap();
+
+        let history = db.get_history(id).unwrap();
+        let actions: Vec<HistoryAction> = history.iter().map(|h| h.action).collect();
+        assert_eq!(actions, [HistoryAction::Created, HistoryAction::Moved, HistoryAction::Deleted]);
+        assert_eq!(history[1].details, "S-3.10 → H-2.02");
+        assert!(history.iter().all(|h| h.user == "kennari"));
+    }
+
+    #[test]
+    fn retired_items_leave_lists_and_can_be_restored() {
+        let db = Database::with_rooms(&["HA-15"]);
+        let loc = Location::new(Building::new("HA").unwrap(), 1, 5);
+        let id = db.insert_equipment(&Equipment::Chair(Chair::new(loc, 9000, ChairType::Skolastoll))).unwrap();
+
+        db.retire_equipment(id, RetireReason::Broken).unwrap();
+        assert!(db.get_all_equipment().unwrap().is_empty());
+        assert!(db.get_equipment_by_id(id).unwrap().is_none());
+        let bin = db.get_retired_equipment().unwrap();
+        assert_eq!(bin.len(), 1);
+        assert_eq!(bin[0].reason, RetireReason::Broken);
+
+        db.restore_equipment(id).unwrap();
+        assert_eq!(db.get_all_equipment().unwrap().len(), 1);
+        assert!(matches!(db.restore_equipment(id), Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn buildings_are_data_and_guard_their_equipment() {
+        let db = Database::new(":memory:").unwrap();
+        let names: Vec<String> = db.get_buildings().unwrap().into_iter().map(|b| b.name).collect();
+        assert_eq!(names, ["Hafnarfjörður", "Háteigsvegur", "Skólavörðuholt"]);
+
+        let ek = BuildingInfo { building: Building::new("EK").unwrap(), name: "Eiðistorg".into(), address: "Eiðistorgi 13".into() };
+        let room = Location::new(ek.building.clone(), 1, 2);
+        let table = Equipment::Table(Table::new(room, 45000, 4));
+        assert!(matches!(db.insert_equipment(&table), Err(Error::UnknownBuilding(_))));
+
+        db.add_building(&ek).unwrap();
+        db.add_room(&Room::new(table.get_location().clone(), RoomType::Workshop)).unwrap();
+        assert!(matches!(db.add_building(&ek), Err(Error::BuildingExists(_))));
+        assert_eq!(db.find_building("eiðistorg").unwrap(), ek);
+        assert_eq!(db.find_building("Hateigssvegur").unwrap().name, "Háteigsvegur");
+        let id = db.insert_equipment(&table).unwrap();
+        db.retire_equipment(id, RetireReason::Broken).unwrap();
+        assert!(matches!(db.delete_building(&ek.building), Err(Error::BuildingInUse { count: 1, .. })));
+
+        db.delete_equipment(id).unwrap();
+        db.delete_building(&ek.building).unwrap();
+        assert!(db.get_building(&ek.building).unwrap().is_none());
+        assert!(db.get_room(table.get_location()).unwrap().is_none());
+    }
+
+    #[test]
+    fn equipment_only_goes_in_registered_rooms() {
+        let db = Database::with_rooms(&["H-212"]);
+        let lab = Location::try_from("H-213").unwrap();
+        let table = Equipment::Table(Table::new(lab.clone(), 45000, 4));
+        assert!(matches!(db.insert_equipment(&table), Err(Error::UnknownRoom(_))));
+        assert!(matches!(db.add_room(&Room::new(Location::try_from("EK-11").unwrap(), RoomType::Lab)), Err(Error::UnknownBuilding(_))));
+
+        let mut room = Room::new(lab.clone(), RoomType::Lab);
+        room.name = "Efnafræðistofa".into();
+        room.capacity = 24;
+        db.add_room(&room).unwrap();
+        assert!(matches!(db.add_room(&room), Err(Error::RoomExists(_))));
+        let id = db.insert_equipment(&table).unwrap();
+        assert!(matches!(db.update_location(id, &Location::try_from("H-99").unwrap()), Err(Error::UnknownRoom(_))));
+
+        room.responsible = "Guðrún".into();
+        db.update_room(&room).unwrap();
+        assert_eq!(db.get_room(&lab).unwrap(), Some(room));
+        assert!(matches!(db.delete_room(&lab), Err(Error::RoomInUse { count: 1, .. })));
+        db.update_location(id, &Location::try_from("H-212").unwrap()).unwrap();
+        db.delete_room(&lab).unwrap();
+        assert_eq!(db.get_rooms().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn suffixed_and_basement_rooms_are_kept_apart() {
+        let db = Database::with_rooms(&["H-2.02", "H-2.02a", "H-K1.04", "S-11.105"]);
+        let plain = Location::try_from("H-2.02").unwrap();
+        let lettered = Location::try_from("H-2.02a").unwrap();
+        let id = db.insert_equipment(&Equipment::Table(Table::new(lettered.clone(), 45000, 4))).unwrap();
+        assert!(db.get_equipment_by_room(&plain).unwrap().is_empty());
+        assert_eq!(db.get_equipment_by_id(id).unwrap().unwrap().get_location(), &lettered);
+        assert_eq!(db.get_occupied_rooms(&plain.building, Some(2)).unwrap(), std::slice::from_ref(&lettered));
+
+        let basement = Location::try_from("H-K1.04").unwrap();
+        db.update_location(id, &basement).unwrap();
+        assert_eq!(db.get_equipment_by_floor(&basement.building, -1).unwrap().len(), 1);
+        db.delete_room(&lettered).unwrap();
+        let rooms: Vec<String> = db.get_rooms().unwrap().iter().map(|r| r.location.to_string()).collect();
+        assert_eq!(rooms, ["H-K1.04", "H-2.02", "S-11.105"]);
+    }
+
+    #[test]
+    fn category_items_are_checked_against_their_definition() {
+        let db = Database::with_rooms(&["H-2.12"]);
+        let room = Location::try_from("H-2.12").unwrap();
+        let mut width = AttributeDef::new("breidd", "Breidd", AttributeKind::Number);
+        width.required = true;
+        width.min = Some(50);
+        width.unit = "cm".into();
+        let mut board = Category::new("Tafla", "Tafla");
+        board.attributes = vec![width, AttributeDef::new("segull", "Segull", AttributeKind::YesNo)];
+        db.add_category(&board).unwrap();
+        assert!(matches!(db.add_category(&board), Err(Error::CategoryExists(_))));
+
+        let attributes = board.parse_attributes(&[("breidd".to_string(), "240".to_string())].into()).unwrap();
+        let item = Equipment::Custom(CustomEquipment::new(room.clone(), 30000, board.clone(), attributes));
+        let id = db.insert_equipment(&item).unwrap();
+        let stored = db.get_equipment_by_id(id).unwrap().unwrap();
+        assert_eq!(stored.type_code(), "Tafla");
+        assert_eq!(stored.details(), [("Breidd".to_string(), "240 cm".to_string())]);
+        assert_eq!(db.get_equipment_by_type("Tafla").unwrap().len(), 1);
+
+        // Values from JSON are checked too, and unknown categories refused
+        let mut too_narrow = item.clone();
+        if let Equipment::Custom(x) = &mut too_narrow {
+            x.attributes.insert("breidd".into(), AttributeValue::Number(20));
+        }
+        assert!(matches!(db.update_equipment(id, &too_narrow), Err(Error::AttributeOutOfRange { .. })));
+        let mut unknown = item.clone();
+        if let Equipment::Custom(x) = &mut unknown {
+            x.category = Category::new("Sofi", "Sófi");
+        }
+        assert!(matches!(db.insert_equipment(&unknown), Err(Error::UnknownCategory(_))));
+
+        // Renaming the category shows in items already registered; it cannot go while in use
+        board.name = "Tússtafla".into();
+        db.update_category(&board).unwrap();
+        assert_eq!(db.get_equipment_by_id(id).unwrap().unwrap().get_type_name(), "Tússtafla");
+        assert!(matches!(db.delete_category("Tafla"), Err(Error::CategoryInUse { count: 1, .. })));
+        db.delete_equipment(id).unwrap();
+        db.delete_category("Tafla").unwrap();
+        assert!(db.get_categories().unwrap().is_empty());
+        assert_eq!(db.find_corrupt_rows().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn failed_transaction_rolls_back_every_change() {
+        let db = Database::with_rooms(&["HA-15"]);
+        let loc = Location::new(Building::new("HA").unwrap(), 1, 5);
+        let result: Result<()> = db.transaction(|db| {
+            db.insert_equipment(&Equipment::Projector(Projector::new(loc.clone(), 90000, 3000)))?;
+            db.update_location(999, &loc)
+        });
+
+        assert!(matches!(result, Err(Error::NotFound(999))));
+        assert!(db.get_all_equipment().unwrap().is_empty());
+        assert!(db.get_recent_history(10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+//! Straight-line depreciation, for the book value of equipment on any date.
+//!
+//! Finance sets a period in whole years for each type of equipment. An item loses
+//! the same amount every day from its purchase date until it is worth nothing at the
+//! end of the period.
+
+use crate::equipment::Equipment;
+use crate::error::{Error, Result};
+use chrono::{Months, NaiveDate};
+use std::collections::BTreeMap;
+
+/// Longest depreciation period in years
+pub const MAX_YEARS: u8 = 50;
+
+/// Depreciation period of each type, keyed by `Equipment::type_code`; stored in the
+/// `depreciation` table. Types without a period, and items without a purchase date,
+/// stay at their purchase price.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Depreciation {
+    pub years: BTreeMap<String, u8>,
+}
+
+impl Depreciation {
+    pub fn years_for(&self, type_code: &str) -> Option<u8> {
+        self.years.get(type_code).copied()
+    }
+
+    /// Value in ISK on the books at the end of `as_of`; 0 if the item had not been bought yet
+    pub fn book_value(&self, equipment: &Equipment, as_of: NaiveDate) -> u32 {
+        let value = equipment.get_value();
+        let Some(purchased) = equipment.info().purchase_date else {
+            return value;
+        };
+        if as_of < purchased {
+            return 0;
+        }
+        let Some(years) = self.years_for(equipment.type_code()) else {
+            return value;
+        };
+        let Some(end) = purchased.checked_add_months(Months::new(12 * u32::from(years))) else {
+            return value;
+        };
+        if as_of >= end {
+            return 0;
+        }
+        let total = (end - purchased).num_days() as u64;
+        let remaining = (end - as_of).num_days() as u64;
+        // Rounded to the nearest króna
+        ((u64::from(value) * remaining + total / 2) / total) as u32
+    }
+}
+
+/// Read a period as typed, e.g. "5"
+pub fn parse_years(text: &str) -> Result<u8> {
+    text.trim()
+        .parse::<u8>()
+        .ok()
+        .filter(|years| (1..=MAX_YEARS).contains(years))
+        .ok_or_else(|| Error::InvalidDepreciationYears(text.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::building::Building;
+    use crate::location::Location;
+    use crate::projector::Projector;
+
+    #[test]
+    fn straight_line_from_purchase_date() {
+        let date = |text: &str| NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap();
+        let mut projector = Projector::new(Location::new(Building::new("S").unwrap(), 3, 10), 400_000, 3500);
+        projector.info.purchase_date = Some(date("2024-01-01"));
+        let projector = Equipment::Projector(projector);
+
+        let mut depreciation = Depreciation::default();
+        assert_eq!(depreciation.book_value(&projector, date("2030-01-01")), 400_000);
+        depreciation.years.insert("Projector".into(), 4);
+        assert_eq!(depreciation.book_value(&projector, date("2023-12-31")), 0);
+        assert_eq!(depreciation.book_value(&projector, date("2024-01-01")), 400_000);
+        // 2024 is a leap year: 366 of the 1461 days have passed
+        assert_eq!(depreciation.book_value(&projector, date("2025-01-01")), 299_795);
+        assert_eq!(depreciation.book_value(&projector, date("2028-01-01")), 0);
+
+        assert_eq!(parse_years(" 5 ").unwrap(), 5);
+        assert!(matches!(parse_years("0"), Err(Error::InvalidDepreciationYears(_))));
+        assert!(matches!(parse_years("fimm"), Err(Error::InvalidDepreciationYears(_))));
+    }
+}
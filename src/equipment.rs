@@ -1,14 +1,25 @@
 use crate::chair::Chair;
+use crate::computer::Computer;
+use crate::condition::Condition;
+use crate::custom_equipment::CustomEquipment;
+use crate::equipment_info::EquipmentInfo;
+use crate::error::{Error, Result};
+use crate::location::Location;
+use crate::monitor::Monitor;
 use crate::projector::Projector;
 use crate::table::Table;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Equipment {
     Table(Table),
     Chair(Chair),
     Projector(Projector),
+    Computer(Computer),
+    Monitor(Monitor),
+    /// Item in a user-defined category
+    Custom(CustomEquipment),
 }
 
 impl Equipment {
@@ -17,28 +28,165 @@ impl Equipment {
             Equipment::Table(t) => t.id,
             Equipment::Chair(c) => c.id,
             Equipment::Projector(p) => p.id,
+            Equipment::Computer(c) => c.id,
+            Equipment::Monitor(m) => m.id,
+            Equipment::Custom(x) => x.id,
         }
     }
 
     /// Set the ID (useful for tests and JSON import flows)
-    /// Kept public for tooling; harmless if unused in production builds.
-    #[cfg_attr(not(test), allow(dead_code))]
     pub fn set_id(&mut self, id: i64) {
         match self {
             Equipment::Table(t) => t.id = Some(id),
             Equipment::Chair(c) => c.id = Some(id),
             Equipment::Projector(p) => p.id = Some(id),
+            Equipment::Computer(c) => c.id = Some(id),
+            Equipment::Monitor(m) => m.id = Some(id),
+            Equipment::Custom(x) => x.id = Some(id),
         }
     }
 
-// Unit tests for Equipment
-// tests module moved to the bottom of the file (outside impl)
+    pub fn get_location(&self) -> &Location {
+        match self {
+            Equipment::Table(t) => &t.location,
+            Equipment::Chair(c) => &c.location,
+            Equipment::Projector(p) => &p.location,
+            Equipment::Computer(c) => &c.location,
+            Equipment::Monitor(m) => &m.location,
+            Equipment::Custom(x) => &x.location,
+        }
+    }
+
+    pub fn set_location(&mut self, location: Location) {
+        match self {
+            Equipment::Table(t) => t.location = location,
+            Equipment::Chair(c) => c.location = location,
+            Equipment::Projector(p) => p.location = location,
+            Equipment::Computer(c) => c.location = location,
+            Equipment::Monitor(m) => m.location = location,
+            Equipment::Custom(x) => x.location = location,
+        }
+    }
+
+    pub fn get_value(&self) -> u32 {
+        match self {
+            Equipment::Table(t) => t.value,
+            Equipment::Chair(c) => c.value,
+            Equipment::Projector(p) => p.value,
+            Equipment::Computer(c) => c.value,
+            Equipment::Monitor(m) => m.value,
+            Equipment::Custom(x) => x.value,
+        }
+    }
+
+    /// The fields every type shares, see `EquipmentInfo`
+    pub fn info(&self) -> &EquipmentInfo {
+        match self {
+            Equipment::Table(t) => &t.info,
+            Equipment::Chair(c) => &c.info,
+            Equipment::Projector(p) => &p.info,
+            Equipment::Computer(c) => &c.info,
+            Equipment::Monitor(m) => &m.info,
+            Equipment::Custom(x) => &x.info,
+        }
+    }
+
+    pub fn set_info(&mut self, info: EquipmentInfo) {
+        match self {
+            Equipment::Table(t) => t.info = info,
+            Equipment::Chair(c) => c.info = info,
+            Equipment::Projector(p) => p.info = info,
+            Equipment::Computer(c) => c.info = info,
+            Equipment::Monitor(m) => m.info = info,
+            Equipment::Custom(x) => x.info = info,
+        }
+    }
+
+    /// Manufacturer's serial number, if one is recorded
+    pub fn serial_number(&self) -> Option<&str> {
+        Some(self.info().serial_number.as_str()).filter(|s| !s.is_empty())
+    }
+
+    /// Items out of service are highlighted in lists
+    pub fn is_out_of_service(&self) -> bool {
+        self.info().condition == Condition::OutOfService
+    }
+
+    /// Case-insensitive match against ID, type, location, value, description and every
+    /// `EquipmentInfo` field, e.g. asset tag, serial number, condition and notes.
+    /// Shared by the GUI search and the command-line `search` command.
+    pub fn matches_query(&self, query: &str) -> bool {
+        let q = query.to_lowercase();
+        let id_match = self.get_id().unwrap_or(0).to_string().contains(&q);
+        let type_match = self.get_type_name().to_lowercase().contains(&q);
+        // A whole code in the older form (`h-202`) finds the same room as `h-2.02`
+        let location_match = self.get_location().to_string().to_lowercase().contains(&q)
+            || Location::try_from(query.to_uppercase()).is_ok_and(|loc| &loc == self.get_location());
+        let value_match = self.get_value().to_string().contains(&q);
+        let desc_match = self.to_string().to_lowercase().contains(&q);
+        let info_match = self.info().matches(&q);
+        id_match || type_match || location_match || value_match || desc_match || info_match
+    }
 
     pub fn get_type_name(&self) -> &str {
         match self {
             Equipment::Table(_) => "Borð",
             Equipment::Chair(_) => "Stóll",
             Equipment::Projector(_) => "Skjávarpi",
+            Equipment::Computer(_) => "Tölva",
+            Equipment::Monitor(_) => "Skjár",
+            Equipment::Custom(x) => &x.category.name,
+        }
+    }
+
+    /// Code stored in the `equipment.type` column: a built-in type or a category code
+    pub fn type_code(&self) -> &str {
+        match self {
+            Equipment::Table(_) => "Table",
+            Equipment::Chair(_) => "Chair",
+            Equipment::Projector(_) => "Projector",
+            Equipment::Computer(_) => "Computer",
+            Equipment::Monitor(_) => "Monitor",
+            Equipment::Custom(x) => &x.category.code,
+        }
+    }
+
+    /// Labelled type-specific values, e.g. `("Sæti", "4")`, for history, reports and exports.
+    /// The shared fields are in `info`.
+    pub fn details(&self) -> Vec<(String, String)> {
+        match self {
+            Equipment::Table(t) => vec![("Sæti".to_string(), t.seats.to_string())],
+            Equipment::Chair(c) => vec![("Stólategund".to_string(), c.chair_type.to_string())],
+            Equipment::Projector(p) => vec![("Lúmens".to_string(), p.lumens.to_string())],
+            Equipment::Computer(c) => {
+                let mut details = vec![("Tegund tölvu".to_string(), c.kind.to_string())];
+                if !c.cpu.is_empty() {
+                    details.push(("Örgjörvi".to_string(), c.cpu.clone()));
+                }
+                if let Some(ram) = c.ram_gb {
+                    details.push(("Vinnsluminni".to_string(), format!("{} GB", ram)));
+                }
+                details
+            }
+            Equipment::Monitor(m) => vec![
+                ("Skjástærð".to_string(), format!("{}\"", m.screen_size)),
+                ("Upplausn".to_string(), m.resolution.to_string()),
+            ],
+            Equipment::Custom(x) => x.details(),
+        }
+    }
+
+    /// The checks the `TryFrom` constructors make, for items that were not built by them
+    /// (e.g. read from JSON), and those they cannot make because the fields are set after
+    /// them, e.g. a computer's serial number. `Database` runs them before every write.
+    pub fn check(&self) -> Result<()> {
+        self.get_location().check()?;
+        match self {
+            Equipment::Table(t) => t.check(),
+            Equipment::Projector(p) => p.check(),
+            Equipment::Computer(c) => c.check(),
+            Equipment::Monitor(m) => m.check(),
+            Equipment::Chair(_) | Equipment::Custom(_) => Ok(()),
         }
     }
 }
@@ -49,20 +197,79 @@ impl fmt::Display for Equipment {
             Equipment::Table(t) => write!(f, "{}", t),
             Equipment::Chair(c) => write!(f, "{}", c),
             Equipment::Projector(p) => write!(f, "{}", p),
+            Equipment::Computer(c) => write!(f, "{}", c),
+            Equipment::Monitor(m) => write!(f, "{}", m),
+            Equipment::Custom(x) => write!(f, "{}", x),
         }
     }
 }
 
+/// IDs from text such as `1-5, 12 17`, in the order given, without duplicates
+pub fn parse_id_list(text: &str) -> Result<Vec<i64>> {
+    let invalid = || Error::InvalidIdList(text.trim().to_string());
+    let mut ids = Vec::new();
+    for part in text.split(|c: char| c == ',' || c.is_whitespace()).filter(|p| !p.is_empty()) {
+        let (first, last) = match part.split_once('-') {
+            Some((a, b)) => (a.parse::<i64>().map_err(|_| invalid())?, b.parse::<i64>().map_err(|_| invalid())?),
+            None => {
+                let id = part.parse::<i64>().map_err(|_| invalid())?;
+                (id, id)
+            }
+        };
+        // A typo like 1-10000000 should not hang the program
+        if first > last || last - first > 10_000 {
+            return Err(invalid());
+        }
+        for id in first..=last {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    Ok(ids)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{table::Table, location::{Location, Building}};
+    use crate::{building::Building, computer_kind::ComputerKind, table::Table};
 
     #[test]
     fn can_set_id() {
-        let loc = Location { building: Building::Hafnarfjordur, floor: 1, room: 1 };
-        let mut eq = Equipment::Table(Table { id: None, location: loc, value: 1000, seats: 4 });
+        let loc = Location::new(Building::new("HA").unwrap(), 1, 1);
+        let mut eq = Equipment::Table(Table::new(loc, 1000, 4));
         eq.set_id(42);
         assert_eq!(eq.get_id(), Some(42));
     }
+
+    #[test]
+    fn matches_query_checks_location_and_type() {
+        let loc = Location::new(Building::new("H").unwrap(), 2, 2);
+        let mut table = Table::new(loc, 45000, 4).with_id(7);
+        table.info.asset_tag = "TS-004211".into();
+        table.info.notes = "Vinstri fótur laus".into();
+        let eq = Equipment::Table(table);
+        assert!(eq.matches_query("h-2.02"));
+        assert!(eq.matches_query("h-202"));
+        assert!(eq.matches_query("BORÐ"));
+        assert!(!eq.matches_query("skjávarpi"));
+        assert!(eq.matches_query("ts-0042"));
+        assert!(eq.matches_query("FÓTUR"));
+
+        let mut laptop = Computer::new(Location::new(Building::new("H").unwrap(), 2, 12), 250000, ComputerKind::Laptop);
+        laptop.info.serial_number = "5CG1234XYZ".into();
+        laptop.info.model = "EliteBook 840".into();
+        let laptop = Equipment::Computer(laptop);
+        assert!(laptop.matches_query("5cg1234"));
+        assert!(laptop.matches_query("elitebook"));
+        assert_eq!(laptop.serial_number(), Some("5CG1234XYZ"));
+    }
+
+    #[test]
+    fn parses_id_lists_with_ranges() {
+        assert_eq!(parse_id_list("1-3, 7 2,,9").unwrap(), [1, 2, 3, 7, 9]);
+        assert!(parse_id_list("").unwrap().is_empty());
+        assert!(parse_id_list("5-2").is_err());
+        assert!(parse_id_list("H-212").is_err());
+    }
 }
@@ -0,0 +1,94 @@
+use crate::condition::Condition;
+use crate::error::{Error, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Optional facts every item can have, whatever its type. Empty text means not recorded.
+/// In JSON the fields sit next to the item's own fields, so files without them still load.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EquipmentInfo {
+    /// The school's own inventory label, e.g. "TS-004211"
+    pub asset_tag: String,
+    /// From the manufacturer's label; required for computers and monitors
+    pub serial_number: String,
+    pub manufacturer: String, // e.g. "Dell"
+    pub model: String,        // e.g. "Latitude 5440"
+    /// Day the item was bought; depreciation runs from it (see `Depreciation`)
+    pub purchase_date: Option<NaiveDate>,
+    pub supplier: String,       // e.g. "Advania"
+    pub invoice_number: String, // Supplier's invoice, for finding the purchase in the books
+    /// `Condition::Good` unless recorded otherwise
+    pub condition: Condition,
+    /// Free text, e.g. "vinstri fótur laus"
+    pub notes: String,
+}
+
+impl EquipmentInfo {
+    /// Every field with its label, empty ones included, in the order they are shown
+    pub fn fields(&self) -> Vec<(String, String)> {
+        vec![
+            ("Eignanúmer".to_string(), self.asset_tag.clone()),
+            ("Raðnúmer".to_string(), self.serial_number.clone()),
+            ("Framleiðandi".to_string(), self.manufacturer.clone()),
+            ("Gerð".to_string(), self.model.clone()),
+            ("Innkaupsdagur".to_string(), self.purchase_date.map(format_date).unwrap_or_default()),
+            ("Birgir".to_string(), self.supplier.clone()),
+            ("Reikningsnúmer".to_string(), self.invoice_number.clone()),
+            ("Ástand".to_string(), self.condition.to_string()),
+            ("Athugasemdir".to_string(), self.notes.clone()),
+        ]
+    }
+
+    /// The fields that are filled in; the condition always is
+    pub fn details(&self) -> Vec<(String, String)> {
+        self.fields().into_iter().filter(|(_, value)| !value.is_empty()).collect()
+    }
+
+    /// Manufacturer and model together, e.g. "Dell Latitude 5440"; empty if neither is known
+    pub fn product_name(&self) -> String {
+        format!("{} {}", self.manufacturer, self.model).trim().to_string()
+    }
+
+    /// Case-insensitive match of `query` (already lowercase) against every field
+    pub fn matches(&self, query: &str) -> bool {
+        self.fields().iter().any(|(_, value)| value.to_lowercase().contains(query))
+    }
+
+    /// Same values with surrounding whitespace removed, as they are stored
+    pub fn trimmed(&self) -> EquipmentInfo {
+        EquipmentInfo {
+            asset_tag: self.asset_tag.trim().to_string(),
+            serial_number: self.serial_number.trim().to_string(),
+            manufacturer: self.manufacturer.trim().to_string(),
+            model: self.model.trim().to_string(),
+            purchase_date: self.purchase_date,
+            supplier: self.supplier.trim().to_string(),
+            invoice_number: self.invoice_number.trim().to_string(),
+            condition: self.condition,
+            notes: self.notes.trim().to_string(),
+        }
+    }
+}
+
+/// Date as shown in forms and reports, e.g. "15.08.2024"
+pub fn format_date(date: NaiveDate) -> String {
+    date.format("%d.%m.%Y").to_string()
+}
+
+/// Read a date as typed, `15.08.2024` or `2024-08-15`
+pub fn parse_date(text: &str) -> Result<NaiveDate> {
+    let text = text.trim();
+    NaiveDate::parse_from_str(text, "%d.%m.%Y")
+        .or_else(|_| NaiveDate::parse_from_str(text, "%Y-%m-%d"))
+        .map_err(|_| Error::InvalidDate(text.to_string()))
+}
+
+/// Optional date as typed in a form or a CSV file; empty text is `None`
+pub fn parse_optional_date(text: &str) -> Result<Option<NaiveDate>> {
+    if text.trim().is_empty() {
+        Ok(None)
+    } else {
+        parse_date(text).map(Some)
+    }
+}
@@ -0,0 +1,232 @@
+use std::fmt;
+
+/// Errors produced by the domain constructors and the database layer.
+#[derive(Debug)]
+pub enum Error {
+    /// Location text did not match `{HÚS}-{HÆÐ}.{HERBERGI}` or the older `{HÚS}-{HÆÐ}{HERBERGI}`
+    InvalidLocationFormat(String),
+    UnknownBuilding(String),
+    /// Building codes are one to five capital letters
+    InvalidBuildingCode(String),
+    /// A building with this code is already registered
+    BuildingExists(String),
+    /// A building cannot be removed while equipment is registered in it
+    BuildingInUse { code: String, count: usize },
+    /// Location that is not in the rooms table
+    UnknownRoom(String),
+    UnknownRoomType(String),
+    /// A room with this location is already registered
+    RoomExists(String),
+    /// A room cannot be removed while equipment is registered in it
+    RoomInUse { room: String, count: usize },
+    UnknownChairType(String),
+    /// Category codes are a letter followed by letters, digits or `_`, and not a built-in type
+    InvalidCategoryCode(String),
+    UnknownCategory(String),
+    /// A category with this code is already defined
+    CategoryExists(String),
+    /// A category cannot be removed while equipment is registered in it
+    CategoryInUse { code: String, count: usize },
+    /// An attribute definition that cannot be used, e.g. a choice list with no choices
+    InvalidAttributeDef { key: String, reason: String },
+    UnknownAttributeKind(String),
+    /// A value given for an attribute the category does not have
+    UnknownAttribute(String),
+    /// A required attribute was left empty
+    MissingAttribute(String),
+    /// Attribute text that does not fit its kind, e.g. letters in a number
+    InvalidAttribute { label: String, value: String },
+    AttributeOutOfRange { label: String, value: i64, min: Option<i64>, max: Option<i64> },
+    UnknownRetireReason(String),
+    UnknownImportMode(String),
+    /// ID list text such as `1-5, 12` could not be read
+    InvalidIdList(String),
+    RoomOutOfRange(u16),
+    /// Floors go from `K9` (ninth basement level) to 99
+    FloorOutOfRange(i16),
+    /// Room suffixes are a single letter, as in `H-2.02a`
+    InvalidRoomSuffix(char),
+    ZeroSeats,
+    ZeroLumens,
+    UnknownComputerKind(String),
+    /// Computers and monitors must have the manufacturer's serial number
+    MissingSerialNumber,
+    ZeroRam,
+    /// Screen sizes are inches, above 0 and at most `monitor::MAX_SCREEN_SIZE`
+    InvalidScreenSize(String),
+    /// Resolution text did not match `{BREIDD}x{HÆÐ}`
+    InvalidResolution(String),
+    /// Dates are written `dd.mm.yyyy` or `yyyy-mm-dd`
+    InvalidDate(String),
+    /// Depreciation periods are whole years from 1 to `depreciation::MAX_YEARS`
+    InvalidDepreciationYears(String),
+    UnknownCondition(String),
+    /// No equipment with this ID
+    NotFound(i64),
+    /// No stocktake with this ID
+    AuditNotFound(i64),
+    /// Scans cannot be added to or removed from a finished stocktake
+    AuditFinished(i64),
+    /// A stored row could not be turned into `Equipment`
+    CorruptRow { id: i64, reason: String },
+    /// A row in an imported CSV file could not be turned into `Equipment`
+    CsvLine { line: usize, reason: String },
+    /// An item in an import file fails the checks every write makes; `item` counts from 1
+    ImportItem { item: usize, reason: String },
+    /// An external program (browser opener, `lpr`) is missing or reported an error
+    CommandFailed { command: String, reason: String },
+    Csv(csv::Error),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Xlsx(rust_xlsxwriter::XlsxError),
+    Pdf(printpdf::Error),
+    Qr(qrcode::types::QrError),
+    Sql(rusqlite::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidLocationFormat(value) => write!(
+                f,
+                "Ógilt staðsetningarsnið: {}. Ætti að vera t.d. H-2.02, HA-1.23, S-11.05 eða H-K1.04 (kjallari)",
+                value
+            ),
+            Error::UnknownBuilding(value) => write!(f, "Óþekkt hús: {}", value),
+            Error::InvalidBuildingCode(value) => {
+                write!(f, "Ógildur kóði húss: {}. Ætti að vera 1-5 hástafir, t.d. H eða HA", value)
+            }
+            Error::BuildingExists(code) => write!(f, "Hús með kóðann {} er þegar til", code),
+            Error::BuildingInUse { code, count } => {
+                write!(f, "Ekki er hægt að eyða húsinu {}: {} búnaður er skráður þar", code, count)
+            }
+            Error::UnknownChairType(value) => write!(f, "Óþekkt stólategund: {}", value),
+            Error::InvalidCategoryCode(value) => write!(
+                f,
+                "Ógildur kóði flokks: {}. Ætti að byrja á bókstaf og innihalda aðeins enska bókstafi, tölustafi og _",
+                value
+            ),
+            Error::UnknownCategory(value) => write!(f, "Óþekktur flokkur: {}", value),
+            Error::CategoryExists(code) => write!(f, "Flokkur með kóðann {} er þegar til", code),
+            Error::CategoryInUse { code, count } => {
+                write!(f, "Ekki er hægt að eyða flokknum {}: {} búnaður er skráður í honum", code, count)
+            }
+            Error::InvalidAttributeDef { key, reason } => write!(f, "Ógildur eiginleiki {}: {}", key, reason),
+            Error::UnknownAttributeKind(value) => write!(f, "Óþekkt tegund eiginleika: {}", value),
+            Error::UnknownAttribute(key) => write!(f, "Flokkurinn hefur engan eiginleika {}", key),
+            Error::MissingAttribute(label) => write!(f, "Það verður að fylla út {}", label),
+            Error::InvalidAttribute { label, value } => write!(f, "Ógilt gildi í {}: {}", label, value),
+            Error::AttributeOutOfRange { label, value, min, max } => match (min, max) {
+                (Some(min), Some(max)) => write!(f, "{} verður að vera á bilinu {} til {}: {}", label, min, max, value),
+                (Some(min), None) => write!(f, "{} má ekki vera lægra en {}: {}", label, min, value),
+                (None, Some(max)) => write!(f, "{} má ekki vera hærra en {}: {}", label, max, value),
+                (None, None) => write!(f, "Ógilt gildi í {}: {}", label, value),
+            },
+            Error::UnknownRetireReason(value) => write!(f, "Óþekkt ástæða afskráningar: {}", value),
+            Error::UnknownImportMode(value) => write!(f, "Óþekktur innflutningshamur: {}", value),
+            Error::InvalidIdList(value) => write!(f, "Ógildur ID listi: {}. Ætti að vera t.d. 1-5, 12", value),
+            Error::UnknownRoom(room) => write!(f, "Stofan {} er ekki skráð. Skráðu hana fyrst undir Hús og stofur", room),
+            Error::UnknownRoomType(value) => write!(f, "Óþekkt tegund stofu: {}", value),
+            Error::RoomExists(room) => write!(f, "Stofan {} er þegar skráð", room),
+            Error::RoomInUse { room, count } => {
+                write!(f, "Ekki er hægt að eyða stofunni {}: {} búnaður er skráður þar", room, count)
+            }
+            Error::RoomOutOfRange(room) => {
+                write!(f, "Herbergisnúmer má ekki vera hærra en 999: {}", room)
+            }
+            Error::FloorOutOfRange(floor) => {
+                write!(f, "Hæð verður að vera á bilinu K9 (kjallari) til 99: {}", floor)
+            }
+            Error::InvalidRoomSuffix(c) => write!(f, "Ógildur bókstafur á eftir herbergisnúmeri: {}", c),
+            Error::ZeroSeats => write!(f, "Fjöldi sæta má ekki vera 0"),
+            Error::ZeroLumens => write!(f, "Lúmens má ekki vera 0"),
+            Error::UnknownComputerKind(value) => write!(f, "Óþekkt tegund tölvu: {}. Ætti að vera Fartölva eða Borðtölva", value),
+            Error::MissingSerialNumber => write!(f, "Raðnúmer vantar"),
+            Error::ZeroRam => write!(f, "Vinnsluminni má ekki vera 0"),
+            Error::InvalidScreenSize(value) => write!(f, "Ógild skjástærð: {}. Ætti að vera í tommum, t.d. 24 eða 27", value),
+            Error::InvalidResolution(value) => write!(f, "Ógild upplausn: {}. Ætti að vera t.d. 1920x1080", value),
+            Error::InvalidDate(value) => write!(f, "Ógild dagsetning: {}. Ætti að vera t.d. 15.08.2024", value),
+            Error::UnknownCondition(value) => {
+                write!(f, "Óþekkt ástand: {}. Ætti að vera Nýtt, Gott, Slitið, Þarf viðgerð eða Úr notkun", value)
+            }
+            Error::InvalidDepreciationYears(value) => {
+                write!(f, "Ógildur afskriftartími: {}. Ætti að vera heil ár frá 1 til {}", value, crate::depreciation::MAX_YEARS)
+            }
+            Error::NotFound(id) => write!(f, "Búnaður með ID {} fannst ekki", id),
+            Error::AuditNotFound(id) => write!(f, "Úttekt númer {} fannst ekki", id),
+            Error::AuditFinished(id) => write!(f, "Úttekt númer {} er lokið og henni verður ekki breytt", id),
+            Error::CorruptRow { id, reason } => {
+                write!(f, "Skemmd færsla með ID {}: {}", id, reason)
+            }
+            Error::CsvLine { line, reason } => write!(f, "Lína {}: {}", line, reason),
+            Error::ImportItem { item, reason } => write!(f, "Atriði {} í skránni: {}", item, reason),
+            Error::CommandFailed { command, reason } => write!(f, "Skipunin {} mistókst: {}", command, reason),
+            Error::Csv(e) => write!(f, "Villa í CSV skrá: {}", e),
+            Error::Io(e) => write!(f, "Villa við lestur eða skrift: {}", e),
+            Error::Json(e) => write!(f, "Villa í JSON: {}", e),
+            Error::Xlsx(e) => write!(f, "Villa við gerð Excel skjals: {}", e),
+            Error::Pdf(e) => write!(f, "Villa við gerð PDF skjals: {}", e),
+            Error::Qr(e) => write!(f, "Villa við gerð QR kóða: {}", e),
+            Error::Sql(e) => write!(f, "Gagnagrunnsvilla: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Csv(e) => Some(e),
+            Error::Io(e) => Some(e),
+            Error::Json(e) => Some(e),
+            Error::Xlsx(e) => Some(e),
+            Error::Pdf(e) => Some(e),
+            Error::Qr(e) => Some(e),
+            Error::Sql(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Error::Sql(e)
+    }
+}
+
+impl From<csv::Error> for Error {
+    fn from(e: csv::Error) -> Self {
+        Error::Csv(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl From<rust_xlsxwriter::XlsxError> for Error {
+    fn from(e: rust_xlsxwriter::XlsxError) -> Self {
+        Error::Xlsx(e)
+    }
+}
+
+impl From<printpdf::Error> for Error {
+    fn from(e: printpdf::Error) -> Self {
+        Error::Pdf(e)
+    }
+}
+
+impl From<qrcode::types::QrError> for Error {
+    fn from(e: qrcode::types::QrError) -> Self {
+        Error::Qr(e)
+    }
+}
@@ -0,0 +1,114 @@
+use crate::equipment::Equipment;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What happened to an item in one history entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryAction {
+    Created,
+    Moved,
+    Edited,
+    Retired,
+    Restored,
+    Deleted,
+}
+
+impl HistoryAction {
+    pub fn to_code(self) -> &'static str {
+        match self {
+            HistoryAction::Created => "Created",
+            HistoryAction::Moved => "Moved",
+            HistoryAction::Edited => "Edited",
+            HistoryAction::Retired => "Retired",
+            HistoryAction::Restored => "Restored",
+            HistoryAction::Deleted => "Deleted",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "Created" => Some(HistoryAction::Created),
+            "Moved" => Some(HistoryAction::Moved),
+            "Edited" => Some(HistoryAction::Edited),
+            "Retired" => Some(HistoryAction::Retired),
+            "Restored" => Some(HistoryAction::Restored),
+            "Deleted" => Some(HistoryAction::Deleted),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HistoryAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                HistoryAction::Created => "Skráð",
+                HistoryAction::Moved => "Flutt",
+                HistoryAction::Edited => "Breytt",
+                HistoryAction::Retired => "Afskráð",
+                HistoryAction::Restored => "Endurheimt",
+                HistoryAction::Deleted => "Eytt",
+            }
+        )
+    }
+}
+
+/// One row of the `equipment_history` table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: i64,
+    pub equipment_id: i64,
+    pub action: HistoryAction,
+    /// Local time, `YYYY-MM-DD HH:MM:SS`
+    pub timestamp: String,
+    pub user: String,
+    pub details: String,
+}
+
+impl fmt::Display for HistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}  {:<10} ID {} ({}): {}",
+            self.timestamp,
+            self.action.to_string(),
+            self.equipment_id,
+            self.user,
+            self.details
+        )
+    }
+}
+
+/// Labelled field values used to describe an edit as "field: old → new"
+fn fields(equipment: &Equipment) -> Vec<(String, String)> {
+    let mut fields = vec![
+        ("Tegund".to_string(), equipment.get_type_name().to_string()),
+        ("Staðsetning".to_string(), equipment.get_location().to_string()),
+        ("Verðmæti".to_string(), format!("{} kr.", equipment.get_value())),
+    ];
+    fields.extend(equipment.details());
+    fields.extend(equipment.info().fields());
+    fields
+}
+
+/// Human-readable list of the fields that differ between two versions of an item
+pub fn describe_changes(old: &Equipment, new: &Equipment) -> String {
+    let old_fields = fields(old);
+    let new_fields = fields(new);
+    let mut changes = Vec::new();
+    for (label, new_value) in &new_fields {
+        match old_fields.iter().find(|(l, _)| l == label) {
+            Some((_, old_value)) if old_value == new_value => {}
+            Some((_, old_value)) => changes.push(format!("{}: {} → {}", label, or_dash(old_value), or_dash(new_value))),
+            None => changes.push(format!("{}: {}", label, new_value)),
+        }
+    }
+    changes.join("; ")
+}
+
+/// Empty fields, e.g. notes that were cleared, shown as a dash
+fn or_dash(value: &str) -> &str {
+    if value.is_empty() { "–" } else { value }
+}
@@ -0,0 +1,335 @@
+//! Importing a list of equipment (e.g. a JSON file from a department head).
+//!
+//! `ImportPlan::new` compares the list with the database without changing anything,
+//! so the differences can be shown first. `ImportPlan::apply` then runs the chosen
+//! `ImportMode` in one transaction and returns the changes for the undo stack.
+
+use crate::database::Database;
+use crate::equipment::Equipment;
+use crate::error::{Error, Result};
+use crate::retire_reason::RetireReason;
+use crate::undo::Change;
+use std::fmt;
+
+/// How an import treats items that are already in the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// The database ends up matching the file; items missing from it go to the recycle bin
+    Replace,
+    /// Add new items and apply changes; conflicts and missing items are left alone
+    Merge,
+    /// Add every item in the file under a new ID; nothing existing is touched
+    AppendAsNew,
+}
+
+impl ImportMode {
+    pub fn all() -> Vec<ImportMode> {
+        vec![ImportMode::Replace, ImportMode::Merge, ImportMode::AppendAsNew]
+    }
+}
+
+impl fmt::Display for ImportMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ImportMode::Replace => "Skipta út",
+                ImportMode::Merge => "Sameina",
+                ImportMode::AppendAsNew => "Bæta við sem nýjum",
+            }
+        )
+    }
+}
+
+impl TryFrom<&str> for ImportMode {
+    type Error = Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "replace" | "skipta" => Ok(ImportMode::Replace),
+            "merge" | "sameina" => Ok(ImportMode::Merge),
+            "append" | "append-as-new" | "bæta" | "baeta" => Ok(ImportMode::AppendAsNew),
+            _ => Err(Error::UnknownImportMode(value.to_string())),
+        }
+    }
+}
+
+/// Why an item in the file cannot simply be merged
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConflictKind {
+    /// The ID belongs to an item of another type
+    TypeChanged,
+    /// The ID belongs to an item in the recycle bin
+    InRecycleBin(RetireReason),
+    /// The ID appears more than once in the file; only the first one is used
+    DuplicateId,
+}
+
+impl fmt::Display for ConflictKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConflictKind::TypeChanged => write!(f, "Önnur tegund"),
+            ConflictKind::InRecycleBin(reason) => write!(f, "Í ruslafötu ({})", reason),
+            ConflictKind::DuplicateId => write!(f, "ID kemur oftar en einu sinni fyrir"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub id: i64,
+    pub kind: ConflictKind,
+    /// Current version in the database, if any
+    pub existing: Option<Equipment>,
+    pub incoming: Equipment,
+}
+
+/// Differences between an import file and the database
+#[derive(Debug, Clone, Default)]
+pub struct ImportPlan {
+    /// Items without an ID or with an ID the database has not seen
+    pub new: Vec<Equipment>,
+    /// (current, incoming) for active items whose fields differ
+    pub changed: Vec<(Equipment, Equipment)>,
+    pub unchanged: Vec<Equipment>,
+    pub conflicts: Vec<Conflict>,
+    /// Active items that are not in the file
+    pub missing: Vec<Equipment>,
+    /// Why items that fail `Equipment::check`, e.g. with 0 seats, are left out of the plan
+    pub invalid: Vec<String>,
+}
+
+impl ImportPlan {
+    /// Compare `incoming` with the database. Nothing is written.
+    pub fn new(db: &Database, incoming: Vec<Equipment>) -> Result<Self> {
+        let current = db.get_all_equipment()?;
+        let retired = db.get_retired_equipment()?;
+        let mut plan = ImportPlan::default();
+        let categories = db.get_categories()?;
+        let mut seen = Vec::new();
+
+        for (index, mut eq) in incoming.into_iter().enumerate() {
+            // JSON files name a category by its code only
+            if let Equipment::Custom(item) = &mut eq {
+                if let Some(category) = categories.iter().find(|c| c.code == item.category.code) {
+                    item.category = category.clone();
+                }
+            }
+            // Values JSON allows but the constructors do not would be written and then dropped as corrupt
+            if let Err(e) = eq.check() {
+                plan.invalid.push(Error::ImportItem { item: index + 1, reason: e.to_string() }.to_string());
+                // An item that is in the file, even if broken, is not missing from it
+                seen.extend(eq.get_id());
+                continue;
+            }
+            let Some(id) = eq.get_id() else {
+                plan.new.push(eq);
+                continue;
+            };
+            if seen.contains(&id) {
+                let existing = current.iter().find(|c| c.get_id() == Some(id)).cloned();
+                plan.conflicts.push(Conflict { id, kind: ConflictKind::DuplicateId, existing, incoming: eq });
+                continue;
+            }
+            seen.push(id);
+
+            if let Some(existing) = current.iter().find(|c| c.get_id() == Some(id)) {
+                if *existing == eq {
+                    plan.unchanged.push(eq);
+                } else if existing.type_code() != eq.type_code() {
+                    plan.conflicts.push(Conflict {
+                        id,
+                        kind: ConflictKind::TypeChanged,
+                        existing: Some(existing.clone()),
+                        incoming: eq,
+                    });
+                } else {
+                    plan.changed.push((existing.clone(), eq));
+                }
+            } else if let Some(r) = retired.iter().find(|r| r.equipment.get_id() == Some(id)) {
+                plan.conflicts.push(Conflict {
+                    id,
+                    kind: ConflictKind::InRecycleBin(r.reason),
+                    existing: Some(r.equipment.clone()),
+                    incoming: eq,
+                });
+            } else {
+                plan.new.push(eq);
+            }
+        }
+
+        plan.missing = current
+            .into_iter()
+            .filter(|c| c.get_id().is_some_and(|id| !seen.contains(&id)))
+            .collect();
+        Ok(plan)
+    }
+
+    /// One-line count of each category, e.g. for a status message
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} nýir, {} breyttir, {} óbreyttir, {} árekstrar, {} vantar í skrá",
+            self.new.len(),
+            self.changed.len(),
+            self.unchanged.len(),
+            self.conflicts.len(),
+            self.missing.len()
+        );
+        if !self.invalid.is_empty() {
+            summary.push_str(&format!(", {} ógild", self.invalid.len()));
+        }
+        summary
+    }
+
+    /// Write the import in one transaction; on any error nothing is changed.
+    /// Returns what was done, in order, so it can be undone as one step.
+    pub fn apply(&self, db: &Database, mode: ImportMode) -> Result<Vec<Change>> {
+        db.transaction(|db| {
+            let mut changes = Vec::new();
+            if mode == ImportMode::AppendAsNew {
+                let all = self
+                    .new
+                    .iter()
+                    .chain(self.changed.iter().map(|(_, eq)| eq))
+                    .chain(&self.unchanged)
+                    .chain(self.conflicts.iter().map(|c| &c.incoming));
+                for eq in all {
+                    changes.push(insert_as_new(db, eq)?);
+                }
+                return Ok(changes);
+            }
+
+            for eq in &self.new {
+                match eq.get_id() {
+                    Some(id) => {
+                        db.insert_equipment_with_id(id, eq)?;
+                        changes.push(Change::Registered { id, equipment: Box::new(eq.clone()) });
+                    }
+                    None => changes.push(insert_as_new(db, eq)?),
+                }
+            }
+            for (before, after) in &self.changed {
+                let id = after.get_id().unwrap_or_default();
+                db.update_equipment(id, after)?;
+                changes.push(Change::Edited { id, before: Box::new(before.clone()), after: Box::new(after.clone()) });
+            }
+            if mode == ImportMode::Merge {
+                return Ok(changes);
+            }
+
+            for conflict in &self.conflicts {
+                let (id, incoming) = (conflict.id, &conflict.incoming);
+                match (&conflict.kind, &conflict.existing) {
+                    (ConflictKind::TypeChanged, Some(before)) => {
+                        db.update_equipment(id, incoming)?;
+                        changes.push(Change::Edited { id, before: Box::new(before.clone()), after: Box::new(incoming.clone()) });
+                    }
+                    (ConflictKind::InRecycleBin(reason), Some(before)) => {
+                        db.insert_equipment_with_id(id, incoming)?;
+                        changes.push(Change::Restored { id, reason: *reason });
+                        if before != incoming {
+                            changes.push(Change::Edited { id, before: Box::new(before.clone()), after: Box::new(incoming.clone()) });
+                        }
+                    }
+                    // Duplicates are skipped: the first occurrence has already been used
+                    _ => {}
+                }
+            }
+            for eq in &self.missing {
+                if let Some(id) = eq.get_id() {
+                    db.retire_equipment(id, RetireReason::Other)?;
+                    changes.push(Change::Retired { id, reason: RetireReason::Other });
+                }
+            }
+            Ok(changes)
+        })
+    }
+}
+
+fn insert_as_new(db: &Database, eq: &Equipment) -> Result<Change> {
+    let id = db.insert_equipment(eq)?;
+    let mut equipment = eq.clone();
+    equipment.set_id(id);
+    Ok(Change::Registered { id, equipment: Box::new(equipment) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chair::Chair;
+    use crate::chair_type::ChairType;
+    use crate::building::Building;
+    use crate::location::Location;
+    use crate::projector::Projector;
+    use crate::table::Table;
+
+    fn with_id(mut eq: Equipment, id: i64) -> Equipment {
+        eq.set_id(id);
+        eq
+    }
+
+    #[test]
+    fn plan_sorts_items_and_merge_leaves_conflicts_alone() {
+        let db = Database::with_rooms(&["H-22", "S-11"]);
+        let loc = Location::new(Building::new("H").unwrap(), 2, 2);
+        let table = Equipment::Table(Table::new(loc.clone(), 45000, 4));
+        let chair = Equipment::Chair(Chair::new(loc.clone(), 9000, ChairType::Skolastoll));
+        let projector = Equipment::Projector(Projector::new(loc.clone(), 90000, 3000));
+        let t = db.insert_equipment(&table).unwrap();
+        let c = db.insert_equipment(&chair).unwrap();
+        let p = db.insert_equipment(&projector).unwrap();
+        let gone = db.insert_equipment(&table).unwrap();
+
+        let mut moved_table = with_id(table.clone(), t);
+        moved_table.set_location(Location::new(Building::new("S").unwrap(), 1, 1));
+        let incoming = vec![
+            moved_table.clone(),
+            with_id(table.clone(), c),     // chair ID, but a table
+            with_id(projector.clone(), p), // unchanged
+            with_id(chair.clone(), 40),    // new with ID
+            chair.clone(),                 // new without ID
+            with_id(chair.clone(), 40),    // duplicate
+        ];
+        let plan = ImportPlan::new(&db, incoming).unwrap();
+        assert_eq!((plan.new.len(), plan.changed.len(), plan.unchanged.len()), (2, 1, 1));
+        assert_eq!(plan.conflicts.len(), 2);
+        assert_eq!(plan.conflicts[0].kind, ConflictKind::TypeChanged);
+        assert_eq!(plan.conflicts[1].kind, ConflictKind::DuplicateId);
+        assert_eq!(plan.missing.iter().map(|e| e.get_id()).collect::<Vec<_>>(), [Some(gone)]);
+
+        let changes = plan.apply(&db, ImportMode::Merge).unwrap();
+        assert_eq!(changes.len(), 3);
+        assert_eq!(db.get_equipment_by_id(t).unwrap(), Some(moved_table));
+        assert_eq!(db.get_equipment_by_id(c).unwrap().unwrap().get_type_name(), chair.get_type_name());
+        assert!(db.get_equipment_by_id(gone).unwrap().is_some());
+        assert_eq!(db.get_all_equipment().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn invalid_items_are_reported_and_never_written() {
+        let db = Database::with_rooms(&["H-22"]);
+        let loc = Location::new(Building::new("H").unwrap(), 2, 2);
+        let t = db.insert_equipment(&Equipment::Table(Table::new(loc.clone(), 45000, 4))).unwrap();
+        let before = db.get_all_equipment().unwrap();
+
+        // As serde reads them from a JSON file
+        let no_seats = Equipment::Table(Table::new(loc.clone(), 45000, 0));
+        let mut far = loc.clone();
+        far.room = 5000;
+        let moved_far = with_id(Equipment::Table(Table::new(far, 45000, 4)), t);
+        let plan = ImportPlan::new(&db, vec![no_seats.clone(), moved_far]).unwrap();
+        assert_eq!(plan.invalid.len(), 2);
+        assert_eq!(plan.invalid[0], "Atriði 1 í skránni: Fjöldi sæta má ekki vera 0");
+        assert!(plan.new.is_empty() && plan.changed.is_empty());
+        // The table is in the file, so Replace does not send it to the recycle bin
+        assert!(plan.missing.is_empty());
+        assert!(plan.apply(&db, ImportMode::Replace).unwrap().is_empty());
+        assert_eq!(db.get_all_equipment().unwrap(), before);
+
+        // Even if an invalid item reaches `apply`, the write fails and nothing is kept
+        let plan = ImportPlan { new: vec![Equipment::Chair(Chair::new(loc, 9000, ChairType::Skolastoll)), no_seats], ..Default::default() };
+        assert!(matches!(plan.apply(&db, ImportMode::Merge), Err(Error::ZeroSeats)));
+        assert_eq!(db.get_all_equipment().unwrap(), before);
+    }
+}
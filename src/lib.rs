@@ -0,0 +1,69 @@
+//! Búnaðarlisti Tækniskólans – domain model and SQLite storage.
+//!
+//! The egui application in `main.rs` is one client of this library; scripts,
+//! import tools and tests can use the same types and `Database` methods.
+
+pub mod attribute;
+pub mod attribute_kind;
+pub mod audit;
+pub mod building;
+pub mod category;
+pub mod chair;
+pub mod chair_type;
+pub mod computer;
+pub mod computer_kind;
+pub mod condition;
+pub mod csv_file;
+pub mod custom_equipment;
+pub mod database;
+pub mod depreciation;
+pub mod equipment;
+pub mod equipment_info;
+pub mod error;
+pub mod history;
+pub mod import;
+pub mod location;
+pub mod migrations;
+pub mod monitor;
+pub mod pdf;
+pub mod print;
+pub mod projector;
+pub mod resolution;
+pub mod retire_reason;
+pub mod room;
+pub mod room_type;
+pub mod seating;
+pub mod statistics;
+pub mod table;
+pub mod undo;
+pub mod xlsx_export;
+
+pub use attribute::{AttributeDef, AttributeValue};
+pub use attribute_kind::AttributeKind;
+pub use audit::{AuditReport, AuditScan, AuditSession};
+pub use building::{Building, BuildingInfo};
+pub use category::Category;
+pub use chair::Chair;
+pub use chair_type::ChairType;
+pub use computer::Computer;
+pub use computer_kind::ComputerKind;
+pub use condition::Condition;
+pub use custom_equipment::CustomEquipment;
+pub use database::Database;
+pub use depreciation::Depreciation;
+pub use equipment::Equipment;
+pub use equipment_info::EquipmentInfo;
+pub use error::{Error, Result};
+pub use history::{HistoryAction, HistoryEntry};
+pub use import::{Conflict, ConflictKind, ImportMode, ImportPlan};
+pub use location::Location;
+pub use monitor::Monitor;
+pub use projector::Projector;
+pub use resolution::Resolution;
+pub use retire_reason::{RetireReason, RetiredEquipment};
+pub use room::Room;
+pub use room_type::RoomType;
+pub use seating::{ChairMove, RoomSeating};
+pub use statistics::{Statistics, Tally};
+pub use table::Table;
+pub use undo::{Change, UndoStack};
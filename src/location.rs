@@ -1,156 +1,184 @@
+use crate::building::Building;
+use crate::error::Error;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// Lowest floor; basements are negative and written `K1`..`K9` (kjallari)
+pub const MIN_FLOOR: i8 = -9;
+pub const MAX_FLOOR: i8 = 99;
+pub const MAX_ROOM: u16 = 999;
+
+/// Room in a building, written `{HÚS}-{HÆÐ}.{HERBERGI}{BÓKSTAFUR}`, e.g. `H-2.12`, `S-11.05`, `H-K1.04` or `H-2.02a`.
+///
+/// Field order is the sort order: building, floor, room number, then suffix.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Location {
     pub building: Building,
-    pub floor: u8,
-    pub room: u8,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Building {
-    Hafnarfjordur,  // HA
-    Hateigssvegur,  // H
-    Skolavorduhollt, // S
+    pub floor: i8,
+    pub room: u16,
+    /// Lowercase letter after the room number, as in `H-2.02a`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<char>,
 }
 
-impl Building {
-    pub fn from_code(code: &str) -> Option<Self> {
-        match code {
-            "HA" => Some(Building::Hafnarfjordur),
-            "H" => Some(Building::Hateigssvegur),
-            "S" => Some(Building::Skolavorduhollt),
-            _ => None,
-        }
-    }
-
-    pub fn to_code(&self) -> &str {
-        match self {
-            Building::Hafnarfjordur => "HA",
-            Building::Hateigssvegur => "H",
-            Building::Skolavorduhollt => "S",
+impl Location {
+    pub fn new(building: Building, floor: i8, room: u16) -> Self {
+        Location {
+            building,
+            floor,
+            room,
+            suffix: None,
         }
     }
 
-    pub fn all() -> Vec<Building> {
-        vec![
-            Building::Hafnarfjordur,
-            Building::Hateigssvegur,
-            Building::Skolavorduhollt,
-        ]
+    /// Same checks as `TryFrom`, for locations that were not built by it, e.g. read from JSON
+    pub fn check(&self) -> Result<(), Error> {
+        Location::try_from((self.building.clone(), self.floor, self.room, self.suffix)).map(|_| ())
     }
 }
 
-impl fmt::Display for Building {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Building::Hafnarfjordur => "Hafnarfjörður",
-                Building::Hateigssvegur => "Háteigsvegur",
-                Building::Skolavorduhollt => "Skólavörðuholt",
-            }
-        )
+/// Floor as it is written in a location: `2`, or `K1` for the first basement level
+pub fn floor_code(floor: i8) -> String {
+    if floor < 0 {
+        format!("K{}", -i16::from(floor))
+    } else {
+        floor.to_string()
     }
 }
 
-impl TryFrom<&str> for Building {
-    type Error = String;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        // Accept both short codes and full Icelandic names
-        match value.trim() {
-            "HA" | "Hafnarfjörður" | "Hafnarfjordur" => Ok(Building::Hafnarfjordur),
-            "H" | "Háteigsvegur" | "Hateigssvegur" => Ok(Building::Hateigssvegur),
-            "S" | "Skólavörðuholt" | "Skolavorduhollt" => Ok(Building::Skolavorduhollt),
-            other => Err(format!("Óþekkt hús: {}", other)),
-        }
+/// Read a floor as written in a location or a CSV column: `2`, `K1` or `-1`
+pub fn parse_floor(text: &str) -> Result<i8, Error> {
+    let text = text.trim();
+    let floor = match text.strip_prefix(['K', 'k']) {
+        Some(depth) => depth.parse::<i16>().map(|d| -d),
+        None => text.parse::<i16>(),
     }
-}
-
-impl TryFrom<String> for Building {
-    type Error = String;
-
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        Building::try_from(value.as_str())
+    .map_err(|_| Error::InvalidLocationFormat(text.to_string()))?;
+    let floor = i8::try_from(floor).map_err(|_| Error::FloorOutOfRange(floor))?;
+    if !(MIN_FLOOR..=MAX_FLOOR).contains(&floor) {
+        return Err(Error::FloorOutOfRange(floor.into()));
     }
+    Ok(floor)
 }
 
-impl Location {
-    pub fn new(building: Building, floor: u8, room: u8) -> Self {
-        Location {
-            building,
-            floor,
-            room,
-        }
+/// Floor for headings, e.g. "2. hæð" or "1. kjallari"
+pub fn floor_name(floor: i8) -> String {
+    if floor < 0 {
+        format!("{}. kjallari", -i16::from(floor))
+    } else {
+        format!("{}. hæð", floor)
     }
 }
 
 impl fmt::Display for Location {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}-{}{}", self.building.to_code(), self.floor, self.room)
+        write!(f, "{}-{}.{:02}", self.building.to_code(), floor_code(self.floor), self.room)?;
+        if let Some(suffix) = self.suffix {
+            write!(f, "{}", suffix)?;
+        }
+        Ok(())
     }
 }
 
 impl TryFrom<&str> for Location {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let re = Regex::new(r"^(HA|H|S)-([0-9])([0-9]{1,2})$").unwrap();
-
-        if let Some(caps) = re.captures(value) {
-            let building_code = caps.get(1).unwrap().as_str();
-            let floor_str = caps.get(2).unwrap().as_str();
-            let room_str = caps.get(3).unwrap().as_str();
-
-            let building = Building::from_code(building_code)
-                .ok_or_else(|| format!("Ógilt húsmerki: {}", building_code))?;
-
-            let floor = floor_str
-                .parse::<u8>()
-                .map_err(|_| format!("Ógild hæð: {}", floor_str))?;
-
-            let room = room_str
-                .parse::<u8>()
-                .map_err(|_| format!("Ógillt herbergisnúmer: {}", room_str))?;
-
-            if room > 99 {
-                return Err(format!("Herbergisnúmer má ekki vera hærra en 99: {}", room));
-            }
-
-            Ok(Location {
-                building,
-                floor,
-                room,
-            })
-        } else {
-            Err(format!(
-                "Ógilt staðsetningarsnið: {}. Ætti að vera t.d. H-202 eða HA-123",
-                value
-            ))
-        }
+        // The code is only checked for shape here; `Database` checks it against the buildings table
+        let text = value.trim();
+        let current = Regex::new(r"^([A-Z]{1,5})-(K[0-9]{1,3}|[0-9]{1,3})\.([0-9]{1,4})([a-zA-Z])?$").unwrap();
+        // Codes written before the floor separator: one floor digit, then the room (`H-212`, `H-22`)
+        let legacy = Regex::new(r"^([A-Z]{1,5})-([0-9])([0-9]{1,2})([a-zA-Z])?$").unwrap();
+
+        let caps = current
+            .captures(text)
+            .or_else(|| legacy.captures(text))
+            .ok_or_else(|| Error::InvalidLocationFormat(value.to_string()))?;
+        let building = Building::new(&caps[1])?;
+        let floor = parse_floor(&caps[2])?;
+        let room = caps[3]
+            .parse::<u16>()
+            .map_err(|_| Error::InvalidLocationFormat(value.to_string()))?;
+        let suffix = caps.get(4).and_then(|s| s.as_str().chars().next());
+
+        Location::try_from((building, floor, room, suffix))
     }
 }
 
 impl TryFrom<String> for Location {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         Location::try_from(value.as_str())
     }
 }
 
-impl TryFrom<(Building, u8, u8)> for Location {
-    type Error = String;
+impl TryFrom<(Building, i8, u16)> for Location {
+    type Error = Error;
 
-    fn try_from(value: (Building, u8, u8)) -> Result<Self, Self::Error> {
+    fn try_from(value: (Building, i8, u16)) -> Result<Self, Self::Error> {
         let (building, floor, room) = value;
-        if room > 99 {
-            return Err(format!("Herbergisnúmer má ekki vera hærra en 99: {}", room));
+        Location::try_from((building, floor, room, None))
+    }
+}
+
+impl TryFrom<(Building, i8, u16, Option<char>)> for Location {
+    type Error = Error;
+
+    fn try_from(value: (Building, i8, u16, Option<char>)) -> Result<Self, Self::Error> {
+        let (building, floor, room, suffix) = value;
+        if !(MIN_FLOOR..=MAX_FLOOR).contains(&floor) {
+            return Err(Error::FloorOutOfRange(floor.into()));
+        }
+        if room > MAX_ROOM {
+            return Err(Error::RoomOutOfRange(room));
         }
-        Ok(Location { building, floor, room })
+        let suffix = match suffix {
+            Some(c) if c.is_ascii_alphabetic() => Some(c.to_ascii_lowercase()),
+            Some(c) => return Err(Error::InvalidRoomSuffix(c)),
+            None => None,
+        };
+        Ok(Location { building, floor, room, suffix })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loc(text: &str) -> Location {
+        Location::try_from(text).unwrap()
+    }
+
+    #[test]
+    fn reads_current_and_legacy_codes() {
+        let h = || Building::new("H").unwrap();
+        assert_eq!(loc("H-2.12"), Location::new(h(), 2, 12));
+        assert_eq!(loc("H-212"), Location::new(h(), 2, 12));
+        // Old codes with a one-digit room number were displayed without the zero
+        assert_eq!(loc("H-202"), loc("H-22"));
+        assert_eq!(loc("H-K1.04"), Location::new(h(), -1, 4));
+        assert_eq!(loc("S-11.05").floor, 11);
+        assert_eq!(loc("S-1.105").room, 105);
+        assert_eq!(loc("H-202A").suffix, Some('a'));
+        assert_eq!(loc("H-2.02a"), loc("H-202a"));
+
+        for text in ["H-2.02a", "S-11.05", "H-K1.04", "S-1.105", "H-0.00"] {
+            assert_eq!(loc(text).to_string(), text);
+        }
+
+        assert!(matches!(Location::try_from("S-1105"), Err(Error::InvalidLocationFormat(_))));
+        assert!(matches!(Location::try_from("H-2.1000"), Err(Error::RoomOutOfRange(_))));
+        assert!(matches!(Location::try_from("H-K10.01"), Err(Error::FloorOutOfRange(-10))));
+        assert!(matches!(Location::try_from((h(), 1, 1, Some('1'))), Err(Error::InvalidRoomSuffix('1'))));
+    }
+
+    #[test]
+    fn sorts_by_floor_then_room() {
+        let mut rooms = [loc("H-10.01"), loc("H-2.02a"), loc("H-K1.01"), loc("H-2.02"), loc("H-2.10")];
+        rooms.sort();
+        let codes: Vec<String> = rooms.iter().map(|r| r.to_string()).collect();
+        assert_eq!(codes, ["H-K1.01", "H-2.02", "H-2.02a", "H-2.10", "H-10.01"]);
     }
 }
@@ -1,25 +1,26 @@
-mod chair;
-mod chair_type;
-mod database;
-mod equipment;
-mod location;
-mod projector;
-mod table;
-
-use chair::Chair;
-use chair_type::ChairType;
-use database::Database;
+use bunadarlisti::{
+    AttributeDef, AttributeKind, AuditReport, AuditSession, Building, BuildingInfo, Category, Chair, ChairType, Change, Computer, ComputerKind,
+    Condition, CustomEquipment, Database, Depreciation, Equipment, EquipmentInfo, Error, HistoryEntry, ImportMode, ImportPlan, Location, Monitor,
+    Projector, Resolution, RetireReason, RetiredEquipment, Room, RoomSeating, RoomType, Statistics, Table, UndoStack,
+};
+use bunadarlisti::csv_file;
+use bunadarlisti::equipment::parse_id_list;
+use bunadarlisti::depreciation;
+use bunadarlisti::equipment_info;
+use bunadarlisti::pdf;
+use bunadarlisti::seating;
+use bunadarlisti::print;
+use bunadarlisti::xlsx_export;
+use bunadarlisti::history::describe_changes;
+use bunadarlisti::location;
+use bunadarlisti::monitor;
 use eframe::egui;
 use eframe::egui::{IconData, TextureHandle};
 use eframe::epaint::ColorImage;
 use rfd::FileDialog;
-use equipment::Equipment;
-use location::{Building, Location};
-use projector::Projector;
-use table::Table;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::sync::Mutex;
-use std::process::Command;
 use std::time::Duration;
 
 fn main() -> Result<(), eframe::Error> {
@@ -50,13 +51,177 @@ enum AppSection {
     Edit,
     Search,
     Print,
+    Audit,
+    Buildings,
+    Categories,
+    RecycleBin,
 }
 
-#[derive(PartialEq, Clone, Copy)]
+#[derive(PartialEq, Clone)]
 enum EquipmentType {
     Table,
     Chair,
     Projector,
+    Computer,
+    Monitor,
+    /// User-defined category, by code
+    Custom(String),
+}
+
+impl EquipmentType {
+    /// Value of the `equipment.type` column
+    fn code(&self) -> &str {
+        match self {
+            EquipmentType::Table => "Table",
+            EquipmentType::Chair => "Chair",
+            EquipmentType::Projector => "Projector",
+            EquipmentType::Computer => "Computer",
+            EquipmentType::Monitor => "Monitor",
+            EquipmentType::Custom(code) => code,
+        }
+    }
+
+    fn name(&self, categories: &[Category]) -> String {
+        match self {
+            EquipmentType::Table => "Borð".to_string(),
+            EquipmentType::Chair => "Stóll".to_string(),
+            EquipmentType::Projector => "Skjávarpi".to_string(),
+            EquipmentType::Computer => "Tölva".to_string(),
+            EquipmentType::Monitor => "Skjár".to_string(),
+            EquipmentType::Custom(code) => categories.iter().find(|c| c.code == *code).map_or_else(|| code.clone(), |c| c.name.clone()),
+        }
+    }
+}
+
+// Input fields shared by the Register and Edit screens so both validate the same way
+struct EquipmentForm {
+    equipment_type: EquipmentType,
+    location: Location,
+    value: String,
+    table_seats: u8,
+    chair_type: ChairType,
+    projector_lumens: String,
+    // Asset tag, serial number, manufacturer, model, purchase and notes, for every type
+    info: EquipmentInfo,
+    // Purchase date as typed; parsed into `info` when saved
+    purchase_date: String,
+    // Computers and monitors
+    computer_kind: ComputerKind,
+    cpu: String,
+    ram_gb: String,
+    screen_size: String,
+    resolution: String,
+    // Attribute values of a category item as typed, by key
+    attributes: BTreeMap<String, String>,
+}
+
+impl EquipmentForm {
+    fn new(location: Location) -> Self {
+        EquipmentForm {
+            equipment_type: EquipmentType::Table,
+            location,
+            value: String::new(),
+            table_seats: 4,
+            chair_type: ChairType::Skolastoll,
+            projector_lumens: String::new(),
+            info: EquipmentInfo::default(),
+            purchase_date: String::new(),
+            computer_kind: ComputerKind::Laptop,
+            cpu: String::new(),
+            ram_gb: String::new(),
+            screen_size: String::new(),
+            resolution: "1920x1080".to_string(),
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    fn from_equipment(equipment: &Equipment) -> Self {
+        let mut form = EquipmentForm {
+            value: equipment.get_value().to_string(),
+            info: equipment.info().clone(),
+            purchase_date: equipment.info().purchase_date.map(equipment_info::format_date).unwrap_or_default(),
+            ..EquipmentForm::new(equipment.get_location().clone())
+        };
+        match equipment {
+            Equipment::Table(t) => {
+                form.equipment_type = EquipmentType::Table;
+                form.table_seats = t.seats;
+            }
+            Equipment::Chair(c) => {
+                form.equipment_type = EquipmentType::Chair;
+                form.chair_type = c.chair_type;
+            }
+            Equipment::Projector(p) => {
+                form.equipment_type = EquipmentType::Projector;
+                form.projector_lumens = p.lumens.to_string();
+            }
+            Equipment::Computer(c) => {
+                form.equipment_type = EquipmentType::Computer;
+                form.computer_kind = c.kind;
+                form.cpu = c.cpu.clone();
+                form.ram_gb = c.ram_gb.map(|n| n.to_string()).unwrap_or_default();
+            }
+            Equipment::Monitor(m) => {
+                form.equipment_type = EquipmentType::Monitor;
+                form.screen_size = m.screen_size.to_string();
+                form.resolution = m.resolution.to_string();
+            }
+            Equipment::Custom(x) => {
+                form.equipment_type = EquipmentType::Custom(x.category.code.clone());
+                form.attributes = x.attributes.iter().map(|(k, v)| (k.clone(), v.to_string())).collect();
+            }
+        }
+        form
+    }
+
+    fn to_equipment(&self, categories: &[Category]) -> Result<Equipment, String> {
+        let value = self
+            .value
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| "Verðmæti verður að vera tala".to_string())?;
+        let location = self.location.clone();
+        let mut equipment = match &self.equipment_type {
+            EquipmentType::Table => Table::try_from((location, value, self.table_seats)).map(Equipment::Table),
+            EquipmentType::Chair => Chair::try_from((location, value, self.chair_type)).map(Equipment::Chair),
+            EquipmentType::Projector => {
+                let lumens = self
+                    .projector_lumens
+                    .trim()
+                    .parse::<u32>()
+                    .map_err(|_| "Lumens verður að vera tala".to_string())?;
+                Projector::try_from((location, value, lumens)).map(Equipment::Projector)
+            }
+            EquipmentType::Computer => {
+                let mut computer = Computer::new(location, value, self.computer_kind);
+                computer.cpu = self.cpu.trim().to_string();
+                if !self.ram_gb.trim().is_empty() {
+                    computer.ram_gb =
+                        Some(self.ram_gb.trim().parse::<u16>().map_err(|_| "Vinnsluminni verður að vera tala (GB)".to_string())?);
+                }
+                Ok(Equipment::Computer(computer))
+            }
+            EquipmentType::Monitor => {
+                let screen_size = monitor::parse_screen_size(&self.screen_size).map_err(|e| e.to_string())?;
+                let resolution = Resolution::try_from(self.resolution.as_str()).map_err(|e| e.to_string())?;
+                Ok(Equipment::Monitor(Monitor::new(location, value, screen_size, resolution)))
+            }
+            EquipmentType::Custom(code) => {
+                let category = categories.iter().find(|c| c.code == *code).ok_or_else(|| format!("Óþekktur flokkur: {}", code))?;
+                // Values left over from another category are not part of this one
+                let raw = self.attributes.iter().filter(|(k, _)| category.attribute(k).is_some()).map(|(k, v)| (k.clone(), v.clone())).collect();
+                category
+                    .parse_attributes(&raw)
+                    .map(|attributes| Equipment::Custom(CustomEquipment::new(location, value, category.clone(), attributes)))
+            }
+        }
+        .map_err(|e| e.to_string())?;
+        let mut info = self.info.trimmed();
+        info.purchase_date = equipment_info::parse_optional_date(&self.purchase_date).map_err(|e| e.to_string())?;
+        equipment.set_info(info);
+        equipment.check().map_err(|e| e.to_string())?;
+        Ok(equipment)
+    }
 }
 
 #[derive(PartialEq, Copy, Clone)]
@@ -66,6 +231,7 @@ enum DisplayFilter {
     ByType,
     ByRoom,
     ByFloor,
+    ByCondition,
 }
 
 #[derive(PartialEq, Clone, Copy)]
@@ -82,6 +248,14 @@ enum SortOrder {
     Descending,
 }
 
+/// Import file that has been read and compared with the database, not yet applied
+struct ImportPreview {
+    file_name: String,
+    plan: ImportPlan,
+    // Lines of a CSV file that could not be read
+    errors: Vec<String>,
+}
+
 struct EquipmentApp {
     db: Arc<Mutex<Database>>,
     current_section: AppSection,
@@ -92,23 +266,17 @@ struct EquipmentApp {
     came_from_print: bool,
     
     // Registration fields
-    reg_equipment_type: EquipmentType,
-    reg_building: Building,
-    reg_floor: u8,
-    reg_room: u8,
-    reg_value: String,
-    reg_table_seats: u8,
-    reg_chair_type: ChairType,
-    reg_projector_lumens: String,
+    reg_form: EquipmentForm,
     
     // Edit fields (combined update/delete)
     edit_id: String,
     // Debounce timestamp for auto-fetching by ID in Edit
     edit_id_changed_at: Option<std::time::Instant>,
     edit_equipment: Option<Equipment>,
-    edit_building: Building,
-    edit_floor: u8,
-    edit_room: u8,
+    // Timeline of the fetched ID (also shown for deleted items)
+    edit_history: Vec<HistoryEntry>,
+    edit_retire_reason: RetireReason,
+    edit_form: EquipmentForm,
     
     // Search fields
     search_query: String,
@@ -116,13 +284,69 @@ struct EquipmentApp {
     search_selected_index: Option<usize>,
     search_initialized: bool,
     
+    // Recycle bin
+    bin_items: Vec<RetiredEquipment>,
+    // ID waiting for a second click on "Eyða varanlega"
+    bin_confirm_purge: Option<i64>,
+    
+    // Undo/redo of changes made in this session
+    undo_stack: UndoStack,
+    
+    // JSON/CSV import waiting for confirmation
+    import_preview: Option<ImportPreview>,
+    import_mode: ImportMode,
+    
+    // Room inventory sheets: building and, optionally, one floor
+    sheet_building: Building,
+    sheet_floor: Option<i8>,
+    
+    // QR labels: ID list (empty means the displayed list) and labels already used on the first sheet
+    label_ids: String,
+    label_skip: usize,
+    
+    // Direct printing through lpr, checked once at startup
+    direct_print: bool,
+    printers: Vec<String>,
+    printer: Option<String>,
+    
+    // Buildings from the database, by name; reloaded after they are changed
+    buildings: Vec<BuildingInfo>,
+    // "Hús" screen: the new-building form, the one being edited and a pending delete
+    new_building_code: String,
+    new_building_name: String,
+    new_building_address: String,
+    edit_building: Option<BuildingInfo>,
+    building_confirm_delete: Option<Building>,
+    
+    // Registered rooms, offered by the room picker; reloaded after they are changed
+    rooms: Vec<Room>,
+    // Room list on the same screen: its search field, the new-room form, the one being edited and a pending delete
+    room_filter: String,
+    new_room: Room,
+    edit_room: Option<Room>,
+    room_confirm_delete: Option<Location>,
+    seating_problems_only: bool,
+    
+    // User-defined equipment categories, offered in the forms; reloaded after they are changed
+    categories: Vec<Category>,
+    // "Flokkar" screen: the new-category form, the one being edited and a pending delete
+    new_category_code: String,
+    new_category_name: String,
+    edit_category: Option<Category>,
+    category_confirm_delete: Option<String>,
+    
+    // Stocktake: room to start in, the open session and the field IDs are scanned into
+    audit_location: Location,
+    audit_session: Option<AuditSession>,
+    audit_input: String,
+    
     // Display fields
     display_filter: DisplayFilter,
     display_building: Building,
     display_type: EquipmentType,
-    display_room_floor: u8,
-    display_room_number: u8,
-    display_floor: u8,
+    display_room: Location,
+    display_floor: i8,
+    display_condition: Condition,
     
     // Display results
     displayed_equipment: Vec<Equipment>,
@@ -138,18 +362,24 @@ struct EquipmentApp {
     
     // Statistics
     show_stats: bool,
+    // Date the book values are for, as typed
+    stats_date: String,
+    // Depreciation periods; reloaded after they are changed
+    depreciation: Depreciation,
+    // Periods as typed in the settings under the statistics, by type code
+    depreciation_inputs: BTreeMap<String, String>,
     // Sidebar toggle
     show_sidebar: bool,
 }
 
 impl EquipmentApp {
     // Draw a radio button with black fill when selected, preserving label color
-    fn radio_black_value<T: PartialEq + Copy>(ui: &mut egui::Ui, value: &mut T, selected: T, label: &str) {
+    fn radio_black_value<T: PartialEq + Clone>(ui: &mut egui::Ui, value: &mut T, selected: T, label: &str) {
         ui.horizontal(|ui| {
             let size = ui.spacing().interact_size.y;
             let (rect, resp) = ui.allocate_at_least(egui::vec2(size, size), egui::Sense::click());
             let is_selected = *value == selected;
-            if resp.clicked() { *value = selected; }
+            if resp.clicked() { *value = selected.clone(); }
             // Paint black-dot radio
             let center = rect.center();
             let outer_r = rect.height() * 0.35;
@@ -167,6 +397,29 @@ impl EquipmentApp {
     }
     fn new() -> Self {
         let db = Database::new("equipment.db").expect("Failed to create database");
+        // Load errors are shown once the app is up; the defaults keep the forms usable meanwhile
+        let mut load_errors = Vec::new();
+        let buildings = db.get_buildings().unwrap_or_else(|e| {
+            load_errors.push(format!("Villa við að sækja hús: {}", e));
+            Vec::new()
+        });
+        // The buildings table is seeded, so this only falls back if every building was removed
+        let first = buildings.first().map(|b| b.building.clone()).unwrap_or_else(|| Building::new("H").unwrap());
+        let rooms = db.get_rooms().unwrap_or_else(|e| {
+            load_errors.push(format!("Villa við að sækja stofur: {}", e));
+            Vec::new()
+        });
+        let categories = db.get_categories().unwrap_or_else(|e| {
+            load_errors.push(format!("Villa við að sækja flokka: {}", e));
+            Vec::new()
+        });
+        let depreciation = db.get_depreciation().unwrap_or_else(|e| {
+            load_errors.push(format!("Villa við að sækja afskriftir: {}", e));
+            Depreciation::default()
+        });
+        let depreciation_inputs = depreciation.years.iter().map(|(code, years)| (code.clone(), years.to_string())).collect();
+        // Forms start in the first registered room
+        let first_room = rooms.first().map(|r| r.location.clone()).unwrap_or_else(|| Location::new(first.clone(), 1, 1));
         
         let mut this = Self {
             db: Arc::new(Mutex::new(db)),
@@ -174,30 +427,55 @@ impl EquipmentApp {
             app_icon_tex: None,
             came_from_search: false,
             came_from_print: false,
-            reg_equipment_type: EquipmentType::Table,
-            reg_building: Building::Hafnarfjordur,
-            reg_floor: 1,
-            reg_room: 1,
-            reg_value: String::new(),
-            reg_table_seats: 4,
-            reg_chair_type: ChairType::Skolastoll,
-            reg_projector_lumens: String::new(),
+            reg_form: EquipmentForm::new(first_room.clone()),
             edit_id: String::new(),
             edit_id_changed_at: None,
             edit_equipment: None,
-            edit_building: Building::Hafnarfjordur,
-            edit_floor: 1,
-            edit_room: 1,
+            edit_history: Vec::new(),
+            edit_retire_reason: RetireReason::Broken,
+            edit_form: EquipmentForm::new(first_room.clone()),
             search_query: String::new(),
             search_results: Vec::new(),
             search_selected_index: None,
             search_initialized: false,
+            bin_items: Vec::new(),
+            bin_confirm_purge: None,
+            undo_stack: UndoStack::new(),
+            import_preview: None,
+            import_mode: ImportMode::Merge,
+            sheet_building: first.clone(),
+            sheet_floor: None,
+            label_ids: String::new(),
+            label_skip: 0,
+            direct_print: print::can_print_directly(),
+            printers: print::printers(),
+            printer: None,
+            buildings,
+            new_building_code: String::new(),
+            new_building_name: String::new(),
+            new_building_address: String::new(),
+            edit_building: None,
+            building_confirm_delete: None,
+            rooms,
+            room_filter: String::new(),
+            new_room: Room::new(Location::new(first.clone(), 1, 1), RoomType::Classroom),
+            edit_room: None,
+            room_confirm_delete: None,
+            seating_problems_only: false,
+            categories,
+            new_category_code: String::new(),
+            new_category_name: String::new(),
+            edit_category: None,
+            category_confirm_delete: None,
+            audit_location: first_room.clone(),
+            audit_session: None,
+            audit_input: String::new(),
             display_filter: DisplayFilter::All,
-            display_building: Building::Hafnarfjordur,
+            display_building: first,
             display_type: EquipmentType::Table,
-            display_room_floor: 1,
-            display_room_number: 1,
+            display_room: first_room,
             display_floor: 1,
+            display_condition: Condition::NeedsRepair,
             displayed_equipment: Vec::new(),
             display_output: String::new(),
             sort_column: None,
@@ -205,94 +483,166 @@ impl EquipmentApp {
             message: String::new(),
             error_message: String::new(),
             show_stats: false,
+            stats_date: equipment_info::format_date(chrono::Local::now().date_naive()),
+            depreciation,
+            depreciation_inputs,
             show_sidebar: false,
         };
         // Run initial search so users don't need to click "Sækja" or type to see data
         this.perform_search();
         this.search_initialized = true;
+        this.report_corrupt_rows();
+        if !load_errors.is_empty() {
+            this.error_message = format!(" {}", load_errors.join("; "));
+        }
         this
     }
+
+    // Rows that can't be read are left out of every list; tell the user about them once at startup
+    fn report_corrupt_rows(&mut self) {
+        let db = self.db.lock().unwrap();
+        match db.find_corrupt_rows() {
+            Ok(corrupt) if !corrupt.is_empty() => {
+                let details: Vec<String> = corrupt.iter().map(|e| e.to_string()).collect();
+                self.error_message = format!(
+                    " {} færslur í gagnagrunni er ekki hægt að lesa og eru faldar: {}",
+                    corrupt.len(),
+                    details.join("; ")
+                );
+            }
+            Ok(_) => {}
+            Err(e) => {
+                self.error_message = format!(" Villa við að lesa gagnagrunn: {}", e);
+            }
+        }
+    }
+    
+    /// Floor picker that shows basements as K1, K2, ...
+    fn floor_input(ui: &mut egui::Ui, floor: &mut i8) {
+        ui.add(
+            egui::DragValue::new(floor)
+                .range(location::MIN_FLOOR..=location::MAX_FLOOR)
+                .prefix("hæð ")
+                .custom_formatter(|n, _| location::floor_code(n as i8))
+                .custom_parser(|text| location::parse_floor(text).ok().map(f64::from)),
+        );
+    }
+    
+    // Building picker showing full names; `id_source` keeps several combos on one screen apart
+    fn building_combo(ui: &mut egui::Ui, id_source: &str, building: &mut Building, buildings: &[BuildingInfo]) {
+        egui::ComboBox::from_id_source(id_source)
+            .selected_text(format!("{} ({})", BuildingInfo::name_of(buildings, building), building))
+            .show_ui(ui, |ui| {
+                for b in buildings {
+                    ui.selectable_value(building, b.building.clone(), format!("{} ({})", b.name, b.building));
+                }
+            });
+    }
     
-    fn render_location_input(
-        ui: &mut egui::Ui,
-        building: &mut Building,
-        floor: &mut u8,
-        room: &mut u8,
-    ) {
+    // Searchable picker over the registered rooms. The search text is kept in egui's
+    // temporary memory under `id_source`, so every picker has its own.
+    fn render_location_input(ui: &mut egui::Ui, id_source: &str, rooms: &[Room], location: &mut Location) {
+        let query_id = egui::Id::new(id_source).with("query");
+        let mut query: String = ui.data_mut(|d| d.get_temp(query_id)).unwrap_or_default();
+        
         ui.horizontal(|ui| {
-            ui.label("Hús:");
-            egui::ComboBox::from_id_source("building_combo")
-                .selected_text(format!("{}", building))
+            ui.label("Stofa:");
+            let search = ui.add(
+                egui::TextEdit::singleline(&mut query)
+                    .hint_text("🔍 t.d. H-2, tölvuver eða kennari")
+                    .desired_width(200.0),
+            );
+            // Typing a whole room code picks it without opening the list
+            if search.changed() {
+                if let Some(room) = Location::try_from(query.to_uppercase()).ok().and_then(|loc| rooms.iter().find(|r| r.location == loc)) {
+                    *location = room.location.clone();
+                }
+            }
+            
+            let current = rooms.iter().find(|r| &r.location == location);
+            let selected = current.map(|r| r.to_string()).unwrap_or_else(|| format!("{} (óskráð)", location));
+            egui::ComboBox::from_id_source(id_source)
+                .width(300.0)
+                .selected_text(selected)
                 .show_ui(ui, |ui| {
-                    for b in Building::all() {
-                        ui.selectable_value(building, b, format!("{}", b));
+                    let mut any = false;
+                    for room in rooms.iter().filter(|r| r.matches_query(&query)) {
+                        any = true;
+                        ui.selectable_value(location, room.location.clone(), room.to_string());
+                    }
+                    if !any {
+                        ui.label("Engin stofa passar við leitina");
                     }
                 });
         });
         
-        ui.horizontal(|ui| {
-            ui.label("Hæð:");
-            ui.add(egui::Slider::new(floor, 0..=9).text("hæð"));
-        });
-        
-        ui.horizontal(|ui| {
-            ui.label("Herbergi:");
-            ui.add(egui::Slider::new(room, 0..=99).text("herbergi"));
-        });
-        
-        ui.label(format!(
-            "Staðsetning: {}-{}{}",
-            building.to_code(),
-            floor,
-            room
-        ));
+        match rooms.iter().find(|r| &r.location == location) {
+            Some(room) => {
+                let mut details = vec![format!("Staðsetning: {}", room.location), room.room_type.to_string()];
+                if room.capacity > 0 {
+                    details.push(format!("{} sæti", room.capacity));
+                }
+                if !room.responsible.is_empty() {
+                    details.push(format!("Ábyrgð: {}", room.responsible));
+                }
+                ui.label(details.join(" · "));
+            }
+            None => {
+                ui.colored_label(
+                    egui::Color32::from_rgb(211, 47, 47),
+                    format!("{} er ekki skráð stofa. Skráðu hana undir \"🏢 Hús og stofur\"", location),
+                );
+            }
+        }
+        ui.data_mut(|d| d.insert_temp(query_id, query));
     }
     
-    fn register_section(&mut self, ui: &mut egui::Ui) {
-        ui.heading("📝 Skrá nýjan búnað");
-        ui.separator();
-        
+    // Type, location, value and type-specific fields; used by both Register and Edit
+    fn render_equipment_form(ui: &mut egui::Ui, id_source: &str, rooms: &[Room], categories: &[Category], form: &mut EquipmentForm) {
         // Custom black-dot radios (button only) with extra spacing; label stays default color
-        ui.horizontal(|ui| {
+        ui.horizontal_wrapped(|ui| {
             ui.label("Tegund búnaðar:");
-            Self::radio_black_value(ui, &mut self.reg_equipment_type, EquipmentType::Table, "■ Borð");
+            Self::radio_black_value(ui, &mut form.equipment_type, EquipmentType::Table, "■ Borð");
+            ui.add_space(12.0);
+            Self::radio_black_value(ui, &mut form.equipment_type, EquipmentType::Chair, "💺 Stóll");
             ui.add_space(12.0);
-            Self::radio_black_value(ui, &mut self.reg_equipment_type, EquipmentType::Chair, "💺 Stóll");
+            Self::radio_black_value(ui, &mut form.equipment_type, EquipmentType::Projector, "📽 Skjávarpi");
             ui.add_space(12.0);
-            Self::radio_black_value(ui, &mut self.reg_equipment_type, EquipmentType::Projector, "📽 Skjávarpi");
+            Self::radio_black_value(ui, &mut form.equipment_type, EquipmentType::Computer, "💻 Tölva");
+            ui.add_space(12.0);
+            Self::radio_black_value(ui, &mut form.equipment_type, EquipmentType::Monitor, "🖥 Skjár");
+            for category in categories {
+                ui.add_space(12.0);
+                Self::radio_black_value(ui, &mut form.equipment_type, EquipmentType::Custom(category.code.clone()), &category.name);
+            }
         });
         
         ui.add_space(10.0);
         
-        Self::render_location_input(
-            ui,
-            &mut self.reg_building,
-            &mut self.reg_floor,
-            &mut self.reg_room,
-        );
+        Self::render_location_input(ui, id_source, rooms, &mut form.location);
         
         ui.add_space(10.0);
         
         ui.horizontal(|ui| {
             ui.label("Verðmæti (kr):");
-            ui.text_edit_singleline(&mut self.reg_value);
+            ui.text_edit_singleline(&mut form.value);
         });
         
-        match self.reg_equipment_type {
+        match &form.equipment_type {
             EquipmentType::Table => {
                 ui.horizontal(|ui| {
                     ui.label("Fjöldi sæta:");
-                    ui.add(egui::Slider::new(&mut self.reg_table_seats, 1..=20).text("sæti"));
+                    ui.add(egui::Slider::new(&mut form.table_seats, 1..=20).text("sæti"));
                 });
             }
             EquipmentType::Chair => {
                 ui.horizontal(|ui| {
                     ui.label("Tegund stóls:");
                     egui::ComboBox::from_label("")
-                        .selected_text(format!("{}", self.reg_chair_type))
+                        .selected_text(format!("{}", form.chair_type))
                         .show_ui(ui, |ui| {
                             for ct in ChairType::all() {
-                                ui.selectable_value(&mut self.reg_chair_type, ct, format!("{}", ct));
+                                ui.selectable_value(&mut form.chair_type, ct, format!("{}", ct));
                             }
                         });
                 });
@@ -300,10 +650,142 @@ impl EquipmentApp {
             EquipmentType::Projector => {
                 ui.horizontal(|ui| {
                     ui.label("Lumens:");
-                    ui.text_edit_singleline(&mut self.reg_projector_lumens);
+                    ui.text_edit_singleline(&mut form.projector_lumens);
+                });
+            }
+            EquipmentType::Computer => {
+                ui.horizontal(|ui| {
+                    ui.label("Tegund tölvu:");
+                    egui::ComboBox::from_id_source(egui::Id::new(id_source).with("computer_kind"))
+                        .selected_text(form.computer_kind.to_string())
+                        .show_ui(ui, |ui| {
+                            for kind in ComputerKind::all() {
+                                ui.selectable_value(&mut form.computer_kind, kind, kind.to_string());
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Örgjörvi:");
+                    ui.add(egui::TextEdit::singleline(&mut form.cpu).hint_text("t.d. Intel Core i5-1335U"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Vinnsluminni (GB):");
+                    ui.add(egui::TextEdit::singleline(&mut form.ram_gb).desired_width(60.0));
+                });
+            }
+            EquipmentType::Monitor => {
+                ui.horizontal(|ui| {
+                    ui.label("Skjástærð (tommur):");
+                    ui.add(egui::TextEdit::singleline(&mut form.screen_size).hint_text("t.d. 27").desired_width(60.0));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Upplausn:");
+                    ui.add(egui::TextEdit::singleline(&mut form.resolution).hint_text("t.d. 1920x1080").desired_width(120.0));
                 });
             }
+            EquipmentType::Custom(code) => match categories.iter().find(|c| c.code == *code) {
+                Some(category) => {
+                    for def in &category.attributes {
+                        let text = form.attributes.entry(def.key.clone()).or_default();
+                        Self::attribute_input(ui, id_source, def, text);
+                    }
+                }
+                None => {
+                    ui.colored_label(egui::Color32::from_rgb(211, 47, 47), format!("Flokkurinn {} er ekki lengur til", code));
+                }
+            },
         }
+
+        ui.add_space(10.0);
+        Self::info_inputs(ui, id_source, form);
+    }
+    
+    // Asset tag, serial number, manufacturer, model, purchase, condition and notes, shown for every type
+    fn info_inputs(ui: &mut egui::Ui, id_source: &str, form: &mut EquipmentForm) {
+        let serial_required = matches!(form.equipment_type, EquipmentType::Computer | EquipmentType::Monitor);
+        let info = &mut form.info;
+        ui.horizontal(|ui| {
+            ui.label("Eignanúmer:");
+            ui.add(egui::TextEdit::singleline(&mut info.asset_tag).hint_text("t.d. TS-004211"));
+        });
+        ui.horizontal(|ui| {
+            ui.label(if serial_required { "Raðnúmer *:" } else { "Raðnúmer:" });
+            ui.text_edit_singleline(&mut info.serial_number);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Framleiðandi:");
+            ui.add(egui::TextEdit::singleline(&mut info.manufacturer).hint_text("t.d. Dell"));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Gerð:");
+            ui.add(egui::TextEdit::singleline(&mut info.model).hint_text("t.d. Latitude 5440"));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Innkaupsdagur:");
+            ui.add(egui::TextEdit::singleline(&mut form.purchase_date).hint_text("t.d. 15.08.2024").desired_width(100.0));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Birgir:");
+            ui.add(egui::TextEdit::singleline(&mut info.supplier).hint_text("t.d. Advania"));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Reikningsnúmer:");
+            ui.text_edit_singleline(&mut info.invoice_number);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Ástand:");
+            egui::ComboBox::from_id_source(egui::Id::new(id_source).with("condition"))
+                .selected_text(info.condition.to_string())
+                .show_ui(ui, |ui| {
+                    for condition in Condition::all() {
+                        ui.selectable_value(&mut info.condition, condition, condition.to_string());
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Athugasemdir:");
+            ui.add(egui::TextEdit::multiline(&mut info.notes).hint_text("t.d. vinstri fótur laus").desired_rows(2));
+        });
+    }
+
+    // One attribute of a category item, with the widget its kind calls for
+    fn attribute_input(ui: &mut egui::Ui, id_source: &str, def: &AttributeDef, text: &mut String) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}{}:", def.label, if def.required { " *" } else { "" }));
+            match def.kind {
+                AttributeKind::Number | AttributeKind::Text => {
+                    let mut hint = def.range_text();
+                    if !def.unit.is_empty() {
+                        hint = format!("{} {}", hint, def.unit).trim().to_string();
+                    }
+                    ui.add(egui::TextEdit::singleline(text).hint_text(hint).desired_width(200.0));
+                }
+                AttributeKind::Choice => {
+                    egui::ComboBox::from_id_source(egui::Id::new(id_source).with(&def.key))
+                        .selected_text(text.as_str())
+                        .show_ui(ui, |ui| {
+                            if !def.required {
+                                ui.selectable_value(text, String::new(), "—");
+                            }
+                            for choice in &def.choices {
+                                ui.selectable_value(text, choice.clone(), choice);
+                            }
+                        });
+                }
+                AttributeKind::YesNo => {
+                    let mut yes = text.as_str() == "Já";
+                    ui.checkbox(&mut yes, "");
+                    *text = if yes { "Já" } else { "Nei" }.to_string();
+                }
+            }
+        });
+    }
+
+    fn register_section(&mut self, ui: &mut egui::Ui) {
+        ui.heading("📝 Skrá nýjan búnað");
+        ui.separator();
+        
+        Self::render_equipment_form(ui, "reg_room", &self.rooms, &self.categories, &mut self.reg_form);
         
         ui.add_space(20.0);
         
@@ -316,50 +798,25 @@ impl EquipmentApp {
         self.error_message.clear();
         self.message.clear();
         
-        let value = match self.reg_value.parse::<u32>() {
-            Ok(v) => v,
-            Err(_) => {
-                self.error_message = "Verðmæti verður að vera tala".to_string();
-                return;
-            }
-        };
-        
-        let location = match Location::try_from((self.reg_building, self.reg_floor, self.reg_room)) {
-            Ok(loc) => loc,
+        let equipment = match self.reg_form.to_equipment(&self.categories) {
+            Ok(eq) => eq,
             Err(e) => { self.error_message = e; return; }
         };
         
-        let equipment = match self.reg_equipment_type {
-            EquipmentType::Table => {
-                match Table::try_from((location, value, self.reg_table_seats)) {
-                    Ok(t) => Equipment::Table(t),
-                    Err(e) => { self.error_message = e; return; }
-                }
-            }
-            EquipmentType::Chair => {
-                match Chair::try_from((location, value, self.reg_chair_type)) {
-                    Ok(c) => Equipment::Chair(c),
-                    Err(e) => { self.error_message = e; return; }
-                }
-            }
-            EquipmentType::Projector => {
-                let lumens = match self.reg_projector_lumens.parse::<u32>() {
-                    Ok(l) => l,
-                    Err(_) => { self.error_message = "Lumens verður að vera tala".to_string(); return; }
-                };
-                match Projector::try_from((location, value, lumens)) {
-                    Ok(p) => Equipment::Projector(p),
-                    Err(e) => { self.error_message = e; return; }
-                }
-            }
-        };
-        
         let db = self.db.lock().unwrap();
         match db.insert_equipment(&equipment) {
             Ok(id) => {
                 self.message = format!(" Búnaður skráður með ID: {}", id);
-                self.reg_value.clear();
-                self.reg_projector_lumens.clear();
+                let mut equipment = equipment;
+                equipment.set_id(id);
+                self.undo_stack.record(Change::Registered { id, equipment: Box::new(equipment) });
+                self.reg_form.value.clear();
+                self.reg_form.projector_lumens.clear();
+                // Keep manufacturer, model, purchase and specs for the next one of a batch; the rest is the item's own
+                self.reg_form.info.asset_tag.clear();
+                self.reg_form.info.serial_number.clear();
+                self.reg_form.info.notes.clear();
+                self.reg_form.attributes.clear();
             }
             Err(e) => {
                 self.error_message = format!(" Villa við skráningu: {}", e);
@@ -434,23 +891,23 @@ impl EquipmentApp {
                         ui.end_row();
                         
                         ui.label("Staðsetning:");
-                        let location = match equipment {
-                            Equipment::Table(t) => &t.location,
-                            Equipment::Chair(c) => &c.location,
-                            Equipment::Projector(p) => &p.location,
-                        };
-                        ui.label(format!("{}", location));
+                        ui.label(format!("{}", equipment.get_location()));
                         ui.end_row();
                         
                         ui.label("Verðmæti:");
-                        let value = match equipment {
-                            Equipment::Table(t) => t.value,
-                            Equipment::Chair(c) => c.value,
-                            Equipment::Projector(p) => p.value,
-                        };
-                        ui.label(format!("{} kr.", value));
+                        ui.label(format!("{} kr.", equipment.get_value()));
+                        ui.end_row();
+                        
+                        ui.label("Bókfært verð í dag:");
+                        ui.label(format!("{} kr.", self.depreciation.book_value(equipment, chrono::Local::now().date_naive())));
                         ui.end_row();
                         
+                        for (label, value) in equipment.details().into_iter().chain(equipment.info().details()) {
+                            ui.label(format!("{}:", label));
+                            ui.label(value);
+                            ui.end_row();
+                        }
+                        
                         ui.label("Lýsing:");
                         ui.label(format!("{}", equipment));
                         ui.end_row();
@@ -461,42 +918,82 @@ impl EquipmentApp {
             ui.separator();
             ui.add_space(15.0);
             
-            // Update location section
-            ui.heading("🔄 Uppfæra staðsetningu");
+            // Edit all fields (moving is just changing the location)
+            ui.heading("🔄 Uppfæra búnað");
             ui.add_space(10.0);
             
-            Self::render_location_input(
-                ui,
-                &mut self.edit_building,
-                &mut self.edit_floor,
-                &mut self.edit_room,
-            );
+            Self::render_equipment_form(ui, "edit_room", &self.rooms, &self.categories, &mut self.edit_form);
             
             ui.add_space(15.0);
             
-            if ui.button("✅ Uppfæra staðsetningu").clicked() {
-                self.update_location();
+            if ui.button("✅ Vista breytingar").clicked() {
+                self.save_edit();
             }
             
             ui.add_space(20.0);
             ui.separator();
             ui.add_space(15.0);
             
-            // Delete section
-            ui.heading("🗑 Eyða búnaði"); // works in most mono / text fonts
+            // Retire section (soft delete into the recycle bin)
+            ui.heading("🗑 Afskrá búnað"); // works in most mono / text fonts
             ui.add_space(10.0);
             
-            ui.label("⚠ Varúð: Þessi aðgerð er óafturkræf!");
+            ui.label("Búnaðurinn fer í ruslafötuna og hægt er að endurheimta hann þaðan.");
             ui.add_space(10.0);
             
-            if ui.button("❌ Eyða búnaði").clicked() {
-                self.delete_equipment();
-            }
+            ui.horizontal(|ui| {
+                ui.label("Ástæða:");
+                egui::ComboBox::from_id_source("retire_reason_combo")
+                    .selected_text(format!("{}", self.edit_retire_reason))
+                    .show_ui(ui, |ui| {
+                        for r in RetireReason::all() {
+                            ui.selectable_value(&mut self.edit_retire_reason, r, format!("{}", r));
+                        }
+                    });
+                if ui.button("❌ Afskrá búnað").clicked() {
+                    self.delete_equipment();
+                }
+            });
+            
+            ui.add_space(20.0);
+            ui.separator();
+            Self::render_history(ui, &self.edit_history);
+        } else if !self.edit_history.is_empty() {
+            // Retired or deleted item: the timeline shows where it was last
+            ui.label("Þessi búnaður er ekki í notkun (sjá ruslafötu). Saga hans:");
+            Self::render_history(ui, &self.edit_history);
         } else if !self.edit_id.is_empty() {
             ui.label("Sláðu inn ID og smelltu á 'Sækja' til að skoða búnað");
         }
     }
     
+    fn render_history(ui: &mut egui::Ui, history: &[HistoryEntry]) {
+        ui.heading("📜 Saga");
+        ui.add_space(5.0);
+        if history.is_empty() {
+            ui.label("Engin saga skráð");
+            return;
+        }
+        egui::Grid::new("edit_history_grid")
+            .num_columns(4)
+            .spacing([20.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("Tími");
+                ui.strong("Aðgerð");
+                ui.strong("Notandi");
+                ui.strong("Nánar");
+                ui.end_row();
+                for entry in history.iter().rev() {
+                    ui.label(&entry.timestamp);
+                    ui.label(entry.action.to_string());
+                    ui.label(&entry.user);
+                    ui.label(&entry.details);
+                    ui.end_row();
+                }
+            });
+    }
+    
     fn fetch_equipment_for_edit(&mut self) {
         self.error_message.clear();
         self.message.clear();
@@ -510,19 +1007,23 @@ impl EquipmentApp {
         };
         
         let db = self.db.lock().unwrap();
+        self.edit_history = match db.get_history(id) {
+            Ok(history) => history,
+            Err(e) => {
+                // The item can still be edited; an empty timeline would just look like nothing ever happened
+                self.error_message = format!(" Villa við að sækja breytingasögu: {}", e);
+                Vec::new()
+            }
+        };
         match db.get_equipment_by_id(id) {
             Ok(Some(equipment)) => {
-                // Set the location fields to current location
-                let location = match &equipment {
-                    Equipment::Table(t) => &t.location,
-                    Equipment::Chair(c) => &c.location,
-                    Equipment::Projector(p) => &p.location,
-                };
-                self.edit_building = location.building;
-                self.edit_floor = location.floor;
-                self.edit_room = location.room;
+                // Fill the form with the current values
+                self.edit_form = EquipmentForm::from_equipment(&equipment);
                 self.edit_equipment = Some(equipment);
             }
+            Ok(None) if !self.edit_history.is_empty() => {
+                self.edit_equipment = None;
+            }
             Ok(None) => {
                 self.error_message = format!(" Búnaður með ID {} fannst ekki", id);
                 self.edit_equipment = None;
@@ -534,7 +1035,7 @@ impl EquipmentApp {
         }
     }
     
-    fn update_location(&mut self) {
+    fn save_edit(&mut self) {
         self.error_message.clear();
         self.message.clear();
         
@@ -546,15 +1047,42 @@ impl EquipmentApp {
             }
         };
         
-        let location = match Location::try_from((self.edit_building, self.edit_floor, self.edit_room)) {
-            Ok(loc) => loc,
+        let mut equipment = match self.edit_form.to_equipment(&self.categories) {
+            Ok(eq) => eq,
             Err(e) => { self.error_message = e; return; }
         };
+        equipment.set_id(id);
+        
+        // A pure move goes through update_location; anything else rewrites the whole item
+        let (location_changed, other_changed) = match &self.edit_equipment {
+            Some(old) => {
+                let mut moved = old.clone();
+                moved.set_location(equipment.get_location().clone());
+                (old.get_location() != equipment.get_location(), moved != equipment)
+            }
+            None => (true, true),
+        };
+        if !location_changed && !other_changed {
+            self.message = " Engar breytingar til að vista".to_string();
+            return;
+        }
         
         let db = self.db.lock().unwrap();
-        match db.update_location(id, &location) {
+        let result = if other_changed {
+            db.update_equipment(id, &equipment)
+        } else {
+            db.update_location(id, equipment.get_location())
+        };
+        match result {
             Ok(_) => {
-                self.message = format!(" Staðsetning uppfærð fyrir búnað með ID: {}", id);
+                if let Some(before) = self.edit_equipment.clone() {
+                    self.undo_stack.record(Change::Edited { id, before: Box::new(before), after: Box::new(equipment) });
+                }
+                self.message = if other_changed {
+                    format!(" Búnaður með ID {} uppfærður", id)
+                } else {
+                    format!(" Staðsetning uppfærð fyrir búnað með ID: {}", id)
+                };
                 // Refresh the equipment info
                 drop(db);
                 self.fetch_equipment_for_edit();
@@ -578,43 +1106,1059 @@ impl EquipmentApp {
         };
         
         let db = self.db.lock().unwrap();
-        match db.delete_equipment(id) {
+        match db.retire_equipment(id, self.edit_retire_reason) {
             Ok(_) => {
-                self.message = format!(" Búnaði með ID {} eytt", id);
+                self.message = format!(" Búnaður með ID {} færður í ruslafötu ({})", id, self.edit_retire_reason);
+                self.undo_stack.record(Change::Retired { id, reason: self.edit_retire_reason });
                 self.edit_id.clear();
                 self.edit_equipment = None;
+                self.edit_history.clear();
             }
             Err(e) => {
-                self.error_message = format!(" Villa við eyðingu: {}", e);
+                self.error_message = format!(" Villa við afskráningu: {}", e);
             }
         }
     }
     
-    fn search_section(&mut self, ui: &mut egui::Ui) {
-        ui.heading("🔍 Leita að búnaði");
+    fn buildings_section(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().id_source("buildings_page").show(ui, |ui| {
+            self.buildings_list(ui);
+            ui.add_space(16.0);
+            self.rooms_list(ui);
+            ui.add_space(16.0);
+            self.seating_list(ui);
+        });
+    }
+    
+    fn buildings_list(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🏢 Hús");
         ui.separator();
-
-        // Always refresh search like the sidebar does
-        self.perform_search();
-
-        // Live search input
-        ui.horizontal(|ui| {
-            ui.label("Leit:");
-            let changed = ui.text_edit_singleline(&mut self.search_query).changed();
-            if changed {
-                self.perform_search();
-            }
+        ui.label("Hús og leigð rými sem búnaður getur verið skráður í. Kóðinn er fremst í staðsetningu, t.d. H í H-2.02.");
+        ui.add_space(10.0);
+        
+        let data = self.buildings.clone();
+        let mut save = None;
+        let mut delete = None;
+        let mut cancel = false;
+        ui.push_id("buildings_table", |ui| {
+            use egui_extras::{TableBuilder, Column};
+            TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .vscroll(false)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .column(Column::initial(70.0)) // Kóði
+                .column(Column::initial(200.0)) // Nafn
+                .column(Column::initial(260.0)) // Heimilisfang
+                .column(Column::remainder()) // Aðgerðir
+                .header(22.0, |mut header| {
+                    for h in ["Kóði", "Nafn", "Heimilisfang", "Aðgerðir"] {
+                        header.col(|ui| { ui.strong(h); });
+                    }
+                })
+                .body(|mut body| {
+                    for info in &data {
+                        body.row(26.0, |mut row| {
+                            row.col(|ui| { ui.label(info.building.to_code()); });
+                            let editing = self.edit_building.as_ref().is_some_and(|e| e.building == info.building);
+                            match &mut self.edit_building {
+                                Some(edited) if editing => {
+                                    row.col(|ui| { ui.text_edit_singleline(&mut edited.name); });
+                                    row.col(|ui| { ui.text_edit_singleline(&mut edited.address); });
+                                    row.col(|ui| {
+                                        if ui.button("💾 Vista").clicked() {
+                                            save = Some(edited.clone());
+                                        }
+                                        if ui.button("Hætta við").clicked() {
+                                            cancel = true;
+                                        }
+                                    });
+                                }
+                                _ => {
+                                    row.col(|ui| { ui.label(&info.name); });
+                                    row.col(|ui| { ui.label(&info.address); });
+                                    row.col(|ui| {
+                                        if ui.button("✏ Breyta").clicked() {
+                                            self.edit_building = Some(info.clone());
+                                            self.building_confirm_delete = None;
+                                        }
+                                        let confirming = self.building_confirm_delete.as_ref() == Some(&info.building);
+                                        let label = if confirming { "⚠ Staðfesta eyðingu" } else { "❌ Eyða" };
+                                        if ui.button(label).clicked() {
+                                            if confirming {
+                                                delete = Some(info.building.clone());
+                                            } else {
+                                                self.building_confirm_delete = Some(info.building.clone());
+                                            }
+                                        }
+                                    });
+                                }
+                            }
+                        });
+                    }
+                });
         });
+        
         ui.add_space(10.0);
-
-        // Ensure search is initialized (first time entering without typing)
-        if !self.search_initialized {
-            self.perform_search();
-            self.search_initialized = true;
+        ui.separator();
+        ui.label(egui::RichText::new("Nýtt hús").strong());
+        egui::Grid::new("new_building").num_columns(2).show(ui, |ui| {
+            ui.label("Kóði:");
+            ui.add(egui::TextEdit::singleline(&mut self.new_building_code).hint_text("t.d. EK").desired_width(80.0));
+            ui.end_row();
+            ui.label("Nafn:");
+            ui.text_edit_singleline(&mut self.new_building_name);
+            ui.end_row();
+            ui.label("Heimilisfang:");
+            ui.text_edit_singleline(&mut self.new_building_address);
+            ui.end_row();
+        });
+        if ui.button("➕ Bæta við húsi").clicked() {
+            self.add_building();
         }
-
-        // If exactly one result, auto-select it
-        if self.search_results.len() == 1 {
+        
+        if cancel {
+            self.edit_building = None;
+        }
+        if let Some(info) = save {
+            self.error_message.clear();
+            self.message.clear();
+            let result = self.db.lock().unwrap().update_building(&info);
+            match result {
+                Ok(_) => {
+                    self.message = format!("✅ Upplýsingar um {} vistaðar", info.name);
+                    self.edit_building = None;
+                }
+                Err(e) => self.error_message = format!(" Gat ekki vistað hús: {}", e),
+            }
+            self.reload_buildings();
+        }
+        if let Some(building) = delete {
+            self.error_message.clear();
+            self.message.clear();
+            self.building_confirm_delete = None;
+            let result = self.db.lock().unwrap().delete_building(&building);
+            match result {
+                Ok(_) => self.message = format!("✅ Húsi {} eytt", building),
+                Err(e) => self.error_message = format!(" {}", e),
+            }
+            self.reload_buildings();
+            self.reload_rooms();
+        }
+    }
+    
+    fn room_type_combo(ui: &mut egui::Ui, id_source: impl std::hash::Hash, room_type: &mut RoomType) {
+        egui::ComboBox::from_id_source(id_source)
+            .selected_text(room_type.to_string())
+            .show_ui(ui, |ui| {
+                for t in RoomType::all() {
+                    ui.selectable_value(room_type, t, t.to_string());
+                }
+            });
+    }
+    
+    // Room registry: only rooms listed here can be picked in the location inputs
+    fn rooms_list(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🚪 Stofur");
+        ui.separator();
+        ui.label("Búnaður verður aðeins skráður í stofur sem eru á þessum lista.");
+        ui.horizontal(|ui| {
+            ui.label("Leita:");
+            ui.add(egui::TextEdit::singleline(&mut self.room_filter).hint_text("stofa, nafn, tegund eða kennari"));
+        });
+        ui.add_space(6.0);
+        
+        let data: Vec<Room> = self.rooms.iter().filter(|r| r.matches_query(&self.room_filter)).cloned().collect();
+        let mut save = None;
+        let mut delete = None;
+        let mut cancel = false;
+        ui.push_id("rooms_table", |ui| {
+            use egui_extras::{TableBuilder, Column};
+            TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .vscroll(false)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .column(Column::initial(70.0)) // Stofa
+                .column(Column::initial(170.0)) // Nafn
+                .column(Column::initial(150.0)) // Tegund
+                .column(Column::initial(60.0)) // Sæti
+                .column(Column::initial(150.0)) // Ábyrgð
+                .column(Column::remainder()) // Aðgerðir
+                .header(22.0, |mut header| {
+                    for h in ["Stofa", "Nafn", "Tegund", "Sæti", "Ábyrgð", "Aðgerðir"] {
+                        header.col(|ui| { ui.strong(h); });
+                    }
+                })
+                .body(|mut body| {
+                    for room in &data {
+                        body.row(26.0, |mut row| {
+                            row.col(|ui| { ui.label(room.location.to_string()); });
+                            let editing = self.edit_room.as_ref().is_some_and(|r| r.location == room.location);
+                            match &mut self.edit_room {
+                                Some(edited) if editing => {
+                                    row.col(|ui| { ui.text_edit_singleline(&mut edited.name); });
+                                    row.col(|ui| { Self::room_type_combo(ui, "edit_room_type", &mut edited.room_type); });
+                                    row.col(|ui| { ui.add(egui::DragValue::new(&mut edited.capacity).range(0..=1000)); });
+                                    row.col(|ui| { ui.text_edit_singleline(&mut edited.responsible); });
+                                    row.col(|ui| {
+                                        if ui.button("💾 Vista").clicked() {
+                                            save = Some(edited.clone());
+                                        }
+                                        if ui.button("Hætta við").clicked() {
+                                            cancel = true;
+                                        }
+                                    });
+                                }
+                                _ => {
+                                    row.col(|ui| { ui.label(&room.name); });
+                                    row.col(|ui| { ui.label(room.room_type.to_string()); });
+                                    row.col(|ui| { ui.label(room.capacity.to_string()); });
+                                    row.col(|ui| { ui.label(&room.responsible); });
+                                    row.col(|ui| {
+                                        if ui.button("✏ Breyta").clicked() {
+                                            self.edit_room = Some(room.clone());
+                                            self.room_confirm_delete = None;
+                                        }
+                                        let confirming = self.room_confirm_delete.as_ref() == Some(&room.location);
+                                        let label = if confirming { "⚠ Staðfesta eyðingu" } else { "❌ Eyða" };
+                                        if ui.button(label).clicked() {
+                                            if confirming {
+                                                delete = Some(room.location.clone());
+                                            } else {
+                                                self.room_confirm_delete = Some(room.location.clone());
+                                            }
+                                        }
+                                    });
+                                }
+                            }
+                        });
+                    }
+                });
+        });
+        
+        ui.add_space(10.0);
+        ui.separator();
+        ui.label(egui::RichText::new("Ný stofa").strong());
+        egui::Grid::new("new_room").num_columns(2).show(ui, |ui| {
+            ui.label("Hús:");
+            Self::building_combo(ui, "new_room_building", &mut self.new_room.location.building, &self.buildings);
+            ui.end_row();
+            ui.label("Hæð og herbergi:");
+            ui.horizontal(|ui| {
+                Self::floor_input(ui, &mut self.new_room.location.floor);
+                ui.add(egui::DragValue::new(&mut self.new_room.location.room).range(0..=location::MAX_ROOM).prefix("herbergi "));
+                // Optional letter, as in H-2.02a
+                let mut suffix = self.new_room.location.suffix.map(String::from).unwrap_or_default();
+                if ui.add(egui::TextEdit::singleline(&mut suffix).desired_width(20.0).hint_text("a")).changed() {
+                    self.new_room.location.suffix =
+                        suffix.chars().rev().find(|c| c.is_ascii_alphabetic()).map(|c| c.to_ascii_lowercase());
+                }
+                ui.label(format!("= {}", self.new_room.location));
+            });
+            ui.end_row();
+            ui.label("Nafn:");
+            ui.add(egui::TextEdit::singleline(&mut self.new_room.name).hint_text("t.d. Tölvuver 3"));
+            ui.end_row();
+            ui.label("Tegund:");
+            Self::room_type_combo(ui, "new_room_type", &mut self.new_room.room_type);
+            ui.end_row();
+            ui.label("Sæti:");
+            ui.add(egui::DragValue::new(&mut self.new_room.capacity).range(0..=1000));
+            ui.end_row();
+            ui.label("Ábyrgðarmaður:");
+            ui.text_edit_singleline(&mut self.new_room.responsible);
+            ui.end_row();
+        });
+        if ui.button("➕ Skrá stofu").clicked() {
+            self.error_message.clear();
+            self.message.clear();
+            let result = self.db.lock().unwrap().add_room(&self.new_room);
+            match result {
+                Ok(_) => {
+                    self.message = format!("✅ Stofa {} skráð", self.new_room.location);
+                    self.new_room.name.clear();
+                    self.new_room.responsible.clear();
+                }
+                Err(e) => self.error_message = format!(" Gat ekki skráð stofu: {}", e),
+            }
+            self.reload_rooms();
+        }
+        
+        if cancel {
+            self.edit_room = None;
+        }
+        if let Some(room) = save {
+            self.error_message.clear();
+            self.message.clear();
+            let result = self.db.lock().unwrap().update_room(&room);
+            match result {
+                Ok(_) => {
+                    self.message = format!("✅ Stofa {} vistuð", room.location);
+                    self.edit_room = None;
+                }
+                Err(e) => self.error_message = format!(" Gat ekki vistað stofu: {}", e),
+            }
+            self.reload_rooms();
+        }
+        if let Some(location) = delete {
+            self.error_message.clear();
+            self.message.clear();
+            self.room_confirm_delete = None;
+            let result = self.db.lock().unwrap().delete_room(&location);
+            match result {
+                Ok(_) => self.message = format!("✅ Stofu {} eytt", location),
+                Err(e) => self.error_message = format!(" {}", e),
+            }
+            self.reload_rooms();
+        }
+    }
+    
+    // Seats at tables against chairs, per room, with chairs that could be moved
+    fn seating_list(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🪑 Sæti og stólar");
+        ui.separator();
+        ui.label("Stólar í hverri stofu bornir saman við sætafjölda stofunnar, eða sæti við borð ef hann er ekki skráður.");
+        ui.checkbox(&mut self.seating_problems_only, "Sýna aðeins stofur sem þarf að laga");
+        let items = match self.db.lock().unwrap().get_all_equipment() {
+            Ok(items) => items,
+            Err(e) => {
+                // A report with no chairs anywhere would be wrong, not empty
+                self.error_message = format!(" Villa við lestur búnaðar: {}", e);
+                return;
+            }
+        };
+        let report = seating::seating_report(&items, &self.rooms);
+        let moves = seating::suggest_moves(&report);
+        let rows: Vec<&RoomSeating> = report.iter().filter(|r| !self.seating_problems_only || !r.is_ok()).collect();
+        
+        ui.push_id("seating_table", |ui| {
+            use egui_extras::{TableBuilder, Column};
+            TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .vscroll(false)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .column(Column::initial(80.0)) // Stofa
+                .column(Column::initial(160.0)) // Nafn
+                .column(Column::initial(70.0)) // Rúmar
+                .column(Column::initial(80.0)) // Borðsæti
+                .column(Column::initial(70.0)) // Stólar
+                .column(Column::remainder()) // Staða
+                .header(22.0, |mut header| {
+                    for h in ["Stofa", "Nafn", "Rúmar", "Borðsæti", "Stólar", "Staða"] {
+                        header.col(|ui| { ui.strong(h); });
+                    }
+                })
+                .body(|mut body| {
+                    for room in rows {
+                        body.row(22.0, |mut row| {
+                            row.col(|ui| { ui.label(room.location.to_string()); });
+                            row.col(|ui| { ui.label(&room.name); });
+                            row.col(|ui| { ui.label(if room.capacity > 0 { room.capacity.to_string() } else { "–".to_string() }); });
+                            row.col(|ui| { ui.label(room.seats.to_string()); });
+                            row.col(|ui| { ui.label(room.chairs.to_string()); });
+                            row.col(|ui| {
+                                let color = if room.missing_chairs() > 0 || room.missing_table_seats() > 0 {
+                                    egui::Color32::from_rgb(211, 47, 47)
+                                } else if room.surplus_chairs() > 0 {
+                                    egui::Color32::from_rgb(230, 126, 34)
+                                } else {
+                                    egui::Color32::from_rgb(46, 125, 50)
+                                };
+                                ui.label(egui::RichText::new(room.status()).color(color));
+                            });
+                        });
+                    }
+                });
+        });
+        
+        if !moves.is_empty() {
+            ui.add_space(8.0);
+            ui.label(egui::RichText::new("Tillögur að flutningi stóla").strong());
+            for m in &moves {
+                ui.label(format!("• {}", m));
+            }
+        }
+    }
+    
+    fn reload_rooms(&mut self) {
+        match self.db.lock().unwrap().get_rooms() {
+            Ok(rooms) => self.rooms = rooms,
+            Err(e) => self.error_message = format!(" Villa við að sækja stofur: {}", e),
+        }
+    }
+    
+    fn add_building(&mut self) {
+        self.error_message.clear();
+        self.message.clear();
+        let name = self.new_building_name.trim().to_string();
+        if name.is_empty() {
+            self.error_message = " Nafn hússins vantar".to_string();
+            return;
+        }
+        let result = Building::new(&self.new_building_code).and_then(|building| {
+            let info = BuildingInfo { building, name, address: self.new_building_address.trim().to_string() };
+            self.db.lock().unwrap().add_building(&info)?;
+            Ok(info)
+        });
+        match result {
+            Ok(info) => {
+                self.message = format!("✅ {} ({}) bætt við", info.name, info.building);
+                self.new_building_code.clear();
+                self.new_building_name.clear();
+                self.new_building_address.clear();
+                self.reload_buildings();
+            }
+            Err(e) => self.error_message = format!(" Gat ekki bætt við húsi: {}", e),
+        }
+    }
+    
+    fn reload_buildings(&mut self) {
+        match self.db.lock().unwrap().get_buildings() {
+            Ok(buildings) => self.buildings = buildings,
+            Err(e) => self.error_message = format!(" Villa við að sækja hús: {}", e),
+        }
+    }
+    
+    // Equipment categories beyond tables, chairs and projectors, and the attributes each one has
+    fn categories_section(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().id_source("categories_page").show(ui, |ui| {
+            ui.heading("🗂 Flokkar búnaðar");
+            ui.separator();
+            ui.label("Flokkar fyrir annan búnað en borð, stóla og skjávarpa, t.d. töflur eða hátalara. Eiginleikar flokks birtast í skráningarforminu.");
+            ui.add_space(10.0);
+            
+            let data = self.categories.clone();
+            let mut delete = None;
+            ui.push_id("categories_table", |ui| {
+                use egui_extras::{TableBuilder, Column};
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .resizable(true)
+                    .vscroll(false)
+                    .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                    .column(Column::initial(120.0)) // Kóði
+                    .column(Column::initial(160.0)) // Heiti
+                    .column(Column::initial(320.0)) // Eiginleikar
+                    .column(Column::remainder()) // Aðgerðir
+                    .header(22.0, |mut header| {
+                        for h in ["Kóði", "Heiti", "Eiginleikar", "Aðgerðir"] {
+                            header.col(|ui| { ui.strong(h); });
+                        }
+                    })
+                    .body(|mut body| {
+                        for category in &data {
+                            body.row(26.0, |mut row| {
+                                row.col(|ui| { ui.label(&category.code); });
+                                row.col(|ui| { ui.label(&category.name); });
+                                row.col(|ui| {
+                                    let labels: Vec<&str> = category.attributes.iter().map(|a| a.label.as_str()).collect();
+                                    ui.label(labels.join(", "));
+                                });
+                                row.col(|ui| {
+                                    if ui.button("✏ Breyta").clicked() {
+                                        self.edit_category = Some(category.clone());
+                                        self.category_confirm_delete = None;
+                                    }
+                                    let confirming = self.category_confirm_delete.as_ref() == Some(&category.code);
+                                    let label = if confirming { "⚠ Staðfesta eyðingu" } else { "❌ Eyða" };
+                                    if ui.button(label).clicked() {
+                                        if confirming {
+                                            delete = Some(category.code.clone());
+                                        } else {
+                                            self.category_confirm_delete = Some(category.code.clone());
+                                        }
+                                    }
+                                });
+                            });
+                        }
+                    });
+            });
+            
+            ui.add_space(10.0);
+            ui.separator();
+            ui.label(egui::RichText::new("Nýr flokkur").strong());
+            egui::Grid::new("new_category").num_columns(2).show(ui, |ui| {
+                ui.label("Kóði:");
+                ui.add(egui::TextEdit::singleline(&mut self.new_category_code).hint_text("t.d. Tafla").desired_width(120.0));
+                ui.end_row();
+                ui.label("Heiti:");
+                ui.text_edit_singleline(&mut self.new_category_name);
+                ui.end_row();
+            });
+            if ui.button("➕ Bæta við flokki").clicked() {
+                self.error_message.clear();
+                self.message.clear();
+                let category = Category::new(&self.new_category_code, &self.new_category_name);
+                let result = self.db.lock().unwrap().add_category(&category);
+                match result {
+                    Ok(_) => {
+                        self.message = format!("✅ Flokknum {} bætt við. Bættu við eiginleikum hans með ✏ Breyta", category.name);
+                        self.new_category_code.clear();
+                        self.new_category_name.clear();
+                        self.edit_category = Some(category);
+                    }
+                    Err(e) => self.error_message = format!(" Gat ekki bætt við flokki: {}", e),
+                }
+                self.reload_categories();
+            }
+            
+            if let Some(code) = delete {
+                self.error_message.clear();
+                self.message.clear();
+                self.category_confirm_delete = None;
+                let result = self.db.lock().unwrap().delete_category(&code);
+                match result {
+                    Ok(_) => self.message = format!("✅ Flokki {} eytt", code),
+                    Err(e) => self.error_message = format!(" {}", e),
+                }
+                self.reload_categories();
+            }
+            
+            ui.add_space(16.0);
+            self.category_editor(ui);
+        });
+    }
+    
+    // Name and attribute definitions of the category being edited
+    fn category_editor(&mut self, ui: &mut egui::Ui) {
+        let Some(category) = &mut self.edit_category else {
+            return;
+        };
+        ui.heading(format!("✏ Flokkurinn {}", category.code));
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("Heiti:");
+            ui.text_edit_singleline(&mut category.name);
+        });
+        ui.add_space(6.0);
+        
+        let mut remove = None;
+        egui::Grid::new("category_attributes").num_columns(9).striped(true).show(ui, |ui| {
+            for h in ["Lykill", "Heiti", "Tegund", "Skylda", "Lágmark", "Hámark", "Valmöguleikar", "Eining", ""] {
+                ui.strong(h);
+            }
+            ui.end_row();
+            for (i, def) in category.attributes.iter_mut().enumerate() {
+                ui.add(egui::TextEdit::singleline(&mut def.key).hint_text("t.d. breidd").desired_width(100.0));
+                ui.add(egui::TextEdit::singleline(&mut def.label).hint_text("t.d. Breidd").desired_width(120.0));
+                egui::ComboBox::from_id_source(("attribute_kind", i))
+                    .selected_text(def.kind.to_string())
+                    .show_ui(ui, |ui| {
+                        for kind in AttributeKind::all() {
+                            ui.selectable_value(&mut def.kind, kind, kind.to_string());
+                        }
+                    });
+                ui.checkbox(&mut def.required, "");
+                let is_number = def.kind == AttributeKind::Number;
+                for bound in [&mut def.min, &mut def.max] {
+                    ui.add_enabled_ui(is_number, |ui| {
+                        ui.horizontal(|ui| {
+                            let mut set = bound.is_some();
+                            ui.checkbox(&mut set, "");
+                            match (set, bound.as_mut()) {
+                                (true, Some(n)) => { ui.add(egui::DragValue::new(n)); }
+                                (true, None) => *bound = Some(0),
+                                (false, _) => *bound = None,
+                            }
+                        });
+                    });
+                }
+                let mut choices = def.choices.join(",");
+                let edit = egui::TextEdit::singleline(&mut choices).hint_text("t.d. IPS,VA").desired_width(140.0);
+                if ui.add_enabled(def.kind == AttributeKind::Choice, edit).changed() {
+                    def.choices = choices.split(',').map(str::to_string).collect();
+                }
+                ui.add(egui::TextEdit::singleline(&mut def.unit).hint_text("t.d. cm").desired_width(60.0));
+                if ui.button("🗑").on_hover_text("Fjarlægja eiginleika").clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(i) = remove {
+            category.attributes.remove(i);
+        }
+        if ui.button("➕ Nýr eiginleiki").clicked() {
+            category.attributes.push(AttributeDef::new("", "", AttributeKind::Text));
+        }
+        
+        ui.add_space(10.0);
+        let mut save = false;
+        let mut cancel = false;
+        ui.horizontal(|ui| {
+            save = ui.button("💾 Vista flokk").clicked();
+            cancel = ui.button("Hætta við").clicked();
+        });
+        if cancel {
+            self.edit_category = None;
+        } else if save {
+            self.error_message.clear();
+            self.message.clear();
+            let mut category = category.clone();
+            for def in &mut category.attributes {
+                def.choices = def.choices.iter().map(|c| c.trim().to_string()).filter(|c| !c.is_empty()).collect();
+            }
+            let result = self.db.lock().unwrap().update_category(&category);
+            match result {
+                Ok(_) => {
+                    self.message = format!("✅ Flokkurinn {} vistaður", category.name);
+                    self.edit_category = None;
+                }
+                Err(e) => self.error_message = format!(" Gat ekki vistað flokk: {}", e),
+            }
+            self.reload_categories();
+        }
+    }
+    
+    fn reload_depreciation(&mut self) {
+        match self.db.lock().unwrap().get_depreciation() {
+            Ok(depreciation) => {
+                self.depreciation_inputs = depreciation.years.iter().map(|(code, years)| (code.clone(), years.to_string())).collect();
+                self.depreciation = depreciation;
+            }
+            Err(e) => self.error_message = format!(" Villa við að sækja afskriftir: {}", e),
+        }
+    }
+    
+    fn reload_categories(&mut self) {
+        match self.db.lock().unwrap().get_categories() {
+            Ok(categories) => self.categories = categories,
+            Err(e) => self.error_message = format!(" Villa við að sækja flokka: {}", e),
+        }
+    }
+    
+    fn recycle_bin_section(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🗑 Ruslafata");
+        ui.separator();
+        
+        // Always reload like the other lists
+        {
+            let db = self.db.lock().unwrap();
+            match db.get_retired_equipment() {
+                Ok(items) => self.bin_items = items,
+                Err(e) => self.error_message = format!(" Villa við að sækja ruslafötu: {}", e),
+            }
+        }
+        
+        if self.bin_items.is_empty() {
+            ui.label("Ruslafatan er tóm");
+            return;
+        }
+        ui.label(format!("Fjöldi: {} atriði", self.bin_items.len()));
+        ui.add_space(10.0);
+        
+        let data = self.bin_items.clone();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            use egui_extras::{TableBuilder, Column};
+            TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .cell_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight))
+                .column(Column::initial(60.0).resizable(true)) // ID
+                .column(Column::initial(100.0).resizable(true)) // Tegund
+                .column(Column::initial(110.0).resizable(true)) // Staðsetning
+                .column(Column::initial(110.0).resizable(true)) // Verðmæti
+                .column(Column::initial(100.0).resizable(true)) // Ástæða
+                .column(Column::initial(150.0).resizable(true)) // Afskráð
+                .column(Column::remainder()) // Aðgerðir
+                .header(22.0, |mut header| {
+                    for h in ["ID", "Tegund", "Staðsetning", "Verðmæti", "Ástæða", "Afskráð", "Aðgerðir"] {
+                        header.col(|ui| { ui.strong(h); });
+                    }
+                })
+                .body(|mut body| {
+                    for item in &data {
+                        let id = item.equipment.get_id().unwrap_or(0);
+                        body.row(26.0, |mut row| {
+                            row.col(|ui| { ui.label(id.to_string()); });
+                            row.col(|ui| { ui.label(item.equipment.get_type_name()); });
+                            row.col(|ui| { ui.label(item.equipment.get_location().to_string()); });
+                            row.col(|ui| { ui.label(format!("{} kr.", item.equipment.get_value())); });
+                            row.col(|ui| { ui.label(item.reason.to_string()); });
+                            row.col(|ui| { ui.label(&item.retired_at); });
+                            row.col(|ui| {
+                                ui.horizontal(|ui| {
+                                    if ui.button("♻ Endurheimta").clicked() {
+                                        self.restore_equipment(id);
+                                    }
+                                    let confirming = self.bin_confirm_purge == Some(id);
+                                    let label = if confirming { "⚠ Staðfesta eyðingu" } else { "❌ Eyða varanlega" };
+                                    if ui.button(label).clicked() {
+                                        if confirming {
+                                            self.purge_equipment(id);
+                                        } else {
+                                            self.bin_confirm_purge = Some(id);
+                                        }
+                                    }
+                                });
+                            });
+                        });
+                    }
+                });
+        });
+    }
+    
+    fn audit_section(&mut self, ui: &mut egui::Ui) {
+        ui.heading("📦 Úttekt");
+        ui.separator();
+        
+        let Some(session) = self.audit_session.clone() else {
+            self.audit_start(ui);
+            return;
+        };
+        // Open sessions are compared with the database on every frame, so moves show at once
+        let report = match session.report(&self.db.lock().unwrap()) {
+            Ok(report) => report,
+            Err(e) => {
+                self.error_message = format!(" Villa við samanburð: {}", e);
+                AuditReport::default()
+            }
+        };
+        
+        ui.horizontal(|ui| {
+            if ui.button("⬅ Til baka").clicked() {
+                self.audit_session = None;
+            }
+            ui.label(egui::RichText::new(format!("Úttekt {} – {}", session.id, session.location)).strong().size(18.0));
+            match &session.finished_at {
+                Some(finished) => ui.label(format!("Lokið {}", finished)),
+                None => ui.label(format!("Hafin {} af {}", session.started_at, session.user)),
+            };
+        });
+        ui.label(report.summary());
+        
+        if !session.is_finished() {
+            ui.horizontal(|ui| {
+                ui.label("Skanna eða slá inn ID:");
+                let response = ui.add(egui::TextEdit::singleline(&mut self.audit_input).desired_width(200.0));
+                // A scanner types the code and presses Enter; keep the focus for the next one
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    self.audit_scan(session.id);
+                    response.request_focus();
+                }
+                if ui.button("➕ Skrá").clicked() {
+                    self.audit_scan(session.id);
+                }
+            });
+        }
+        
+        ui.horizontal(|ui| {
+            if !session.is_finished() && ui.button("✅ Ljúka úttekt").clicked() {
+                self.audit_finish(&session, &report);
+            }
+            if ui.button("📄 Vista skýrslu (PDF)").clicked() {
+                self.audit_export_pdf(&session, &report);
+            }
+        });
+        ui.add_space(6.0);
+        
+        let red = egui::Color32::from_rgb(211, 47, 47);
+        let orange = egui::Color32::from_rgb(230, 120, 0);
+        let green = egui::Color32::from_rgb(46, 125, 50);
+        let mut move_here = None;
+        let mut remove_scan = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::CollapsingHeader::new(egui::RichText::new(format!("❓ Vantar ({})", report.missing.len())).color(red))
+                .default_open(true)
+                .show(ui, |ui| {
+                    for eq in &report.missing {
+                        ui.label(eq.to_string());
+                    }
+                });
+            egui::CollapsingHeader::new(egui::RichText::new(format!("↪ Skráð annars staðar ({})", report.misplaced.len())).color(orange))
+                .default_open(true)
+                .show(ui, |ui| {
+                    for eq in &report.misplaced {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("Skráð í {}: {}", eq.get_location(), eq));
+                            if !session.is_finished() && ui.button(format!("📦 Flytja í {}", session.location)).clicked() {
+                                move_here = Some(eq.clone());
+                            }
+                        });
+                    }
+                });
+            egui::CollapsingHeader::new(egui::RichText::new(format!("⚠ Óþekkt ({})", report.unknown.len())).color(orange))
+                .default_open(true)
+                .show(ui, |ui| {
+                    for text in &report.unknown {
+                        ui.label(text);
+                    }
+                });
+            egui::CollapsingHeader::new(egui::RichText::new(format!("✔ Fannst ({})", report.found.len())).color(green))
+                .show(ui, |ui| {
+                    for eq in &report.found {
+                        ui.label(eq.to_string());
+                    }
+                });
+            egui::CollapsingHeader::new(format!("🕘 Skannanir ({})", session.scans.len())).show(ui, |ui| {
+                for scan in session.scans.iter().rev() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}  {}", scan.scanned_at, scan.text));
+                        if !session.is_finished() && ui.small_button("✖").on_hover_text("Taka til baka").clicked() {
+                            remove_scan = Some(scan.id);
+                        }
+                    });
+                }
+            });
+        });
+        
+        if let Some(eq) = move_here {
+            self.audit_move_here(&session, eq);
+        }
+        if let Some(scan_id) = remove_scan {
+            let result = self.db.lock().unwrap().remove_audit_scan(session.id, scan_id);
+            if let Err(e) = result {
+                self.error_message = format!(" {}", e);
+            }
+            self.reload_audit(session.id);
+        }
+    }
+    
+    // Room picker for a new stocktake and the list of earlier ones
+    fn audit_start(&mut self, ui: &mut egui::Ui) {
+        ui.label("Veldu stofu og skannaðu eða sláðu inn ID alls sem er í henni. Niðurstaðan er borin saman við skráðan búnað.");
+        ui.add_space(6.0);
+        Self::render_location_input(ui, "audit_room", &self.rooms, &mut self.audit_location);
+        if ui.button("▶ Hefja úttekt").clicked() {
+            self.error_message.clear();
+            self.message.clear();
+            let result = self.db.lock().unwrap().start_audit(&self.audit_location);
+            match result {
+                Ok(id) => self.reload_audit(id),
+                Err(e) => self.error_message = format!(" Gat ekki hafið úttekt: {}", e),
+            }
+        }
+        ui.add_space(10.0);
+        ui.separator();
+        
+        let sessions = match self.db.lock().unwrap().get_audits() {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                self.error_message = format!(" Villa við að sækja úttektir: {}", e);
+                return;
+            }
+        };
+        if sessions.is_empty() {
+            ui.label("Engar úttektir enn");
+            return;
+        }
+        let mut open = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            use egui_extras::{TableBuilder, Column};
+            TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .cell_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight))
+                .column(Column::initial(50.0)) // Nr.
+                .column(Column::initial(90.0)) // Stofa
+                .column(Column::initial(150.0)) // Hafin
+                .column(Column::initial(110.0)) // Notandi
+                .column(Column::initial(260.0)) // Staða
+                .column(Column::remainder()) // Aðgerðir
+                .header(22.0, |mut header| {
+                    for h in ["Nr.", "Stofa", "Hafin", "Notandi", "Staða", ""] {
+                        header.col(|ui| { ui.strong(h); });
+                    }
+                })
+                .body(|mut body| {
+                    for session in &sessions {
+                        body.row(26.0, |mut row| {
+                            row.col(|ui| { ui.label(session.id.to_string()); });
+                            row.col(|ui| { ui.label(session.location.to_string()); });
+                            row.col(|ui| { ui.label(&session.started_at); });
+                            row.col(|ui| { ui.label(&session.user); });
+                            row.col(|ui| {
+                                match &session.report {
+                                    Some(report) => ui.label(report.summary()),
+                                    None => ui.label(format!("Opin, {} skannanir", session.scans.len())),
+                                };
+                            });
+                            row.col(|ui| {
+                                let label = if session.is_finished() { "📄 Skoða" } else { "▶ Halda áfram" };
+                                if ui.button(label).clicked() {
+                                    open = Some(session.clone());
+                                }
+                            });
+                        });
+                    }
+                });
+        });
+        if open.is_some() {
+            self.audit_session = open;
+        }
+    }
+    
+    fn reload_audit(&mut self, id: i64) {
+        match self.db.lock().unwrap().get_audit(id) {
+            Ok(session) => self.audit_session = session,
+            Err(e) => self.error_message = format!(" Villa við að sækja úttekt: {}", e),
+        }
+    }
+    
+    fn audit_scan(&mut self, audit_id: i64) {
+        let text = std::mem::take(&mut self.audit_input);
+        if text.trim().is_empty() {
+            return;
+        }
+        self.error_message.clear();
+        self.message.clear();
+        let result = self.db.lock().unwrap().add_audit_scan(audit_id, &text);
+        match result {
+            Ok(_) => self.message = format!(" Skráð: {}", text.trim()),
+            Err(e) => self.error_message = format!(" {}", e),
+        }
+        self.reload_audit(audit_id);
+    }
+    
+    // "Found here but registered elsewhere": move it to the audited room, undoable like any edit
+    fn audit_move_here(&mut self, session: &AuditSession, before: Equipment) {
+        self.error_message.clear();
+        self.message.clear();
+        let Some(id) = before.get_id() else { return };
+        let mut after = before.clone();
+        after.set_location(session.location.clone());
+        let result = self.db.lock().unwrap().update_location(id, &session.location);
+        match result {
+            Ok(_) => {
+                self.message = format!(" Búnaður með ID {} fluttur í {}", id, session.location);
+                self.undo_stack.record(Change::Edited { id, before: Box::new(before), after: Box::new(after) });
+            }
+            Err(e) => self.error_message = format!(" Villa við flutning: {}", e),
+        }
+    }
+    
+    fn audit_finish(&mut self, session: &AuditSession, report: &AuditReport) {
+        self.error_message.clear();
+        self.message.clear();
+        let result = self.db.lock().unwrap().finish_audit(session.id, report);
+        match result {
+            Ok(_) => self.message = format!(" Úttekt {} lokið: {}", session.id, report.summary()),
+            Err(e) => self.error_message = format!(" Gat ekki lokið úttekt: {}", e),
+        }
+        self.reload_audit(session.id);
+    }
+    
+    fn audit_export_pdf(&mut self, session: &AuditSession, report: &AuditReport) {
+        self.error_message.clear();
+        self.message.clear();
+        let name = format!("uttekt-{}-{}.pdf", session.id, session.location);
+        if let Some(dest) = FileDialog::new().set_file_name(&name).add_filter("PDF", &["pdf"]).save_file() {
+            let result = pdf::audit_report(session, report).and_then(|bytes| Ok(std::fs::write(&dest, bytes)?));
+            match result {
+                Ok(_) => self.message = format!("✅ Skýrsla vistuð í {}", dest.display()),
+                Err(e) => self.error_message = format!(" Gat ekki vistað skýrslu: {}", e),
+            }
+        }
+    }
+    
+    fn restore_equipment(&mut self, id: i64) {
+        self.error_message.clear();
+        self.message.clear();
+        let db = self.db.lock().unwrap();
+        // Remember the reason so undo can put it back in the bin as it was
+        let reason = db
+            .get_retired_equipment()
+            .ok()
+            .and_then(|items| items.into_iter().find(|r| r.equipment.get_id() == Some(id)))
+            .map(|r| r.reason)
+            .unwrap_or(RetireReason::Other);
+        match db.restore_equipment(id) {
+            Ok(_) => {
+                self.message = format!(" Búnaður með ID {} endurheimtur", id);
+                self.undo_stack.record(Change::Restored { id, reason });
+            }
+            Err(e) => self.error_message = format!(" Villa við endurheimt: {}", e),
+        }
+    }
+    
+    fn undo(&mut self) {
+        let result = {
+            let db = self.db.lock().unwrap();
+            self.undo_stack.undo(&db)
+        };
+        self.after_undo_redo(result, "Afturkallað", "Gat ekki afturkallað");
+    }
+    
+    fn redo(&mut self) {
+        let result = {
+            let db = self.db.lock().unwrap();
+            self.undo_stack.redo(&db)
+        };
+        self.after_undo_redo(result, "Endurgert", "Gat ekki endurgert");
+    }
+    
+    // Refresh whatever shows the changed items, then report the outcome
+    fn after_undo_redo(&mut self, result: bunadarlisti::Result<Option<String>>, done: &str, failed: &str) {
+        if !self.edit_id.is_empty() {
+            self.fetch_equipment_for_edit();
+        }
+        self.load_equipment();
+        self.bin_confirm_purge = None;
+        self.error_message.clear();
+        self.message.clear();
+        match result {
+            Ok(Some(description)) => self.message = format!(" {}: {}", done, description),
+            Ok(None) => {}
+            Err(e) => self.error_message = format!(" {}: {}", failed, e),
+        }
+    }
+    
+    fn recent_changes(&self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("🕘 Nýlegar aðgerðir")
+            .default_open(false)
+            .show(ui, |ui| {
+                if !self.undo_stack.can_undo() && !self.undo_stack.can_redo() {
+                    ui.label("Engar aðgerðir enn");
+                    return;
+                }
+                // Undone changes (greyed) sit above the ones that are in effect
+                for change in self.undo_stack.undone().collect::<Vec<_>>().into_iter().rev() {
+                    ui.label(egui::RichText::new(format!("↶ {}", change.describe())).color(egui::Color32::GRAY));
+                }
+                for change in self.undo_stack.done().take(10) {
+                    ui.label(change.describe());
+                }
+            });
+    }
+    
+    fn purge_equipment(&mut self, id: i64) {
+        self.error_message.clear();
+        self.message.clear();
+        self.bin_confirm_purge = None;
+        let db = self.db.lock().unwrap();
+        match db.delete_equipment(id) {
+            Ok(_) => self.message = format!(" Búnaði með ID {} eytt varanlega", id),
+            Err(e) => self.error_message = format!(" Villa við eyðingu: {}", e),
+        }
+    }
+    
+    fn search_section(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🔍 Leita að búnaði");
+        ui.separator();
+
+        // Always refresh search like the sidebar does
+        self.perform_search();
+
+        // Live search input
+        ui.horizontal(|ui| {
+            ui.label("Leit:");
+            let changed = ui.text_edit_singleline(&mut self.search_query).changed();
+            if changed {
+                self.perform_search();
+            }
+        });
+        ui.add_space(10.0);
+
+        // Ensure search is initialized (first time entering without typing)
+        if !self.search_initialized {
+            self.perform_search();
+            self.search_initialized = true;
+        }
+
+        // If exactly one result, auto-select it
+        if self.search_results.len() == 1 {
             self.search_selected_index = Some(0);
         } else if self.search_results.is_empty() {
             self.search_selected_index = None;
@@ -625,11 +2169,9 @@ impl EquipmentApp {
             ui.separator();
             ui.horizontal(|ui| {
                 ui.label(format!("Fjöldi niðurstaðna: {} atriði", self.search_results.len()));
-                if self.sort_column.is_some() {
-                    if ui.button("🔄 Endurstilla röðun").clicked() {
-                        self.sort_column = None;
-                        self.sort_order = SortOrder::Ascending;
-                    }
+                if self.sort_column.is_some() && ui.button("🔄 Endurstilla röðun").clicked() {
+                    self.sort_column = None;
+                    self.sort_order = SortOrder::Ascending;
                 }
             });
             ui.add_space(10.0);
@@ -643,13 +2185,13 @@ impl EquipmentApp {
                         SortColumn::Id => a.get_id().unwrap_or(0).cmp(&b.get_id().unwrap_or(0)),
                         SortColumn::Type => a.get_type_name().cmp(b.get_type_name()),
                         SortColumn::Location => {
-                            let loc_a = match a { Equipment::Table(t) => &t.location, Equipment::Chair(c) => &c.location, Equipment::Projector(p) => &p.location };
-                            let loc_b = match b { Equipment::Table(t) => &t.location, Equipment::Chair(c) => &c.location, Equipment::Projector(p) => &p.location };
-                            format!("{}", loc_a).cmp(&format!("{}", loc_b))
+                            let loc_a = a.get_location();
+                            let loc_b = b.get_location();
+                            loc_a.cmp(loc_b)
                         }
                         SortColumn::Value => {
-                            let va = match a { Equipment::Table(t) => t.value, Equipment::Chair(c) => c.value, Equipment::Projector(p) => p.value };
-                            let vb = match b { Equipment::Table(t) => t.value, Equipment::Chair(c) => c.value, Equipment::Projector(p) => p.value };
+                            let va = a.get_value();
+                            let vb = b.get_value();
                             va.cmp(&vb)
                         }
                     };
@@ -669,6 +2211,8 @@ impl EquipmentApp {
                     .column(Column::initial(120.0).resizable(true)) // Tegund
                     .column(Column::initial(160.0).resizable(true)) // Staðsetning
                     .column(Column::initial(120.0).resizable(true)) // Verðmæti
+                    .column(Column::initial(130.0).resizable(true)) // Raðnúmer
+                    .column(Column::initial(110.0).resizable(true)) // Ástand
                     .column(Column::remainder().resizable(true)); // Lýsing
 
                 table
@@ -677,21 +2221,25 @@ impl EquipmentApp {
                         header.col(|ui| { ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight), |ui| { if ui.button(format!("Tegund{}", self.sort_indicator(SortColumn::Type))).clicked() { self.toggle_sort(SortColumn::Type); } }); });
                         header.col(|ui| { ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight), |ui| { if ui.button(format!("Staðsetning{}", self.sort_indicator(SortColumn::Location))).clicked() { self.toggle_sort(SortColumn::Location); } }); });
                         header.col(|ui| { ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight), |ui| { if ui.button(format!("Verðmæti{}", self.sort_indicator(SortColumn::Value))).clicked() { self.toggle_sort(SortColumn::Value); } }); });
+                        header.col(|ui| { ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight), |ui| { ui.label("Raðnúmer"); }); });
+                        header.col(|ui| { ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight), |ui| { ui.label("Ástand"); }); });
                         header.col(|ui| { ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight), |ui| { ui.label("Lýsing"); }); });
                     })
                     .body(|mut body| {
                         let row_h = 22.0;
                         for (i, equipment) in table_data.iter().enumerate() {
                             let id = equipment.get_id().unwrap_or(0);
-                            let location_str = match equipment { Equipment::Table(t) => format!("{}", t.location), Equipment::Chair(c) => format!("{}", c.location), Equipment::Projector(p) => format!("{}", p.location) };
-                            let value = match equipment { Equipment::Table(t) => t.value, Equipment::Chair(c) => c.value, Equipment::Projector(p) => p.value };
+                            let location_str = equipment.get_location().to_string();
+                            let value = equipment.get_value();
                             body.row(row_h, |mut row| {
                                 let mut clicked_any = false;
-                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(id.to_string()).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
-                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(equipment.get_type_name()).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
-                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(location_str.clone()).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
-                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(format!("{} kr.", value)).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
-                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(format!("{}", equipment)).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, id.to_string())).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, equipment.get_type_name())).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, location_str.clone())).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, format!("{} kr.", value))).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, equipment.serial_number().unwrap_or_default())).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, equipment.info().condition.to_string())).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, format!("{}", equipment))).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
 
                                 if clicked_any {
                                     self.search_selected_index = Some(i);
@@ -714,30 +2262,9 @@ impl EquipmentApp {
         let db = self.db.lock().unwrap();
         match db.get_all_equipment() {
             Ok(all_equipment) => {
-                let q = self.search_query.to_lowercase();
                 self.search_results = all_equipment
                     .into_iter()
-                    .filter(|eq| {
-                        // Search across multiple columns
-                        let id_match = eq.get_id().unwrap_or(0).to_string().contains(&q);
-                        let type_match = eq.get_type_name().to_lowercase().contains(&q);
-                        let location_match = match eq {
-                            Equipment::Table(t) => format!("{}", t.location),
-                            Equipment::Chair(c) => format!("{}", c.location),
-                            Equipment::Projector(p) => format!("{}", p.location),
-                        }
-                        .to_lowercase()
-                        .contains(&q);
-                        let value_match = match eq {
-                            Equipment::Table(t) => t.value,
-                            Equipment::Chair(c) => c.value,
-                            Equipment::Projector(p) => p.value,
-                        }
-                        .to_string()
-                        .contains(&q);
-                        let desc_match = format!("{}", eq).to_lowercase().contains(&q);
-                        id_match || type_match || location_match || value_match || desc_match
-                    })
+                    .filter(|eq| eq.matches_query(&self.search_query))
                     .collect();
             }
             Err(e) => {
@@ -789,6 +2316,8 @@ impl EquipmentApp {
             Self::radio_black_value(ui, &mut self.display_filter, DisplayFilter::ByRoom, "Eftir stofu");
             ui.add_space(10.0);
             Self::radio_black_value(ui, &mut self.display_filter, DisplayFilter::ByFloor, "Eftir hæð");
+            ui.add_space(10.0);
+            Self::radio_black_value(ui, &mut self.display_filter, DisplayFilter::ByCondition, "Eftir ástandi");
         });
         
         ui.add_space(10.0);
@@ -798,59 +2327,47 @@ impl EquipmentApp {
             DisplayFilter::ByBuilding => {
                 ui.horizontal(|ui| {
                     ui.label("Hús:");
-                    egui::ComboBox::from_label("")
-                        .selected_text(format!("{}", self.display_building))
-                        .show_ui(ui, |ui| {
-                            for b in Building::all() {
-                                ui.selectable_value(&mut self.display_building, b, format!("{}", b));
-                            }
-                        });
+                    Self::building_combo(ui, "display_building", &mut self.display_building, &self.buildings);
                 });
             }
             DisplayFilter::ByType => {
-                ui.horizontal(|ui| {
+                ui.horizontal_wrapped(|ui| {
                     ui.label("Tegund:");
                     Self::radio_black_value(ui, &mut self.display_type, EquipmentType::Table, "Borð");
                     ui.add_space(10.0);
                     Self::radio_black_value(ui, &mut self.display_type, EquipmentType::Chair, "Stóll");
                     ui.add_space(10.0);
                     Self::radio_black_value(ui, &mut self.display_type, EquipmentType::Projector, "Skjávarpi");
+                    ui.add_space(10.0);
+                    Self::radio_black_value(ui, &mut self.display_type, EquipmentType::Computer, "Tölva");
+                    ui.add_space(10.0);
+                    Self::radio_black_value(ui, &mut self.display_type, EquipmentType::Monitor, "Skjár");
+                    for category in &self.categories {
+                        ui.add_space(10.0);
+                        Self::radio_black_value(ui, &mut self.display_type, EquipmentType::Custom(category.code.clone()), &category.name);
+                    }
                 });
             }
             DisplayFilter::ByRoom => {
-                ui.horizontal(|ui| {
-                    ui.label("Hús:");
-                    egui::ComboBox::from_label("")
-                        .selected_text(format!("{}", self.display_building))
-                        .show_ui(ui, |ui| {
-                            for b in Building::all() {
-                                ui.selectable_value(&mut self.display_building, b, format!("{}", b));
-                            }
-                        });
-                });
-                ui.horizontal(|ui| {
-                    ui.label("Hæð:");
-                    ui.add(egui::Slider::new(&mut self.display_room_floor, 0..=9).text("hæð"));
-                });
-                ui.horizontal(|ui| {
-                    ui.label("Herbergi:");
-                    ui.add(egui::Slider::new(&mut self.display_room_number, 0..=99).text("herbergi"));
-                });
+                Self::render_location_input(ui, "display_room", &self.rooms, &mut self.display_room);
             }
             DisplayFilter::ByFloor => {
                 ui.horizontal(|ui| {
                     ui.label("Hús:");
-                    egui::ComboBox::from_label("")
-                        .selected_text(format!("{}", self.display_building))
-                        .show_ui(ui, |ui| {
-                            for b in Building::all() {
-                                ui.selectable_value(&mut self.display_building, b, format!("{}", b));
-                            }
-                        });
+                    Self::building_combo(ui, "display_floor_building", &mut self.display_building, &self.buildings);
                 });
                 ui.horizontal(|ui| {
                     ui.label("Hæð:");
-                    ui.add(egui::Slider::new(&mut self.display_floor, 0..=9).text("hæð"));
+                    Self::floor_input(ui, &mut self.display_floor);
+                });
+            }
+            DisplayFilter::ByCondition => {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Ástand:");
+                    for condition in Condition::all() {
+                        ui.add_space(10.0);
+                        Self::radio_black_value(ui, &mut self.display_condition, condition, &condition.to_string());
+                    }
                 });
             }
         }
@@ -866,15 +2383,43 @@ impl EquipmentApp {
             if ui.button("📂 Hlaða úr JSON").clicked() {
                 self.load_from_json();
             }
+            if ui.button("📊 Vista í CSV").clicked() {
+                self.save_to_csv();
+            }
+            if ui.button("📂 Hlaða úr CSV").clicked() {
+                self.load_from_csv();
+            }
             ui.add_space(12.0);
             if ui.button("📄 Prenta lista").clicked() {
                 self.print_current_list();
             }
+            if self.direct_print {
+                if ui.button("🖨 Senda á prentara").clicked() {
+                    self.send_to_printer();
+                }
+                if !self.printers.is_empty() {
+                    egui::ComboBox::from_id_source("printer")
+                        .selected_text(self.printer.as_deref().unwrap_or("Sjálfgefinn prentari"))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.printer, None, "Sjálfgefinn prentari");
+                            for name in &self.printers {
+                                ui.selectable_value(&mut self.printer, Some(name.clone()), name);
+                            }
+                        });
+                }
+            }
             if ui.button("💾 Flytja út í PDF").clicked() {
                 self.export_current_list_pdf();
             }
+            if ui.button("📗 Flytja út í Excel").clicked() {
+                self.export_xlsx();
+            }
         });
         
+        ui.add_space(6.0);
+        self.room_sheets_controls(ui);
+        self.labels_controls(ui);
+        
         ui.add_space(10.0);
         
         // Display table with sortable columns
@@ -882,11 +2427,9 @@ impl EquipmentApp {
             ui.separator();
             ui.horizontal(|ui| {
                 ui.label(format!("Fjöldi: {} atriði", self.displayed_equipment.len()));
-                if self.sort_column.is_some() {
-                    if ui.button("🔄 Endurstilla röðun").clicked() {
-                        self.sort_column = None;
-                        self.load_equipment();
-                    }
+                if self.sort_column.is_some() && ui.button("🔄 Endurstilla röðun").clicked() {
+                    self.sort_column = None;
+                    self.load_equipment();
                 }
             });
             
@@ -902,6 +2445,7 @@ impl EquipmentApp {
                     .column(Column::initial(120.0).resizable(true)) // Tegund
                     .column(Column::initial(160.0).resizable(true)) // Staðsetning
                     .column(Column::initial(120.0).resizable(true)) // Verðmæti
+                    .column(Column::initial(110.0).resizable(true)) // Ástand
                     .column(Column::remainder().resizable(true)); // Lýsing
 
                 table
@@ -910,21 +2454,23 @@ impl EquipmentApp {
                         header.col(|ui| { ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight), |ui| { if ui.button(format!("Tegund{}", self.sort_indicator(SortColumn::Type))).clicked() { self.toggle_sort(SortColumn::Type); } }); });
                         header.col(|ui| { ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight), |ui| { if ui.button(format!("Staðsetning{}", self.sort_indicator(SortColumn::Location))).clicked() { self.toggle_sort(SortColumn::Location); } }); });
                         header.col(|ui| { ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight), |ui| { if ui.button(format!("Verðmæti{}", self.sort_indicator(SortColumn::Value))).clicked() { self.toggle_sort(SortColumn::Value); } }); });
+                        header.col(|ui| { ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight), |ui| { ui.label("Ástand"); }); });
                         header.col(|ui| { ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::LeftToRight), |ui| { ui.label("Lýsing"); }); });
                     })
                     .body(|mut body| {
                         let row_h = 22.0;
                         for equipment in &data {
                             let id = equipment.get_id().unwrap_or(0);
-                            let location_str = match equipment { Equipment::Table(t) => format!("{}", t.location), Equipment::Chair(c) => format!("{}", c.location), Equipment::Projector(p) => format!("{}", p.location) };
-                            let value = match equipment { Equipment::Table(t) => t.value, Equipment::Chair(c) => c.value, Equipment::Projector(p) => p.value };
+                            let location_str = equipment.get_location().to_string();
+                            let value = equipment.get_value();
                             body.row(row_h, |mut row| {
                                 let mut clicked_any = false;
-                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(id.to_string()).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
-                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(equipment.get_type_name()).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
-                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(location_str.clone()).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
-                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(format!("{} kr.", value)).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
-                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(format!("{}", equipment)).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, id.to_string())).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, equipment.get_type_name())).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, location_str.clone())).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, format!("{} kr.", value))).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, equipment.info().condition.to_string())).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
+                                row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(Self::row_text(equipment, format!("{}", equipment))).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
 
                                 if clicked_any {
                                     // From Prenta: go to Edit with back button to printing
@@ -941,6 +2487,16 @@ impl EquipmentApp {
         }
     }
 
+    // Out-of-service items stand out in the search and print tables
+    fn row_text(equipment: &Equipment, text: impl Into<String>) -> egui::RichText {
+        let text = egui::RichText::new(text);
+        if equipment.is_out_of_service() {
+            text.color(egui::Color32::from_rgb(211, 47, 47)).strong()
+        } else {
+            text
+        }
+    }
+
     fn escape_html(s: &str) -> String {
         s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
     }
@@ -951,18 +2507,16 @@ impl EquipmentApp {
         for eq in &self.displayed_equipment {
             let id = eq.get_id().unwrap_or(0).to_string();
             let typ = eq.get_type_name().to_string();
-            let (location_str, value) = match eq {
-                Equipment::Table(t) => (format!("{}", t.location), t.value),
-                Equipment::Chair(c) => (format!("{}", c.location), c.value),
-                Equipment::Projector(p) => (format!("{}", p.location), p.value),
-            };
+            let (location_str, value) = (eq.get_location().to_string(), eq.get_value());
             let desc = format!("{}", eq);
             rows.push_str(&format!(
-                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{} kr.</td><td>{}</td></tr>",
+                "<tr{}><td>{}</td><td>{}</td><td>{}</td><td>{} kr.</td><td>{}</td><td>{}</td></tr>",
+                if eq.is_out_of_service() { " class='out-of-service'" } else { "" },
                 Self::escape_html(&id),
                 Self::escape_html(&typ),
                 Self::escape_html(&location_str),
                 value,
+                Self::escape_html(&eq.info().condition.to_string()),
                 Self::escape_html(&desc)
             ));
         }
@@ -975,6 +2529,7 @@ impl EquipmentApp {
             table { border-collapse: collapse; width: 100%; }
             th, td { border: 1px solid #ccc; padding: 8px; text-align: left; font-size: 12px; }
             th { background: #f5f7fb; }
+            tr.out-of-service td { color: #d32f2f; font-weight: bold; }
             @media print { body { margin: 0; } }
         "#;
         let script = r#"
@@ -985,101 +2540,211 @@ impl EquipmentApp {
         "#;
 
         format!(
-            "<!DOCTYPE html><html><head><meta charset='utf-8'><title>{}</title><style>{}</style><script>{}</script></head><body><h1>{}</h1><div class='meta'>Fjöldi: {} atriði</div><table><thead><tr><th>ID</th><th>Tegund</th><th>Staðsetning</th><th>Verðmæti</th><th>Lýsing</th></tr></thead><tbody>{}</tbody></table></body></html>",
+            "<!DOCTYPE html><html><head><meta charset='utf-8'><title>{}</title><style>{}</style><script>{}</script></head><body><h1>{}</h1><div class='meta'>Fjöldi: {} atriði</div><table><thead><tr><th>ID</th><th>Tegund</th><th>Staðsetning</th><th>Verðmæti</th><th>Ástand</th><th>Lýsing</th></tr></thead><tbody>{}</tbody></table></body></html>",
             title, style, script, title, self.displayed_equipment.len(), rows
         )
     }
 
     fn print_current_list(&mut self) {
-        // Write HTML to a temporary file and open it to trigger the OS print dialog
+        // Write HTML to a temporary file and open it in the browser, which runs window.print()
         self.error_message.clear();
         self.message.clear();
         let html = self.generate_print_html();
         let mut path = std::env::temp_dir();
         path.push("bunadarlisti_prenta.html");
-        match std::fs::write(&path, html) {
-            Ok(_) => {
-                // On macOS, 'open' will use default browser which will run window.print()
-                let _ = Command::new("open").arg(&path).spawn();
-                self.message = "📄 Opnaði prentglugga í vafra".into();
-            }
+        if let Err(e) = std::fs::write(&path, &html) {
+            self.error_message = format!(" Gat ekki útbúið prentun: {}", e);
+            return;
+        }
+        match print::open_file(&path) {
+            Ok(_) => self.message = "📄 Opnaði prentglugga í vafra".into(),
             Err(e) => {
-                self.error_message = format!(" Gat ekki útbúið prentun: {}", e);
+                // No browser could be started: let the user keep the page and open it some other way
+                self.error_message = format!(" Gat ekki opnað vafra ({}). Vistaðu síðuna og opnaðu hana í vafra til að prenta.", e);
+                if let Some(dest) = FileDialog::new().set_file_name("bunadarlisti.html").add_filter("HTML", &["html"]).save_file() {
+                    match std::fs::write(&dest, &html) {
+                        Ok(_) => {
+                            self.error_message.clear();
+                            self.message = format!(" Prentsíða vistuð í {}", dest.display());
+                        }
+                        Err(e) => self.error_message = format!(" Gat ekki vistað HTML: {}", e),
+                    }
+                }
+            }
+        }
+    }
+    
+    // Straight to a CUPS printer: the PDF report of the current list, through lpr
+    fn send_to_printer(&mut self) {
+        self.error_message.clear();
+        self.message.clear();
+        let mut path = std::env::temp_dir();
+        path.push("bunadarlisti_prenta.pdf");
+        let result = pdf::equipment_report(&self.displayed_equipment, &self.filter_description())
+            .and_then(|bytes| Ok(std::fs::write(&path, bytes)?))
+            .and_then(|_| print::print_file(&path, self.printer.as_deref()));
+        match result {
+            Ok(_) => {
+                let printer = self.printer.clone().unwrap_or_else(|| "sjálfgefinn prentara".to_string());
+                self.message = format!("🖨 {} atriði send á {}", self.displayed_equipment.len(), printer);
             }
+            Err(e) => self.error_message = format!(" Gat ekki prentað: {}", e),
         }
     }
 
+    // What the current list shows, for report headers
+    fn filter_description(&self) -> String {
+        let type_name = match &self.display_type {
+            EquipmentType::Table => "Borð".to_string(),
+            EquipmentType::Chair => "Stólar".to_string(),
+            EquipmentType::Projector => "Skjávarpar".to_string(),
+            EquipmentType::Computer => "Tölvur".to_string(),
+            EquipmentType::Monitor => "Skjáir".to_string(),
+            custom => custom.name(&self.categories),
+        };
+        match self.display_filter {
+            DisplayFilter::All => "Allur búnaður".to_string(),
+            DisplayFilter::ByBuilding => format!("Hús: {}", self.building_name(&self.display_building)),
+            DisplayFilter::ByType => format!("Tegund: {}", type_name),
+            DisplayFilter::ByRoom => format!("Stofa: {}", self.display_room),
+            DisplayFilter::ByFloor => {
+                format!("Hæð: {}, {}", self.building_name(&self.display_building), location::floor_name(self.display_floor))
+            }
+            DisplayFilter::ByCondition => format!("Ástand: {}", self.display_condition),
+        }
+    }
+    
+    fn building_name(&self, building: &Building) -> String {
+        BuildingInfo::name_of(&self.buildings, building)
+    }
+    
     fn export_current_list_pdf(&mut self) {
         self.error_message.clear();
         self.message.clear();
         if let Some(dest) = FileDialog::new().set_file_name("bunadarlisti.pdf").add_filter("PDF", &["pdf"]).save_file() {
-            // Generate a simple PDF with a table listing the current displayed_equipment
-            use printpdf::*;
-            let (doc, page1, layer1) = PdfDocument::new("Búnaðarlisti", Mm(210.0), Mm(297.0), "Layer 1");
-            let mut current_layer = doc.get_page(page1).get_layer(layer1);
-
-            // Basic text config
-            let font = doc.add_builtin_font(BuiltinFont::Helvetica).unwrap();
-            let title = "Búnaðarlisti Tækniskólans";
-            let mut y = Mm(280.0);
-            current_layer.use_text(title, 16.0, Mm(14.0), y, &font);
-            y = Mm(y.0 - 8.0);
-            current_layer.use_text(format!("Fjöldi: {} atriði", self.displayed_equipment.len()), 10.0, Mm(14.0), y, &font);
-            y = Mm(y.0 - 10.0);
-
-            // Table headers
-            let headers = ["ID", "Tegund", "Staðsetning", "Verðmæti", "Lýsing"];
-            let col_x = [Mm(14.0), Mm(34.0), Mm(70.0), Mm(110.0), Mm(140.0)];
-            for (i, h) in headers.iter().enumerate() {
-                current_layer.use_text(h.to_string(), 10.0, col_x[i], y, &font);
+            let result = pdf::equipment_report(&self.displayed_equipment, &self.filter_description())
+                .and_then(|bytes| Ok(std::fs::write(&dest, bytes)?));
+            match result {
+                Ok(_) => self.message = format!("✅ Vistað PDF í {}", dest.display()),
+                Err(e) => self.error_message = format!(" Gat ekki vistað PDF: {}", e),
             }
-            y = Mm(y.0 - 6.0);
-
-            // Rows (simple flow, wraps to new page if needed)
-            let line_h = 5.5;
-            let mut page = 1;
-            for eq in &self.displayed_equipment {
-                if y.0 < 20.0 {
-                    // new page
-                    page += 1;
-                    let (p, l) = doc.add_page(Mm(210.0), Mm(297.0), format!("Layer {}", page));
-                    current_layer = doc.get_page(p).get_layer(l);
-                    y = Mm(280.0);
-                    // re-draw headers
-                    for (i, h) in headers.iter().enumerate() {
-                        current_layer.use_text(h.to_string(), 10.0, col_x[i], y, &font);
-                    }
-                    y = Mm(y.0 - 6.0);
+        }
+    }
+    
+    fn room_sheets_controls(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("🚪 Stofublöð (til að hengja á hurðir)").show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Hús:");
+                Self::building_combo(ui, "sheet_building", &mut self.sheet_building, &self.buildings);
+                let mut one_floor = self.sheet_floor.is_some();
+                if ui.checkbox(&mut one_floor, "Aðeins ein hæð").changed() {
+                    self.sheet_floor = one_floor.then_some(1);
                 }
+                if let Some(floor) = &mut self.sheet_floor {
+                    Self::floor_input(ui, floor);
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button("📄 Ein PDF skrá").clicked() {
+                    self.export_room_sheets(false);
+                }
+                if ui.button("📁 Ein skrá á hverja stofu").clicked() {
+                    self.export_room_sheets(true);
+                }
+            });
+        });
+    }
 
-                let id = eq.get_id().unwrap_or(0).to_string();
-                let typ = eq.get_type_name().to_string();
-                let (location_str, value, desc) = match eq {
-                    Equipment::Table(t) => (format!("{}", t.location), t.value, format!("{}", eq)),
-                    Equipment::Chair(c) => (format!("{}", c.location), c.value, format!("{}", eq)),
-                    Equipment::Projector(p) => (format!("{}", p.location), p.value, format!("{}", eq)),
-                };
-
-                // Draw row text
-                current_layer.use_text(id, 9.0, col_x[0], y, &font);
-                current_layer.use_text(typ, 9.0, col_x[1], y, &font);
-                current_layer.use_text(location_str, 9.0, col_x[2], y, &font);
-                current_layer.use_text(format!("{} kr.", value), 9.0, col_x[3], y, &font);
-                current_layer.use_text(desc, 9.0, col_x[4], y, &font);
+    fn labels_controls(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("🏷 QR merkimiðar (A4, 3 x 8)").show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("ID:");
+                ui.add(egui::TextEdit::singleline(&mut self.label_ids).hint_text("t.d. 1-5, 12 (tómt = listinn hér að neðan)"));
+            });
+            ui.horizontal(|ui| {
+                ui.label("Þegar notaðir af fyrstu örk:");
+                ui.add(egui::Slider::new(&mut self.label_skip, 0..=23));
+            });
+            if ui.button("🏷 Búa til merkimiða").clicked() {
+                self.export_labels();
+            }
+        });
+    }
 
-                y = Mm(y.0 - line_h);
+    fn export_labels(&mut self) {
+        self.error_message.clear();
+        self.message.clear();
+        let items = if self.label_ids.trim().is_empty() {
+            Ok(self.displayed_equipment.clone())
+        } else {
+            let db = self.db.lock().unwrap();
+            parse_id_list(&self.label_ids).and_then(|ids| {
+                ids.into_iter()
+                    .map(|id| db.get_equipment_by_id(id)?.ok_or(Error::NotFound(id)))
+                    .collect::<Result<Vec<_>, Error>>()
+            })
+        };
+        let items = match items {
+            Ok(items) if items.is_empty() => {
+                self.error_message = " Enginn búnaður valinn".to_string();
+                return;
+            }
+            Ok(items) => items,
+            Err(e) => {
+                self.error_message = format!(" {}", e);
+                return;
+            }
+        };
+        if let Some(dest) = FileDialog::new().set_file_name("merkimidar.pdf").add_filter("PDF", &["pdf"]).save_file() {
+            let result = pdf::equipment_labels(&items, self.label_skip).and_then(|bytes| Ok(std::fs::write(&dest, bytes)?));
+            match result {
+                Ok(_) => self.message = format!("✅ {} merkimiðar vistaðir í {}", items.len(), dest.display()),
+                Err(e) => self.error_message = format!(" Gat ekki vistað merkimiða: {}", e),
             }
+        }
+    }
 
-            match std::fs::File::create(&dest) {
-                Ok(file) => {
-                    let mut buf = std::io::BufWriter::new(file);
-                    match doc.save(&mut buf) {
-                        Ok(_) => self.message = format!("✅ Vistað PDF í {}", dest.display()),
-                        Err(e) => self.error_message = format!(" Gat ekki vistað PDF: {}", e),
-                    }
+    // One page per room with equipment, for the chosen building or floor
+    fn export_room_sheets(&mut self, one_file_per_room: bool) {
+        self.error_message.clear();
+        self.message.clear();
+        let rooms = {
+            let db = self.db.lock().unwrap();
+            db.get_occupied_rooms(&self.sheet_building, self.sheet_floor).and_then(|locations| {
+                locations
+                    .into_iter()
+                    .map(|loc| Ok((loc.clone(), db.get_equipment_by_room(&loc)?)))
+                    .collect::<Result<Vec<_>, Error>>()
+            })
+        };
+        let rooms = match rooms {
+            Ok(rooms) if rooms.is_empty() => {
+                self.error_message = " Enginn búnaður er skráður á þessum stað".to_string();
+                return;
+            }
+            Ok(rooms) => rooms,
+            Err(e) => {
+                self.error_message = format!(" Gat ekki sótt stofur: {}", e);
+                return;
+            }
+        };
+        if one_file_per_room {
+            if let Some(dir) = FileDialog::new().pick_folder() {
+                match pdf::write_room_sheets_to_dir(&dir, &rooms, &self.buildings) {
+                    Ok(files) => self.message = format!("✅ {} stofublöð vistuð í {}", files.len(), dir.display()),
+                    Err(e) => self.error_message = format!(" Gat ekki vistað stofublöð: {}", e),
                 }
-                Err(e) => {
-                    self.error_message = format!(" Gat ekki búið til skrá: {}", e);
+            }
+        } else {
+            let name = match self.sheet_floor {
+                Some(floor) => format!("stofublod-{}-{}.pdf", self.sheet_building.to_code(), location::floor_code(floor)),
+                None => format!("stofublod-{}.pdf", self.sheet_building.to_code()),
+            };
+            if let Some(dest) = FileDialog::new().set_file_name(&name).add_filter("PDF", &["pdf"]).save_file() {
+                let result = pdf::room_sheets(&rooms, &self.buildings).and_then(|bytes| Ok(std::fs::write(&dest, bytes)?));
+                match result {
+                    Ok(_) => self.message = format!("✅ {} stofublöð vistuð í {}", rooms.len(), dest.display()),
+                    Err(e) => self.error_message = format!(" Gat ekki vistað stofublöð: {}", e),
                 }
             }
         }
@@ -1117,99 +2782,118 @@ impl EquipmentApp {
         ""
     }
     
-    fn show_statistics(&self, ui: &mut egui::Ui) {
-        let db = self.db.lock().unwrap();
-        let all_equipment = db.get_all_equipment().unwrap_or_default();
-        
-        let total_count = all_equipment.len();
-        let table_count = all_equipment.iter().filter(|e| matches!(e, Equipment::Table(_))).count();
-        let chair_count = all_equipment.iter().filter(|e| matches!(e, Equipment::Chair(_))).count();
-        let projector_count = all_equipment.iter().filter(|e| matches!(e, Equipment::Projector(_))).count();
-        
-        let total_value: u32 = all_equipment.iter().map(|e| match e {
-            Equipment::Table(t) => t.value,
-            Equipment::Chair(c) => c.value,
-            Equipment::Projector(p) => p.value,
-        }).sum();
-        
-        // Count by building
-        let ha_count = all_equipment.iter().filter(|e| {
-            let loc = match e {
-                Equipment::Table(t) => &t.location,
-                Equipment::Chair(c) => &c.location,
-                Equipment::Projector(p) => &p.location,
-            };
-            matches!(loc.building, Building::Hafnarfjordur)
-        }).count();
-        
-        let h_count = all_equipment.iter().filter(|e| {
-            let loc = match e {
-                Equipment::Table(t) => &t.location,
-                Equipment::Chair(c) => &c.location,
-                Equipment::Projector(p) => &p.location,
-            };
-            matches!(loc.building, Building::Hateigssvegur)
-        }).count();
-        
-        let s_count = all_equipment.iter().filter(|e| {
-            let loc = match e {
-                Equipment::Table(t) => &t.location,
-                Equipment::Chair(c) => &c.location,
-                Equipment::Projector(p) => &p.location,
-            };
-            matches!(loc.building, Building::Skolavorduhollt)
-        }).count();
+    fn show_statistics(&mut self, ui: &mut egui::Ui) {
+        let all_equipment = self.db.lock().unwrap().get_all_equipment().unwrap_or_default();
+        // An unfinished date shows today's book values until it can be read
+        let date_result = equipment_info::parse_date(&self.stats_date);
+        let date = date_result.as_ref().copied().unwrap_or_else(|_| chrono::Local::now().date_naive());
+        // Same counts and purchase prices as the summary sheet of the Excel export
+        let stats = Statistics::as_of(&all_equipment, &self.buildings, &self.depreciation, date);
         
         ui.group(|ui| {
             ui.heading("📊 Tölfræði");
             ui.add_space(5.0);
             
+            ui.horizontal(|ui| {
+                ui.label("Bókfært verð þann:");
+                ui.add(egui::TextEdit::singleline(&mut self.stats_date).hint_text("dd.mm.áááá").desired_width(100.0));
+                if let Err(e) = &date_result {
+                    ui.colored_label(egui::Color32::from_rgb(211, 47, 47), e.to_string());
+                }
+            });
+            ui.add_space(5.0);
+            
             egui::Grid::new("stats_grid")
-                .num_columns(2)
+                .num_columns(4)
                 .spacing([40.0, 4.0])
                 .striped(true)
                 .show(ui, |ui| {
                     ui.label("📦 Heildarfjöldi búnaðar:");
-                    ui.label(format!("{}", total_count));
+                    ui.label(format!("{}", stats.total.count));
                     ui.end_row();
                     
                     ui.label("💰 Heildarverðmæti:");
-                    ui.label(format!("{} kr.", total_value));
+                    ui.label("");
+                    ui.label(format!("{} kr.", stats.total.value));
+                    ui.label(format!("{} kr. bókfært", stats.total.book_value));
                     ui.end_row();
                     
                     ui.separator();
                     ui.separator();
-                    ui.end_row();
-                    
-                    ui.label("■ Borð:");
-                    ui.label(format!("{} ({:.1}%)", table_count, (table_count as f32 / total_count as f32) * 100.0));
-                    ui.end_row();
-                    
-                    ui.label("💺 Stólar:");
-                    ui.label(format!("{} ({:.1}%)", chair_count, (chair_count as f32 / total_count as f32) * 100.0));
-                    ui.end_row();
-                    
-                    ui.label("📽 Skjávarpar");
-                    ui.label(format!("{} ({:.1}%)", projector_count, (projector_count as f32 / total_count as f32) * 100.0));
-                    ui.end_row();
-                    
                     ui.separator();
                     ui.separator();
                     ui.end_row();
                     
-                    ui.label("🏢 Hafnarfjörður:");
-                    ui.label(format!("{} ({:.1}%)", ha_count, (ha_count as f32 / total_count as f32) * 100.0));
-                    ui.end_row();
+                    for (type_name, tally) in &stats.by_type {
+                        let icon = match type_name.as_str() {
+                            "Borð" => "■",
+                            "Stóll" => "💺",
+                            "Skjávarpi" => "📽",
+                            "Tölva" => "💻",
+                            "Skjár" => "🖥",
+                            _ => "🗂",
+                        };
+                        ui.label(format!("{} {}:", icon, type_name));
+                        ui.label(format!("{} ({:.1}%)", tally.count, stats.share(*tally)));
+                        ui.label(format!("{} kr.", tally.value));
+                        ui.label(format!("{} kr. bókfært", tally.book_value));
+                        ui.end_row();
+                    }
                     
-                    ui.label("🏢 Háteigsvegur:");
-                    ui.label(format!("{} ({:.1}%)", h_count, (h_count as f32 / total_count as f32) * 100.0));
+                    ui.separator();
+                    ui.separator();
+                    ui.separator();
+                    ui.separator();
                     ui.end_row();
                     
-                    ui.label("🏢 Skólavörðuholt:");
-                    ui.label(format!("{} ({:.1}%)", s_count, (s_count as f32 / total_count as f32) * 100.0));
-                    ui.end_row();
+                    for (building, tally) in &stats.by_building {
+                        ui.label(format!("🏢 {}:", building.name));
+                        ui.label(format!("{} ({:.1}%)", tally.count, stats.share(*tally)));
+                        ui.label(format!("{} kr.", tally.value));
+                        ui.label(format!("{} kr. bókfært", tally.book_value));
+                        ui.end_row();
+                    }
                 });
+            
+            ui.add_space(5.0);
+            egui::CollapsingHeader::new("⚙ Afskriftir").id_source("depreciation_settings").show(ui, |ui| {
+                ui.label("Línuleg afskrift frá innkaupsdegi, í heilum árum. Tegundir án afskriftartíma og búnaður án innkaupsdags halda innkaupsverði.");
+                self.depreciation_settings(ui);
+            });
+        });
+    }
+    
+    // Depreciation period of every type, each saved on its own
+    fn depreciation_settings(&mut self, ui: &mut egui::Ui) {
+        let types: Vec<EquipmentType> =
+            [EquipmentType::Table, EquipmentType::Chair, EquipmentType::Projector, EquipmentType::Computer, EquipmentType::Monitor]
+                .into_iter()
+                .chain(self.categories.iter().map(|c| EquipmentType::Custom(c.code.clone())))
+                .collect();
+        let mut save = None;
+        egui::Grid::new("depreciation_grid").num_columns(3).spacing([20.0, 4.0]).show(ui, |ui| {
+            for equipment_type in &types {
+                let code = equipment_type.code().to_string();
+                ui.label(equipment_type.name(&self.categories));
+                let text = self.depreciation_inputs.entry(code.clone()).or_default();
+                ui.add(egui::TextEdit::singleline(text).hint_text("ár").desired_width(50.0));
+                let stored = self.depreciation.years_for(&code).map(|y| y.to_string()).unwrap_or_default();
+                if text.trim() != stored && ui.button("💾 Vista").clicked() {
+                    save = Some((code, text.clone()));
+                }
+                ui.end_row();
+            }
         });
+        if let Some((code, text)) = save {
+            self.error_message.clear();
+            self.message.clear();
+            let years = if text.trim().is_empty() { Ok(None) } else { depreciation::parse_years(&text).map(Some) };
+            match years.and_then(|years| self.db.lock().unwrap().set_depreciation_years(&code, years)) {
+                Ok(()) => self.message = "✅ Afskriftartími vistaður".to_string(),
+                Err(e) => self.error_message = format!(" Gat ekki vistað afskriftartíma: {}", e),
+            }
+            self.reload_depreciation();
+        }
     }
     
     fn sort_equipment(&mut self) {
@@ -1225,31 +2909,10 @@ impl EquipmentApp {
                         a.get_type_name().cmp(b.get_type_name())
                     }
                     SortColumn::Location => {
-                        let loc_a = match a {
-                            Equipment::Table(t) => &t.location,
-                            Equipment::Chair(c) => &c.location,
-                            Equipment::Projector(p) => &p.location,
-                        };
-                        let loc_b = match b {
-                            Equipment::Table(t) => &t.location,
-                            Equipment::Chair(c) => &c.location,
-                            Equipment::Projector(p) => &p.location,
-                        };
-                        
-                        format!("{}", loc_a).cmp(&format!("{}", loc_b))
+                        a.get_location().cmp(b.get_location())
                     }
                     SortColumn::Value => {
-                        let val_a = match a {
-                            Equipment::Table(t) => t.value,
-                            Equipment::Chair(c) => c.value,
-                            Equipment::Projector(p) => p.value,
-                        };
-                        let val_b = match b {
-                            Equipment::Table(t) => t.value,
-                            Equipment::Chair(c) => c.value,
-                            Equipment::Projector(p) => p.value,
-                        };
-                        val_a.cmp(&val_b)
+                        a.get_value().cmp(&b.get_value())
                     }
                 };
                 
@@ -1263,27 +2926,16 @@ impl EquipmentApp {
         
         let equipment = {
             let db = self.db.lock().unwrap();
-            let result = match self.display_filter {
+            match self.display_filter {
                 DisplayFilter::All => db.get_all_equipment(),
-                DisplayFilter::ByBuilding => db.get_equipment_by_building(self.display_building),
-                DisplayFilter::ByType => {
-                    let type_name = match self.display_type {
-                        EquipmentType::Table => "Table",
-                        EquipmentType::Chair => "Chair",
-                        EquipmentType::Projector => "Projector",
-                    };
-                    db.get_equipment_by_type(type_name)
-                }
-                DisplayFilter::ByRoom => db.get_equipment_by_room(
-                    self.display_building,
-                    self.display_room_floor,
-                    self.display_room_number,
-                ),
+                DisplayFilter::ByBuilding => db.get_equipment_by_building(&self.display_building),
+                DisplayFilter::ByType => db.get_equipment_by_type(self.display_type.code()),
+                DisplayFilter::ByRoom => db.get_equipment_by_room(&self.display_room),
                 DisplayFilter::ByFloor => {
-                    db.get_equipment_by_floor(self.display_building, self.display_floor)
+                    db.get_equipment_by_floor(&self.display_building, self.display_floor)
                 }
-            };
-            result
+                DisplayFilter::ByCondition => db.get_equipment_by_condition(self.display_condition),
+            }
         };
         
         match equipment {
@@ -1328,40 +2980,174 @@ impl EquipmentApp {
         let path = FileDialog::new().add_filter("JSON", &["json"]).pick_file();
         if path.is_none() { return; }
         let path = path.unwrap();
-        match std::fs::read_to_string(&path) {
+        let equipment = match std::fs::read_to_string(&path) {
             Ok(json) => match serde_json::from_str::<Vec<Equipment>>(&json) {
-                Ok(mut equipment) => {
-                    // If JSON contains IDs, preserve them and reset AUTOINCREMENT accordingly.
-                    let max_id = equipment.iter().filter_map(|e| e.get_id()).max().unwrap_or(0);
-                    let mut inserted = 0;
-                    {
-                        let db = self.db.lock().unwrap();
-                        if let Err(e) = db.clear_all_equipment() {
-                            self.error_message = format!(" Tókst ekki að tæma gagnagrunn: {}", e);
-                            return;
-                        }
-                        for eq in equipment.drain(..) {
-                            if let Some(id) = eq.get_id() {
-                                let _ = db.insert_equipment_with_id(id, &eq);
-                                inserted += 1;
-                            } else {
-                                if db.insert_equipment(&eq).is_ok() { inserted += 1; }
+                Ok(equipment) => equipment,
+                Err(e) => { self.error_message = format!(" Villa við að lesa JSON: {}", e); return; }
+            },
+            Err(e) => { self.error_message = format!(" Villa við að opna skrá: {}", e); return; }
+        };
+        // Nothing is written until the user has seen the differences and picked a mode
+        let plan = ImportPlan::new(&self.db.lock().unwrap(), equipment);
+        match plan {
+            Ok(plan) => {
+                let file_name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+                let errors = plan.invalid.clone();
+                self.import_preview = Some(ImportPreview { file_name, plan, errors });
+            }
+            Err(e) => self.error_message = format!(" Villa við lestur úr gagnagrunni: {}", e),
+        }
+    }
+    
+    fn export_xlsx(&mut self) {
+        self.error_message.clear();
+        self.message.clear();
+        let path = FileDialog::new().set_file_name("bunadarlisti.xlsx").add_filter("Excel", &["xlsx"]).save_file();
+        if path.is_none() { return; }
+        let path = path.unwrap();
+        // The workbook always covers the whole inventory, not just the filtered list
+        let equipment = self.db.lock().unwrap().get_all_equipment();
+        match equipment.and_then(|items| xlsx_export::write_xlsx(&path, &items, &self.buildings)) {
+            Ok(_) => self.message = format!(" Excel skjal vistað í {}", path.display()),
+            Err(e) => self.error_message = format!(" Villa við Excel útflutning: {}", e),
+        }
+    }
+    
+    fn save_to_csv(&mut self) {
+        self.error_message.clear();
+        self.message.clear();
+        let path = FileDialog::new().set_file_name("bunadur.csv").add_filter("CSV", &["csv"]).save_file();
+        if path.is_none() { return; }
+        let path = path.unwrap();
+        // Export exactly what the list shows, with its filter and sorting
+        let result = std::fs::File::create(&path)
+            .map_err(Error::from)
+            .and_then(|file| csv_file::write_csv(std::io::BufWriter::new(file), &self.displayed_equipment));
+        match result {
+            Ok(_) => self.message = format!(" {} línur vistaðar í {}", self.displayed_equipment.len(), path.display()),
+            Err(e) => self.error_message = format!(" Villa við vistun: {}", e),
+        }
+    }
+    
+    fn load_from_csv(&mut self) {
+        self.error_message.clear();
+        self.message.clear();
+        let path = FileDialog::new().add_filter("CSV", &["csv", "txt"]).pick_file();
+        if path.is_none() { return; }
+        let path = path.unwrap();
+        let (equipment, errors) = match std::fs::File::open(&path).map_err(Error::from).and_then(|file| csv_file::read_csv(file, &self.categories)) {
+            Ok(result) => result,
+            Err(e) => { self.error_message = format!(" Villa við að lesa CSV: {}", e); return; }
+        };
+        // Valid rows go through the same preview as JSON; bad lines are listed there
+        let plan = ImportPlan::new(&self.db.lock().unwrap(), equipment);
+        match plan {
+            Ok(plan) => {
+                let file_name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+                let errors = errors.iter().map(|e| e.to_string()).chain(plan.invalid.iter().cloned()).collect();
+                self.import_preview = Some(ImportPreview { file_name, plan, errors });
+            }
+            Err(e) => self.error_message = format!(" Villa við lestur úr gagnagrunni: {}", e),
+        }
+    }
+    
+    fn import_window(&mut self, ctx: &egui::Context) {
+        let Some(ImportPreview { file_name, plan, errors }) = &self.import_preview else { return };
+        let mut apply = false;
+        let mut cancel = false;
+        egui::Window::new(format!("📂 Innflutningur úr {}", file_name))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(560.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.label(plan.summary());
+                if !errors.is_empty() {
+                    egui::CollapsingHeader::new(
+                        egui::RichText::new(format!("❌ Atriði sem verða ekki flutt inn ({})", errors.len()))
+                            .color(egui::Color32::from_rgb(211, 47, 47)),
+                    )
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical().id_source("import_errors").max_height(120.0).show(ui, |ui| {
+                            for error in errors {
+                                ui.label(error);
                             }
-                        }
-                        if let Err(e) = db.reset_equipment_autoincrement(max_id as i64) {
-                            self.error_message = format!("⚠️ Gat ekki stillt id-runu: {}", e);
-                        }
-                    }
-
-                    self.displayed_equipment.clear();
-                    self.search_results.clear();
-                    self.sort_column = None; // default
-                    self.load_equipment();
-                    self.message = format!(" {} búnaður hlaðinn úr {}", inserted, path.display());
+                        });
+                    });
                 }
-                Err(e) => { self.error_message = format!(" Villa við að lesa JSON: {}", e); }
-            },
-            Err(e) => { self.error_message = format!(" Villa við að opna skrá: {}", e); }
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    let list = |ui: &mut egui::Ui, title: String, rows: Vec<String>| {
+                        if rows.is_empty() { return; }
+                        egui::CollapsingHeader::new(title).show(ui, |ui| {
+                            for row in rows {
+                                ui.label(row);
+                            }
+                        });
+                    };
+                    list(ui, format!("➕ Nýir ({})", plan.new.len()), plan.new.iter().map(|e| e.to_string()).collect());
+                    list(
+                        ui,
+                        format!("✏ Breyttir ({})", plan.changed.len()),
+                        plan.changed
+                            .iter()
+                            .map(|(old, new)| format!("ID {}: {}", new.get_id().unwrap_or_default(), describe_changes(old, new)))
+                            .collect(),
+                    );
+                    list(
+                        ui,
+                        format!("⚠ Árekstrar ({})", plan.conflicts.len()),
+                        plan.conflicts
+                            .iter()
+                            .map(|c| match &c.existing {
+                                Some(old) => format!("ID {} – {}: {} ⇒ {}", c.id, c.kind, old, c.incoming),
+                                None => format!("ID {} – {}: {}", c.id, c.kind, c.incoming),
+                            })
+                            .collect(),
+                    );
+                    list(ui, format!("❓ Vantar í skrá ({})", plan.missing.len()), plan.missing.iter().map(|e| e.to_string()).collect());
+                });
+                ui.separator();
+                for mode in ImportMode::all() {
+                    Self::radio_black_value(ui, &mut self.import_mode, mode, &mode.to_string());
+                }
+                ui.label(egui::RichText::new(match self.import_mode {
+                    ImportMode::Replace => "Nýjum bætt við, breytingar og árekstrar skrifaðir yfir, búnaður sem vantar í skrá fer í ruslafötu.",
+                    ImportMode::Merge => "Nýjum bætt við og breytingar vistaðar. Árekstrum og búnaði sem vantar í skrá er ekki breytt.",
+                    ImportMode::AppendAsNew => "Allur búnaður í skránni er skráður með nýjum ID. Engu núverandi er breytt.",
+                }).italics());
+                ui.horizontal(|ui| {
+                    if ui.button("✅ Flytja inn").clicked() { apply = true; }
+                    if ui.button("Hætta við").clicked() { cancel = true; }
+                });
+            });
+        if cancel {
+            self.import_preview = None;
+        } else if apply {
+            self.apply_import();
+        }
+    }
+    
+    fn apply_import(&mut self) {
+        let Some(ImportPreview { file_name, plan, .. }) = self.import_preview.take() else { return };
+        self.error_message.clear();
+        self.message.clear();
+        let result = plan.apply(&self.db.lock().unwrap(), self.import_mode);
+        match result {
+            Ok(changes) => {
+                let count = changes.len();
+                if !changes.is_empty() {
+                    self.undo_stack.record(Change::Batch { label: format!("Innflutningur úr {}", file_name), changes });
+                }
+                self.displayed_equipment.clear();
+                self.search_results.clear();
+                self.sort_column = None; // default
+                self.load_equipment();
+                self.message = format!(" {} úr {}: {} breytingar", self.import_mode, file_name, count);
+            }
+            // The transaction has been rolled back, so nothing changed
+            Err(e) => self.error_message = format!(" Innflutningur mistókst, engu var breytt: {}", e),
         }
     }
 }
@@ -1370,6 +3156,14 @@ impl eframe::App for EquipmentApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
     // Refresh UI frequently so lists stay snappy
     ctx.request_repaint_after(Duration::from_millis(50));
+        // Ctrl+Z / Ctrl+Shift+Z, unless a text field is focused and should get its own undo
+        if !ctx.wants_keyboard_input() {
+            if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z)) {
+                self.redo();
+            } else if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z)) {
+                self.undo();
+            }
+        }
         // Modern light blue color scheme
         let mut style = (*ctx.style()).clone();
         
@@ -1468,10 +3262,8 @@ impl eframe::App for EquipmentApp {
                     ui.label(format!("Fjöldi: {} atriði", sidebar_data.len()));
                     
                     // Sort reset button
-                    if self.sort_column.is_some() {
-                        if ui.button("🔄 Endurstilla röðun").clicked() {
-                            self.sort_column = None;
-                        }
+                    if self.sort_column.is_some() && ui.button("🔄 Endurstilla röðun").clicked() {
+                        self.sort_column = None;
                     }
                     
                     ui.add_space(6.0);
@@ -1485,13 +3277,13 @@ impl eframe::App for EquipmentApp {
                                 SortColumn::Id => a.get_id().unwrap_or(0).cmp(&b.get_id().unwrap_or(0)),
                                 SortColumn::Type => a.get_type_name().cmp(b.get_type_name()),
                                 SortColumn::Location => {
-                                    let loc_a = match a { Equipment::Table(t) => &t.location, Equipment::Chair(c) => &c.location, Equipment::Projector(p) => &p.location };
-                                    let loc_b = match b { Equipment::Table(t) => &t.location, Equipment::Chair(c) => &c.location, Equipment::Projector(p) => &p.location };
-                                    format!("{}", loc_a).cmp(&format!("{}", loc_b))
+                                    let loc_a = a.get_location();
+                                    let loc_b = b.get_location();
+                                    loc_a.cmp(loc_b)
                                 }
                                 SortColumn::Value => {
-                                    let val_a = match a { Equipment::Table(t) => t.value, Equipment::Chair(c) => c.value, Equipment::Projector(p) => p.value };
-                                    let val_b = match b { Equipment::Table(t) => t.value, Equipment::Chair(c) => c.value, Equipment::Projector(p) => p.value };
+                                    let val_a = a.get_value();
+                                    let val_b = b.get_value();
                                     val_a.cmp(&val_b)
                                 }
                             };
@@ -1529,8 +3321,8 @@ impl eframe::App for EquipmentApp {
                                 let row_h = 20.0;
                                 for equipment in &sorted_data {
                                     let id = equipment.get_id().unwrap_or(0);
-                                    let location_str = match equipment { Equipment::Table(t) => format!("{}", t.location), Equipment::Chair(c) => format!("{}", c.location), Equipment::Projector(p) => format!("{}", p.location) };
-                                    let value = match equipment { Equipment::Table(t) => t.value, Equipment::Chair(c) => c.value, Equipment::Projector(p) => p.value };
+                                    let location_str = equipment.get_location().to_string();
+                                    let value = equipment.get_value();
                                     body.row(row_h, |mut row| {
                                         let mut clicked_any = false;
                                         row.col(|ui| { if ui.add_sized([ui.available_width(), row_h], egui::Label::new(id.to_string()).sense(egui::Sense::click())).clicked() { clicked_any = true; } });
@@ -1556,6 +3348,8 @@ impl eframe::App for EquipmentApp {
                 });
         }
 
+        self.import_window(ctx);
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.add_space(4.0);
             
@@ -1606,8 +3400,71 @@ impl eframe::App for EquipmentApp {
                 if print_btn.clicked() {
                     self.current_section = AppSection::Print;
                 }
+                
+                let buildings_btn = ui.selectable_label(
+                    self.current_section == AppSection::Buildings,
+                    egui::RichText::new("🏢 Hús og stofur").size(16.0)
+                );
+                if buildings_btn.hovered() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                }
+                if buildings_btn.clicked() {
+                    self.current_section = AppSection::Buildings;
+                    self.building_confirm_delete = None;
+                }
+                
+                let categories_btn = ui.selectable_label(
+                    self.current_section == AppSection::Categories,
+                    egui::RichText::new("🗂 Flokkar").size(16.0)
+                );
+                if categories_btn.hovered() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                }
+                if categories_btn.clicked() {
+                    self.current_section = AppSection::Categories;
+                    self.category_confirm_delete = None;
+                }
+                
+                let audit_btn = ui.selectable_label(
+                    self.current_section == AppSection::Audit,
+                    egui::RichText::new("📦 Úttekt").size(16.0)
+                );
+                if audit_btn.hovered() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                }
+                if audit_btn.clicked() {
+                    self.current_section = AppSection::Audit;
+                }
+                
+                let bin_btn = ui.selectable_label(
+                    self.current_section == AppSection::RecycleBin,
+                    egui::RichText::new("🗑 Ruslafata").size(16.0)
+                );
+                if bin_btn.hovered() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                }
+                if bin_btn.clicked() {
+                    self.current_section = AppSection::RecycleBin;
+                    self.bin_confirm_purge = None;
+                }
+                
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let redo_hint = self.undo_stack.undone().next().map(|c| c.describe());
+                    let redo_btn = ui.add_enabled(self.undo_stack.can_redo(), egui::Button::new("↷ Endurgera"));
+                    if redo_btn.clicked() {
+                        self.redo();
+                    }
+                    redo_btn.on_hover_text(redo_hint.unwrap_or_else(|| "Ctrl+Shift+Z".to_string()));
+                    let undo_hint = self.undo_stack.done().next().map(|c| c.describe());
+                    let undo_btn = ui.add_enabled(self.undo_stack.can_undo(), egui::Button::new("↶ Afturkalla"));
+                    if undo_btn.clicked() {
+                        self.undo();
+                    }
+                    undo_btn.on_hover_text(undo_hint.unwrap_or_else(|| "Ctrl+Z".to_string()));
+                });
             });
             
+            self.recent_changes(ui);
             ui.separator();
             
             // Messages with better visibility
@@ -1634,6 +3491,10 @@ impl eframe::App for EquipmentApp {
                 AppSection::Edit => self.edit_section(ui),
                 AppSection::Search => self.search_section(ui),
                 AppSection::Print => self.print_section(ui),
+                AppSection::Audit => self.audit_section(ui),
+                AppSection::Buildings => self.buildings_section(ui),
+                AppSection::Categories => self.categories_section(ui),
+                AppSection::RecycleBin => self.recycle_bin_section(ui),
             }
         });
 
@@ -0,0 +1,191 @@
+//! Versioned schema migrations, run by `Database::new`.
+//!
+//! The current version lives in the one-row `schema_version` table. Each entry in
+//! `MIGRATIONS` moves the schema up one version and runs in its own transaction.
+//! Never edit a migration that has shipped; append a new one instead.
+
+use rusqlite::{Connection, OptionalExtension};
+
+const MIGRATIONS: &[&str] = &[
+    // 1: original schema, type-specific attribute stored as text in `extra_data`.
+    // `IF NOT EXISTS` adopts databases created before versioning was added.
+    "CREATE TABLE IF NOT EXISTS equipment (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        type TEXT NOT NULL,
+        building TEXT NOT NULL,
+        floor INTEGER NOT NULL,
+        room INTEGER NOT NULL,
+        value INTEGER NOT NULL,
+        extra_data TEXT NOT NULL
+    );",
+    // 2: typed per-type columns instead of `extra_data`
+    "ALTER TABLE equipment ADD COLUMN seats INTEGER;
+     ALTER TABLE equipment ADD COLUMN chair_type TEXT;
+     ALTER TABLE equipment ADD COLUMN lumens INTEGER;
+     UPDATE equipment SET seats = CAST(extra_data AS INTEGER) WHERE type = 'Table';
+     UPDATE equipment SET chair_type = extra_data WHERE type = 'Chair';
+     UPDATE equipment SET lumens = CAST(extra_data AS INTEGER) WHERE type = 'Projector';
+     ALTER TABLE equipment DROP COLUMN extra_data;
+     CREATE INDEX IF NOT EXISTS idx_equipment_location ON equipment (building, floor, room);
+     CREATE INDEX IF NOT EXISTS idx_equipment_type ON equipment (type);",
+    // 3: audit log; no foreign key so entries outlive deleted equipment
+    "CREATE TABLE equipment_history (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        equipment_id INTEGER NOT NULL,
+        action TEXT NOT NULL,
+        timestamp TEXT NOT NULL,
+        user TEXT NOT NULL,
+        details TEXT NOT NULL
+     );
+     CREATE INDEX idx_history_equipment ON equipment_history (equipment_id);",
+    // 4: soft delete; NULL reason means the item is in service
+    "ALTER TABLE equipment ADD COLUMN retired_reason TEXT;
+     ALTER TABLE equipment ADD COLUMN retired_at TEXT;",
+    // 5: stocktake sessions and what was scanned in them; `report` is JSON, set when finished
+    "CREATE TABLE audits (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        building TEXT NOT NULL,
+        floor INTEGER NOT NULL,
+        room INTEGER NOT NULL,
+        user TEXT NOT NULL,
+        started_at TEXT NOT NULL,
+        finished_at TEXT,
+        report TEXT
+     );
+     CREATE TABLE audit_scans (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        audit_id INTEGER NOT NULL REFERENCES audits (id),
+        text TEXT NOT NULL,
+        equipment_id INTEGER,
+        scanned_at TEXT NOT NULL
+     );
+     CREATE INDEX idx_audit_scans_audit ON audit_scans (audit_id);",
+    // 6: buildings are data; seeded with the original three sites and any other
+    // code already in use, so every stored row still has its building
+    "CREATE TABLE buildings (
+        code TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        address TEXT NOT NULL DEFAULT ''
+     );
+     INSERT INTO buildings (code, name) VALUES
+        ('HA', 'Hafnarfjörður'),
+        ('H', 'Háteigsvegur'),
+        ('S', 'Skólavörðuholt');
+     INSERT OR IGNORE INTO buildings (code, name)
+        SELECT DISTINCT building, building FROM equipment
+        UNION SELECT DISTINCT building, building FROM audits;",
+    // 7: room registry; every room already holding equipment or audited is
+    // registered as 'Other' so existing rows stay valid
+    "CREATE TABLE rooms (
+        building TEXT NOT NULL REFERENCES buildings (code),
+        floor INTEGER NOT NULL,
+        room INTEGER NOT NULL,
+        name TEXT NOT NULL DEFAULT '',
+        room_type TEXT NOT NULL,
+        capacity INTEGER NOT NULL DEFAULT 0,
+        responsible TEXT NOT NULL DEFAULT '',
+        PRIMARY KEY (building, floor, room)
+     );
+     INSERT OR IGNORE INTO rooms (building, floor, room, room_type)
+        SELECT DISTINCT building, floor, room, 'Other' FROM equipment
+        UNION SELECT DISTINCT building, floor, room, 'Other' FROM audits;",
+    // 8: room letter suffixes (`H-2.02a`) and basement floors, stored as negative
+    // numbers. Existing rows keep their floor and room and get no suffix; the rooms
+    // table is rebuilt because the suffix is part of its key
+    "ALTER TABLE equipment ADD COLUMN suffix TEXT NOT NULL DEFAULT '';
+     ALTER TABLE audits ADD COLUMN suffix TEXT NOT NULL DEFAULT '';
+     DROP INDEX IF EXISTS idx_equipment_location;
+     CREATE INDEX idx_equipment_location ON equipment (building, floor, room, suffix);
+     CREATE TABLE rooms_new (
+        building TEXT NOT NULL REFERENCES buildings (code),
+        floor INTEGER NOT NULL,
+        room INTEGER NOT NULL,
+        suffix TEXT NOT NULL DEFAULT '',
+        name TEXT NOT NULL DEFAULT '',
+        room_type TEXT NOT NULL,
+        capacity INTEGER NOT NULL DEFAULT 0,
+        responsible TEXT NOT NULL DEFAULT '',
+        PRIMARY KEY (building, floor, room, suffix)
+     );
+     INSERT INTO rooms_new (building, floor, room, name, room_type, capacity, responsible)
+        SELECT building, floor, room, name, room_type, capacity, responsible FROM rooms;
+     DROP TABLE rooms;
+     ALTER TABLE rooms_new RENAME TO rooms;",
+    // 9: user-defined equipment categories. Items in them keep their category code
+    // in `equipment.type` and their attribute values as a JSON object in `attributes`;
+    // `choices` is a JSON array
+    "CREATE TABLE categories (
+        code TEXT PRIMARY KEY,
+        name TEXT NOT NULL
+     );
+     CREATE TABLE category_attributes (
+        category TEXT NOT NULL REFERENCES categories (code),
+        position INTEGER NOT NULL,
+        key TEXT NOT NULL,
+        label TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        required INTEGER NOT NULL DEFAULT 0,
+        min INTEGER,
+        max INTEGER,
+        choices TEXT NOT NULL DEFAULT '[]',
+        unit TEXT NOT NULL DEFAULT '',
+        PRIMARY KEY (category, key)
+     );
+     ALTER TABLE equipment ADD COLUMN attributes TEXT;",
+    // 10: computers and monitors. Serial number, manufacturer and model are shared by
+    // both; the other columns belong to one of them and are NULL for every other type
+    "ALTER TABLE equipment ADD COLUMN serial_number TEXT;
+     ALTER TABLE equipment ADD COLUMN manufacturer TEXT;
+     ALTER TABLE equipment ADD COLUMN model TEXT;
+     ALTER TABLE equipment ADD COLUMN computer_kind TEXT;
+     ALTER TABLE equipment ADD COLUMN cpu TEXT;
+     ALTER TABLE equipment ADD COLUMN ram_gb INTEGER;
+     ALTER TABLE equipment ADD COLUMN screen_size REAL;
+     ALTER TABLE equipment ADD COLUMN resolution TEXT;
+     CREATE INDEX idx_equipment_serial_number ON equipment (serial_number);",
+    // 11: asset tag and notes. With them, serial number, manufacturer and model are
+    // kept for every type; NULL means not recorded
+    "ALTER TABLE equipment ADD COLUMN asset_tag TEXT;
+     ALTER TABLE equipment ADD COLUMN notes TEXT;
+     CREATE INDEX idx_equipment_asset_tag ON equipment (asset_tag);",
+    // 12: purchase details, and depreciation periods in years by type code
+    // (`Table`, `Chair`, ... or a category code); types without a row are not depreciated
+    "ALTER TABLE equipment ADD COLUMN purchase_date TEXT;
+     ALTER TABLE equipment ADD COLUMN supplier TEXT;
+     ALTER TABLE equipment ADD COLUMN invoice_number TEXT;
+     CREATE TABLE depreciation (
+         type TEXT PRIMARY KEY,
+         years INTEGER NOT NULL CHECK (years > 0)
+     );",
+    // 13: condition of each item (`Condition::to_code`); everything already registered counts as good
+    "ALTER TABLE equipment ADD COLUMN condition TEXT NOT NULL DEFAULT 'Good';
+     CREATE INDEX idx_equipment_condition ON equipment (condition);",
+];
+
+/// Schema version a fully migrated database has
+pub const LATEST_VERSION: u32 = MIGRATIONS.len() as u32;
+
+pub fn current_version(conn: &Connection) -> rusqlite::Result<u32> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_version (version INTEGER NOT NULL)",
+        [],
+    )?;
+    let version: Option<u32> = conn
+        .query_row("SELECT version FROM schema_version", [], |row| row.get(0))
+        .optional()?;
+    Ok(version.unwrap_or(0))
+}
+
+/// Apply every migration newer than the stored version
+pub fn run(conn: &mut Connection) -> rusqlite::Result<()> {
+    let mut version = current_version(conn)?;
+    while (version as usize) < MIGRATIONS.len() {
+        let tx = conn.transaction()?;
+        tx.execute_batch(MIGRATIONS[version as usize])?;
+        version += 1;
+        tx.execute("DELETE FROM schema_version", [])?;
+        tx.execute("INSERT INTO schema_version (version) VALUES (?1)", [version])?;
+        tx.commit()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use crate::equipment_info::EquipmentInfo;
+use crate::error::{Error, Result};
+use crate::location::Location;
+use crate::resolution::Resolution;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Largest screen size accepted, in inches
+pub const MAX_SCREEN_SIZE: f32 = 120.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Monitor {
+    pub id: Option<i64>,
+    pub location: Location,
+    pub value: u32,       // Value in ISK
+    pub screen_size: f32, // Diagonal in inches, e.g. 23.8
+    pub resolution: Resolution,
+    /// The serial number in it is required
+    #[serde(flatten)]
+    pub info: EquipmentInfo,
+}
+
+impl Monitor {
+    /// Monitor with no `info` yet; the serial number goes there before it is saved
+    pub fn new(location: Location, value: u32, screen_size: f32, resolution: Resolution) -> Self {
+        Monitor { id: None, location, value, screen_size, resolution, info: EquipmentInfo::default() }
+    }
+
+    pub fn with_id(mut self, id: i64) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Every monitor needs a serial number and a screen size that makes sense
+    pub fn check(&self) -> Result<()> {
+        if self.info.serial_number.trim().is_empty() {
+            return Err(Error::MissingSerialNumber);
+        }
+        if !(self.screen_size > 0.0 && self.screen_size <= MAX_SCREEN_SIZE) {
+            return Err(Error::InvalidScreenSize(self.screen_size.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Screen size as typed, in inches; a decimal comma is accepted (`23,8`)
+pub fn parse_screen_size(text: &str) -> Result<f32> {
+    let size: f32 = text.trim().replace(',', ".").parse().map_err(|_| Error::InvalidScreenSize(text.trim().to_string()))?;
+    if !(size > 0.0 && size <= MAX_SCREEN_SIZE) {
+        return Err(Error::InvalidScreenSize(text.trim().to_string()));
+    }
+    Ok(size)
+}
+
+impl fmt::Display for Monitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Skjár")?;
+        if let Some(id) = self.id {
+            write!(f, " með ID: {}", id)?;
+        }
+        let name = self.info.product_name();
+        if !name.is_empty() {
+            write!(f, ", {}", name)?;
+        }
+        write!(
+            f,
+            ", raðnúmer {}, kostar {} kr., {} tommur, {} og er staðsettur í {}",
+            self.info.serial_number, self.value, self.screen_size, self.resolution, self.location
+        )
+    }
+}
@@ -0,0 +1,631 @@
+//! Printable PDF documents.
+//!
+//! DejaVu Sans is embedded so Icelandic letters render the same everywhere, and
+//! its glyph widths are used to wrap text to column widths.
+
+use crate::audit::{AuditReport, AuditSession};
+use crate::building::BuildingInfo;
+use crate::equipment::Equipment;
+use crate::error::Result;
+use crate::location::{self, Location};
+use crate::statistics::Statistics;
+use printpdf::path::PaintMode;
+use printpdf::{
+    Color, Greyscale, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point, Rect,
+};
+use std::path::{Path, PathBuf};
+
+const REGULAR: &[u8] = include_bytes!("../assets/fonts/DejaVuSans.ttf");
+const BOLD: &[u8] = include_bytes!("../assets/fonts/DejaVuSans-Bold.ttf");
+
+// A4 portrait, in mm
+const PAGE_W: f32 = 210.0;
+const PAGE_H: f32 = 297.0;
+const MARGIN: f32 = 14.0;
+/// Lowest y the body may reach; below is the page footer
+const BOTTOM: f32 = 20.0;
+const PT_TO_MM: f32 = 25.4 / 72.0;
+
+/// An embedded font and its metrics for measuring text
+struct Font {
+    pdf: IndirectFontRef,
+    face: ttf_parser::Face<'static>,
+}
+
+impl Font {
+    fn load(doc: &PdfDocumentReference, bytes: &'static [u8]) -> Result<Self> {
+        let pdf = doc.add_external_font_with_subsetting(bytes, true)?;
+        // Same bytes printpdf just parsed, so this cannot fail
+        let face = ttf_parser::Face::parse(bytes, 0).expect("embedded font");
+        Ok(Font { pdf, face })
+    }
+
+    /// Width of `text` in mm at `size` pt
+    fn width(&self, text: &str, size: f32) -> f32 {
+        let units: u32 = text
+            .chars()
+            .map(|c| {
+                self.face
+                    .glyph_index(c)
+                    .and_then(|g| self.face.glyph_hor_advance(g))
+                    .map(u32::from)
+                    .unwrap_or(u32::from(self.face.units_per_em()) / 2)
+            })
+            .sum();
+        units as f32 / f32::from(self.face.units_per_em()) * size * PT_TO_MM
+    }
+
+    /// Split `text` into lines no wider than `width` mm, breaking long words if needed
+    fn wrap(&self, text: &str, size: f32, width: f32) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        for word in text.split_whitespace() {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if self.width(&candidate, size) <= width {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            for c in word.chars() {
+                line.push(c);
+                if self.width(&line, size) > width && line.chars().count() > 1 {
+                    line.pop();
+                    lines.push(std::mem::replace(&mut line, c.to_string()));
+                }
+            }
+        }
+        if !line.is_empty() || lines.is_empty() {
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+/// A document being written top to bottom, with a y cursor on the current page
+struct Pages {
+    doc: PdfDocumentReference,
+    layers: Vec<PdfLayerReference>,
+    regular: Font,
+    bold: Font,
+    /// Current baseline, in mm from the bottom of the page
+    y: f32,
+}
+
+impl Pages {
+    fn new(title: &str) -> Result<Self> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_W), Mm(PAGE_H), "Síða 1");
+        let layer = doc.get_page(page).get_layer(layer);
+        let regular = Font::load(&doc, REGULAR)?;
+        let bold = Font::load(&doc, BOLD)?;
+        Ok(Pages { doc, layers: vec![layer], regular, bold, y: PAGE_H - MARGIN })
+    }
+
+    fn layer(&self) -> &PdfLayerReference {
+        self.layers.last().expect("at least one page")
+    }
+
+    fn new_page(&mut self) {
+        let name = format!("Síða {}", self.layers.len() + 1);
+        let (page, layer) = self.doc.add_page(Mm(PAGE_W), Mm(PAGE_H), name);
+        self.layers.push(self.doc.get_page(page).get_layer(layer));
+        self.y = PAGE_H - MARGIN;
+    }
+
+    fn font(&self, bold: bool) -> &Font {
+        if bold { &self.bold } else { &self.regular }
+    }
+
+    fn text(&self, text: &str, size: f32, x: f32, y: f32, bold: bool) {
+        self.layer().use_text(text, size, Mm(x), Mm(y), &self.font(bold).pdf);
+    }
+
+    fn text_right(&self, text: &str, size: f32, right: f32, y: f32, bold: bool) {
+        let x = right - self.font(bold).width(text, size);
+        self.text(text, size, x, y, bold);
+    }
+
+    fn hline(&self, x1: f32, x2: f32, y: f32, thickness: f32) {
+        let layer = self.layer();
+        layer.set_outline_thickness(thickness);
+        layer.add_line(Line {
+            points: vec![(Point::new(Mm(x1), Mm(y)), false), (Point::new(Mm(x2), Mm(y)), false)],
+            is_closed: false,
+        });
+    }
+
+    /// Grey box; text drawn afterwards is black again
+    fn shade(&self, x1: f32, y1: f32, x2: f32, y2: f32, grey: f32) {
+        let layer = self.layer();
+        layer.set_fill_color(Color::Greyscale(Greyscale::new(grey, None)));
+        layer.add_rect(Rect::new(Mm(x1), Mm(y1), Mm(x2), Mm(y2)).with_mode(PaintMode::Fill));
+        layer.set_fill_color(Color::Greyscale(Greyscale::new(0.0, None)));
+    }
+
+    fn fill_rect(&self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        self.layer().add_rect(Rect::new(Mm(x1), Mm(y1), Mm(x2), Mm(y2)).with_mode(PaintMode::Fill));
+    }
+
+    /// Empty square, e.g. a checkbox, with its lower left corner at (x, y)
+    fn square(&self, x: f32, y: f32, size: f32) {
+        let layer = self.layer();
+        layer.set_outline_thickness(0.4);
+        layer.add_rect(Rect::new(Mm(x), Mm(y), Mm(x + size), Mm(y + size)).with_mode(PaintMode::Stroke));
+    }
+
+    /// Footer on every page: `left` text and "Síða x af y"
+    fn number_pages(&self, left: &str) {
+        let total = self.layers.len();
+        for (i, layer) in self.layers.iter().enumerate() {
+            let y = MARGIN - 4.0;
+            layer.use_text(left, 8.0, Mm(MARGIN), Mm(y), &self.regular.pdf);
+            let label = format!("Síða {} af {}", i + 1, total);
+            let x = PAGE_W - MARGIN - self.regular.width(&label, 8.0);
+            layer.use_text(label, 8.0, Mm(x), Mm(y), &self.regular.pdf);
+        }
+    }
+
+    fn finish(self) -> Result<Vec<u8>> {
+        Ok(self.doc.save_to_bytes()?)
+    }
+}
+
+/// Amount with Icelandic thousands separators, e.g. `1.234.567 kr.`
+pub fn format_isk(amount: u64) -> String {
+    let digits = amount.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push('.');
+        }
+        out.push(c);
+    }
+    format!("{} kr.", out)
+}
+
+/// Column of the list report: title, width in mm and whether it is right-aligned
+const REPORT_COLUMNS: [(&str, f32, bool); 6] = [
+    ("ID", 12.0, false),
+    ("Tegund", 22.0, false),
+    ("Staðsetning", 24.0, false),
+    ("Verðmæti", 30.0, true),
+    ("Ástand", 20.0, false),
+    ("Lýsing", PAGE_W - 2.0 * MARGIN - 108.0, false),
+];
+const CELL_PAD: f32 = 1.5;
+const BODY_SIZE: f32 = 8.5;
+const LINE_H: f32 = 4.0;
+
+/// Equipment list report: header with `filter` and date, a table that wraps
+/// long descriptions and continues over pages, and totals at the end.
+pub fn equipment_report(items: &[Equipment], filter: &str) -> Result<Vec<u8>> {
+    let title = "Búnaðarlisti Tækniskólans";
+    let date = chrono::Local::now().format("%d.%m.%Y %H:%M").to_string();
+    let mut pages = Pages::new(title)?;
+
+    pages.y -= 4.0;
+    pages.text(title, 16.0, MARGIN, pages.y, true);
+    pages.y -= 7.0;
+    pages.text(&format!("Sía: {}", filter), 9.5, MARGIN, pages.y, false);
+    pages.text_right(&format!("Dagsetning: {}", date), 9.5, PAGE_W - MARGIN, pages.y, false);
+    pages.y -= 5.0;
+    pages.text(&format!("Fjöldi: {} atriði", items.len()), 9.5, MARGIN, pages.y, false);
+    pages.y -= 6.0;
+    table_header(&mut pages);
+
+    for eq in items {
+        let cells = [
+            eq.get_id().map(|id| id.to_string()).unwrap_or_default(),
+            eq.get_type_name().to_string(),
+            eq.get_location().to_string(),
+            format_isk(u64::from(eq.get_value())),
+            eq.info().condition.to_string(),
+            eq.to_string(),
+        ];
+        // Out-of-service items are printed in bold so they stand out on paper
+        let bold = eq.is_out_of_service();
+        let wrapped: Vec<Vec<String>> = cells
+            .iter()
+            .zip(REPORT_COLUMNS)
+            .map(|(text, (_, width, _))| pages.font(bold).wrap(text, BODY_SIZE, width - 2.0 * CELL_PAD))
+            .collect();
+        let row_h = wrapped.iter().map(Vec::len).max().unwrap_or(1) as f32 * LINE_H + 2.0;
+        if pages.y - row_h < BOTTOM {
+            pages.new_page();
+            table_header(&mut pages);
+        }
+        let mut x = MARGIN;
+        for (lines, (_, width, right)) in wrapped.iter().zip(REPORT_COLUMNS) {
+            for (i, line) in lines.iter().enumerate() {
+                let y = pages.y - 3.5 - i as f32 * LINE_H;
+                if right {
+                    pages.text_right(line, BODY_SIZE, x + width - CELL_PAD, y, bold);
+                } else {
+                    pages.text(line, BODY_SIZE, x + CELL_PAD, y, bold);
+                }
+            }
+            x += width;
+        }
+        pages.y -= row_h;
+        pages.hline(MARGIN, PAGE_W - MARGIN, pages.y, 0.3);
+    }
+
+    // Totals: overall count and value, then count and value per type
+    let stats = Statistics::new(items, &[]);
+    let footer_h = 8.0 + stats.by_type.len() as f32 * 4.5;
+    if pages.y - footer_h < BOTTOM {
+        pages.new_page();
+    }
+    let value_right = MARGIN + REPORT_COLUMNS[..4].iter().map(|c| c.1).sum::<f32>() - CELL_PAD;
+    pages.hline(MARGIN, PAGE_W - MARGIN, pages.y, 1.0);
+    pages.y -= 5.0;
+    pages.text(&format!("Samtals: {} atriði", stats.total.count), 9.5, MARGIN + CELL_PAD, pages.y, true);
+    pages.text_right(&format_isk(stats.total.value), 9.5, value_right, pages.y, true);
+    for (type_name, tally) in &stats.by_type {
+        pages.y -= 4.5;
+        pages.text(&format!("{}: {}", type_name, tally.count), BODY_SIZE, MARGIN + CELL_PAD, pages.y, false);
+        pages.text_right(&format_isk(tally.value), BODY_SIZE, value_right, pages.y, false);
+    }
+
+    pages.number_pages(&format!("{} – {}", title, date));
+    pages.finish()
+}
+
+fn table_header(pages: &mut Pages) {
+    let h = 6.0;
+    pages.shade(MARGIN, pages.y - h, PAGE_W - MARGIN, pages.y, 0.88);
+    let mut x = MARGIN;
+    for (title, width, right) in REPORT_COLUMNS {
+        if right {
+            pages.text_right(title, BODY_SIZE, x + width - CELL_PAD, pages.y - 4.2, true);
+        } else {
+            pages.text(title, BODY_SIZE, x + CELL_PAD, pages.y - 4.2, true);
+        }
+        x += width;
+    }
+    pages.y -= h;
+}
+
+/// Columns of a room sheet: checkbox, ID, type and details
+const SHEET_COLUMNS: [(&str, f32); 4] = [("", 10.0), ("ID", 18.0), ("Tegund", 30.0), ("Lýsing", PAGE_W - 2.0 * MARGIN - 58.0)];
+const SHEET_ROW_H: f32 = 7.0;
+/// Room for the signature lines at the bottom of a sheet
+const SIGNATURE_H: f32 = 36.0;
+
+/// Inventory sheets to post on classroom doors, one page per room (more only if
+/// a room has too many items): the room code in large type, what should be in
+/// the room with a checkbox per item, and signature lines for the yearly check.
+/// Building names are looked up in `buildings`.
+pub fn room_sheets(rooms: &[(Location, Vec<Equipment>)], buildings: &[BuildingInfo]) -> Result<Vec<u8>> {
+    let date = chrono::Local::now().format("%d.%m.%Y").to_string();
+    let mut pages = Pages::new("Stofublöð")?;
+    for (i, (location, items)) in rooms.iter().enumerate() {
+        if i > 0 {
+            pages.new_page();
+        }
+        let building_name = BuildingInfo::name_of(buildings, &location.building);
+        room_sheet(&mut pages, location, &building_name, items, &date);
+    }
+    pages.finish()
+}
+
+/// One PDF per room in `dir`, named after the room (e.g. `H-2.12.pdf`).
+/// Returns the files written.
+pub fn write_room_sheets_to_dir(
+    dir: &Path,
+    rooms: &[(Location, Vec<Equipment>)],
+    buildings: &[BuildingInfo],
+) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    let mut files = Vec::new();
+    for room in rooms {
+        let path = dir.join(format!("{}.pdf", room.0));
+        std::fs::write(&path, room_sheets(std::slice::from_ref(room), buildings)?)?;
+        files.push(path);
+    }
+    Ok(files)
+}
+
+fn room_sheet(pages: &mut Pages, location: &Location, building_name: &str, items: &[Equipment], date: &str) {
+    let footer = format!("Stofublað {} – prentað {}", location, date);
+    let first_page = pages.layers.len();
+
+    pages.y -= 4.0;
+    pages.text("Búnaðarlisti Tækniskólans", 10.0, MARGIN, pages.y, false);
+    pages.text_right(building_name, 10.0, PAGE_W - MARGIN, pages.y, false);
+    pages.y -= 22.0;
+    pages.text(&location.to_string(), 54.0, MARGIN, pages.y, true);
+    pages.y -= 9.0;
+    pages.text(&format!("{}, {}", building_name, location::floor_name(location.floor)), 12.0, MARGIN, pages.y, false);
+    pages.y -= 7.0;
+    let counts: Vec<String> = Statistics::new(items, &[])
+        .by_type
+        .iter()
+        .filter(|(_, tally)| tally.count > 0)
+        .map(|(type_name, tally)| format!("{}: {}", type_name, tally.count))
+        .collect();
+    let summary = if counts.is_empty() { "Enginn búnaður skráður".to_string() } else { counts.join("   ") };
+    pages.text(&format!("Á að vera í stofunni: {}", summary), 10.0, MARGIN, pages.y, false);
+    pages.y -= 6.0;
+    sheet_header(pages);
+
+    for eq in items {
+        if pages.y - SHEET_ROW_H < BOTTOM {
+            pages.new_page();
+            pages.text(&format!("{} (frh.)", location), 14.0, MARGIN, pages.y - 6.0, true);
+            pages.y -= 10.0;
+            sheet_header(pages);
+        }
+        let detail = match eq {
+            Equipment::Table(t) => format!("{} sæti", t.seats),
+            Equipment::Chair(c) => c.chair_type.to_string(),
+            Equipment::Projector(p) => format!("{} lúmens", p.lumens),
+            Equipment::Computer(_) | Equipment::Monitor(_) | Equipment::Custom(_) => {
+                eq.details().into_iter().map(|(label, value)| format!("{}: {}", label, value)).collect::<Vec<_>>().join(", ")
+            }
+        };
+        // Asset tag and serial number, to check against the labels on the item
+        let info = eq.info();
+        let labels = [("Eignanúmer", &info.asset_tag), ("Raðnúmer", &info.serial_number)];
+        let detail = std::iter::once(detail)
+            .chain(labels.iter().filter(|(_, value)| !value.is_empty()).map(|(label, value)| format!("{}: {}", label, value)))
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let cells = [eq.get_id().map(|id| id.to_string()).unwrap_or_default(), eq.get_type_name().to_string(), detail];
+        let baseline = pages.y - 4.8;
+        pages.square(MARGIN + 3.0, baseline - 0.5, 4.0);
+        let mut x = MARGIN + SHEET_COLUMNS[0].1;
+        for (text, (_, width)) in cells.iter().zip(&SHEET_COLUMNS[1..]) {
+            let line = pages.regular.wrap(text, 10.0, width - 2.0 * CELL_PAD).swap_remove(0);
+            pages.text(&line, 10.0, x + CELL_PAD, baseline, false);
+            x += width;
+        }
+        pages.y -= SHEET_ROW_H;
+        pages.hline(MARGIN, PAGE_W - MARGIN, pages.y, 0.3);
+    }
+
+    if pages.y - SIGNATURE_H < BOTTOM {
+        pages.new_page();
+    }
+    pages.y -= 12.0;
+    pages.text("Athugasemdir:", 10.0, MARGIN, pages.y, false);
+    pages.hline(MARGIN + 26.0, PAGE_W - MARGIN, pages.y - 0.5, 0.4);
+    pages.y -= 14.0;
+    pages.text("Yfirfarið af:", 10.0, MARGIN, pages.y, false);
+    pages.hline(MARGIN + 24.0, MARGIN + 100.0, pages.y - 0.5, 0.4);
+    pages.text("Dagsetning:", 10.0, MARGIN + 108.0, pages.y, false);
+    pages.hline(MARGIN + 130.0, PAGE_W - MARGIN, pages.y - 0.5, 0.4);
+
+    for layer in &pages.layers[first_page - 1..] {
+        layer.use_text(footer.as_str(), 8.0, Mm(MARGIN), Mm(MARGIN - 4.0), &pages.regular.pdf);
+    }
+}
+
+fn sheet_header(pages: &mut Pages) {
+    let h = 6.0;
+    pages.shade(MARGIN, pages.y - h, PAGE_W - MARGIN, pages.y, 0.88);
+    let mut x = MARGIN;
+    for (title, width) in SHEET_COLUMNS {
+        pages.text(title, 10.0, x + CELL_PAD, pages.y - 4.3, true);
+        x += width;
+    }
+    pages.y -= h;
+}
+
+/// Discrepancy report of a stocktake: what is missing, what turned up from
+/// elsewhere, unknown scans, and what was found, with signature lines.
+pub fn audit_report(session: &AuditSession, report: &AuditReport) -> Result<Vec<u8>> {
+    let title = format!("Úttekt {}", session.location);
+    let date = chrono::Local::now().format("%d.%m.%Y %H:%M").to_string();
+    let mut pages = Pages::new(&title)?;
+
+    pages.y -= 4.0;
+    pages.text(&title, 18.0, MARGIN, pages.y, true);
+    pages.text_right(&format!("Úttekt nr. {}", session.id), 9.5, PAGE_W - MARGIN, pages.y, false);
+    pages.y -= 7.0;
+    pages.text(&format!("Framkvæmd af: {}", session.user), 9.5, MARGIN, pages.y, false);
+    pages.y -= 5.0;
+    let status = match &session.finished_at {
+        Some(finished) => format!("Hafin: {}   Lokið: {}", session.started_at, finished),
+        None => format!("Hafin: {}   Ekki lokið", session.started_at),
+    };
+    pages.text(&status, 9.5, MARGIN, pages.y, false);
+    pages.y -= 5.0;
+    pages.text(&report.summary(), 9.5, MARGIN, pages.y, true);
+    pages.y -= 4.0;
+
+    let rows = |items: &[Equipment], with_location: bool| -> Vec<[String; 3]> {
+        items
+            .iter()
+            .map(|eq| {
+                let id = eq.get_id().map(|id| id.to_string()).unwrap_or_default();
+                let last = if with_location { format!("Skráð í {}", eq.get_location()) } else { eq.to_string() };
+                [id, eq.get_type_name().to_string(), last]
+            })
+            .collect()
+    };
+    let unknown: Vec<[String; 3]> = report.unknown.iter().map(|text| [String::new(), String::new(), text.clone()]).collect();
+    let sections = [
+        ("Vantar", rows(&report.missing, false)),
+        ("Skráð annars staðar", rows(&report.misplaced, true)),
+        ("Óþekkt", unknown),
+        ("Fannst", rows(&report.found, false)),
+    ];
+    let widths = [16.0, 26.0, PAGE_W - 2.0 * MARGIN - 42.0];
+    for (heading, rows) in sections {
+        if rows.is_empty() {
+            continue;
+        }
+        if pages.y - 16.0 < BOTTOM {
+            pages.new_page();
+        }
+        pages.y -= 8.0;
+        pages.text(&format!("{} ({})", heading, rows.len()), 11.0, MARGIN, pages.y, true);
+        pages.y -= 2.0;
+        pages.hline(MARGIN, PAGE_W - MARGIN, pages.y, 0.6);
+        for cells in rows {
+            let wrapped: Vec<Vec<String>> = cells
+                .iter()
+                .zip(widths)
+                .map(|(text, width)| pages.regular.wrap(text, BODY_SIZE, width - 2.0 * CELL_PAD))
+                .collect();
+            let row_h = wrapped.iter().map(Vec::len).max().unwrap_or(1) as f32 * LINE_H + 2.0;
+            if pages.y - row_h < BOTTOM {
+                pages.new_page();
+            }
+            let mut x = MARGIN;
+            for (lines, width) in wrapped.iter().zip(widths) {
+                for (i, line) in lines.iter().enumerate() {
+                    pages.text(line, BODY_SIZE, x + CELL_PAD, pages.y - 3.5 - i as f32 * LINE_H, false);
+                }
+                x += width;
+            }
+            pages.y -= row_h;
+            pages.hline(MARGIN, PAGE_W - MARGIN, pages.y, 0.2);
+        }
+    }
+
+    if pages.y - 20.0 < BOTTOM {
+        pages.new_page();
+    }
+    pages.y -= 16.0;
+    pages.text("Yfirfarið af:", 10.0, MARGIN, pages.y, false);
+    pages.hline(MARGIN + 24.0, MARGIN + 100.0, pages.y - 0.5, 0.4);
+    pages.text("Dagsetning:", 10.0, MARGIN + 108.0, pages.y, false);
+    pages.hline(MARGIN + 130.0, PAGE_W - MARGIN, pages.y - 0.5, 0.4);
+
+    pages.number_pages(&format!("{} – prentað {}", title, date));
+    pages.finish()
+}
+
+// A4 label sheet with 3 x 8 labels of 70 x 37.125 mm and no gaps (e.g. Avery 3474)
+const LABEL_COLS: usize = 3;
+const LABEL_ROWS: usize = 8;
+const LABEL_W: f32 = PAGE_W / LABEL_COLS as f32;
+const LABEL_H: f32 = PAGE_H / LABEL_ROWS as f32;
+const LABEL_PAD: f32 = 4.0;
+const QR_SIZE: f32 = LABEL_H - 2.0 * LABEL_PAD;
+
+/// Text stored in an item's QR code: `ID;type;location`, e.g. `12;Borð;H-2.12`.
+/// The ID comes first so a scanner typing into a text field gives the ID up to the `;`.
+pub fn label_payload(equipment: &Equipment) -> String {
+    format!(
+        "{};{};{}",
+        equipment.get_id().unwrap_or(0),
+        equipment.get_type_name(),
+        equipment.get_location()
+    )
+}
+
+/// Labels for A4 label sheets, 3 x 8 per page, each with a QR code of
+/// `label_payload` and the ID, type and location as text. `skip` leaves that
+/// many labels blank at the start, for a sheet that is already partly used.
+pub fn equipment_labels(items: &[Equipment], skip: usize) -> Result<Vec<u8>> {
+    let per_page = LABEL_COLS * LABEL_ROWS;
+    let skip = skip % per_page;
+    let mut pages = Pages::new("Merkimiðar")?;
+    for (i, eq) in items.iter().enumerate() {
+        let slot = skip + i;
+        if slot > 0 && slot.is_multiple_of(per_page) {
+            pages.new_page();
+        }
+        let (col, row) = (slot % LABEL_COLS, slot % per_page / LABEL_COLS);
+        let x = col as f32 * LABEL_W;
+        let top = PAGE_H - row as f32 * LABEL_H;
+        label(&pages, eq, x, top)?;
+    }
+    pages.finish()
+}
+
+fn label(pages: &Pages, eq: &Equipment, x: f32, top: f32) -> Result<()> {
+    let code = qrcode::QrCode::new(label_payload(eq))?;
+    let width = code.width();
+    // Two modules of white around the code so scanners find its edges
+    let module = QR_SIZE / (width + 4) as f32;
+    let (qr_x, qr_top) = (x + LABEL_PAD + 2.0 * module, top - LABEL_PAD - 2.0 * module);
+    for qy in 0..width {
+        // One rectangle per horizontal run of dark modules keeps the file small
+        let mut qx = 0;
+        while qx < width {
+            if code[(qx, qy)] == qrcode::Color::Light {
+                qx += 1;
+                continue;
+            }
+            let start = qx;
+            while qx < width && code[(qx, qy)] == qrcode::Color::Dark {
+                qx += 1;
+            }
+            let y = qr_top - (qy + 1) as f32 * module;
+            pages.fill_rect(qr_x + start as f32 * module, y, qr_x + qx as f32 * module, y + module);
+        }
+    }
+
+    let text_x = x + LABEL_PAD + QR_SIZE + 2.0;
+    let text_w = x + LABEL_W - LABEL_PAD - text_x;
+    let id = eq.get_id().map(|id| id.to_string()).unwrap_or_default();
+    let lines = [
+        (format!("#{}", id), 14.0, true),
+        (eq.get_type_name().to_string(), 10.0, false),
+        (eq.get_location().to_string(), 12.0, true),
+        ("Tækniskólinn".to_string(), 7.0, false),
+    ];
+    let mut y = top - LABEL_PAD - 6.0;
+    for (text, size, bold) in lines {
+        let line = pages.font(bold).wrap(&text, size, text_w).swap_remove(0);
+        pages.text(&line, size, text_x, y, bold);
+        y -= size * PT_TO_MM + 2.5;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::building::Building;
+    use crate::table::Table;
+
+    #[test]
+    fn wraps_to_width_and_formats_amounts() {
+        let pages = Pages::new("próf").unwrap();
+        let text = "Borð með ID: 12, kostar 45000 kr., fyrir 4 manns og er staðsett í H-212";
+        let lines = pages.regular.wrap(text, BODY_SIZE, 40.0);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| pages.regular.width(l, BODY_SIZE) <= 40.0));
+        assert_eq!(lines.join(" "), text);
+
+        assert_eq!(format_isk(0), "0 kr.");
+        assert_eq!(format_isk(1234567), "1.234.567 kr.");
+
+        let items = vec![Equipment::Table(Table::new(Location::new(Building::new("H").unwrap(), 2, 12), 45000, 4)); 120];
+        let pdf = equipment_report(&items, "Allur búnaður").unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn labels_fill_sheets_of_24() {
+        let loc = Location::new(Building::new("H").unwrap(), 2, 12);
+        let mut table = Equipment::Table(Table::new(loc, 45000, 4));
+        table.set_id(12);
+        assert_eq!(label_payload(&table), "12;Borð;H-2.12");
+        let pdf = equipment_labels(&vec![table; 30], 20).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn room_sheet_continues_on_a_new_page_when_full() {
+        let loc = Location::new(Building::new("H").unwrap(), 2, 12);
+        let table = Equipment::Table(Table::new(loc.clone(), 45000, 4));
+        let mut pages = Pages::new("próf").unwrap();
+        room_sheet(&mut pages, &loc, "Háteigsvegur", &vec![table.clone(); 3], "1.1.2025");
+        assert_eq!(pages.layers.len(), 1);
+        pages.new_page();
+        room_sheet(&mut pages, &loc, "Háteigsvegur", &vec![table.clone(); 40], "1.1.2025");
+        assert_eq!(pages.layers.len(), 3);
+        assert!(pages.y >= BOTTOM);
+
+        let pdf = room_sheets(&[(loc, vec![table])], &[]).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+}
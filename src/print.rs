@@ -0,0 +1,118 @@
+//! Opening files in the desktop's default program and printing through CUPS.
+//!
+//! `open_file` picks the platform's opener (`open` on macOS, `start` on Windows,
+//! `xdg-open` elsewhere) and reports when it is missing or fails, so callers can
+//! offer to save the file instead. `print_file` sends a file straight to a
+//! printer with `lpr`, where CUPS is installed.
+
+use crate::error::{Error, Result};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long an opener may run before it is assumed to have handed the file over.
+/// Some `xdg-open` fallbacks keep running until the browser is closed.
+const OPEN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Open `path` in the default program for its type, e.g. an HTML file in the browser
+pub fn open_file(path: &Path) -> Result<()> {
+    let mut command = opener(path);
+    let program = command.get_program().to_string_lossy().into_owned();
+    let mut child = command
+        .stdout(Stdio::null())
+        // Not piped: a browser started by the opener may outlive it and still write to stderr
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| Error::CommandFailed { command: program.clone(), reason: e.to_string() })?;
+
+    let started = Instant::now();
+    while started.elapsed() < OPEN_TIMEOUT {
+        if let Some(status) = child.try_wait()? {
+            if status.success() {
+                return Ok(());
+            }
+            return Err(Error::CommandFailed { command: program, reason: status.to_string() });
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn opener(path: &Path) -> Command {
+    let mut command = Command::new("open");
+    command.arg(path);
+    command
+}
+
+#[cfg(windows)]
+fn opener(path: &Path) -> Command {
+    // `start` is built into cmd; the empty string is the window title
+    let mut command = Command::new("cmd");
+    command.args(["/C", "start", ""]).arg(path);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn opener(path: &Path) -> Command {
+    let mut command = Command::new("xdg-open");
+    command.arg(path);
+    command
+}
+
+/// True if `lpr` is installed, i.e. files can be printed directly
+pub fn can_print_directly() -> bool {
+    !cfg!(windows) && find_in_path("lpr").is_some()
+}
+
+/// Printers CUPS knows about, as reported by `lpstat -a`; empty if it is not installed
+pub fn printers() -> Vec<String> {
+    match Command::new("lpstat").arg("-a").stderr(Stdio::null()).output() {
+        Ok(output) if output.status.success() => parse_lpstat(&String::from_utf8_lossy(&output.stdout)),
+        _ => Vec::new(),
+    }
+}
+
+/// Send `path` to `printer`, or to the default printer, with `lpr`
+pub fn print_file(path: &Path, printer: Option<&str>) -> Result<()> {
+    let mut command = Command::new("lpr");
+    if let Some(printer) = printer {
+        command.arg("-P").arg(printer);
+    }
+    let output = command
+        .arg(path)
+        .output()
+        .map_err(|e| Error::CommandFailed { command: "lpr".to_string(), reason: e.to_string() })?;
+    if !output.status.success() {
+        let reason = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(Error::CommandFailed { command: "lpr".to_string(), reason });
+    }
+    Ok(())
+}
+
+/// `lpstat -a` prints one line per printer: `name accepting requests since ...`
+fn parse_lpstat(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths).map(|dir| dir.join(program)).find(|p| p.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_printer_names_and_reports_missing_programs() {
+        let output = "Kennarastofa accepting requests since Mon 01 Sep 2025 08:00:00\n\
+                      HP_LaserJet_M404 accepting requests since Tue 02 Sep 2025 09:30:00\n";
+        assert_eq!(parse_lpstat(output), ["Kennarastofa", "HP_LaserJet_M404"]);
+        assert!(find_in_path("forrit-sem-er-ekki-til").is_none());
+    }
+}
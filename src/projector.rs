@@ -1,33 +1,46 @@
+use crate::equipment_info::EquipmentInfo;
+use crate::error::Error;
 use crate::location::Location;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Projector {
     pub id: Option<i64>,
     pub location: Location,
     pub value: u32, // Value in ISK
     pub lumens: u32,
+    #[serde(flatten)]
+    pub info: EquipmentInfo,
 }
 
 impl Projector {
     pub fn new(location: Location, value: u32, lumens: u32) -> Self {
-        Projector { id: None, location, value, lumens }
+        Projector { id: None, location, value, lumens, info: EquipmentInfo::default() }
     }
 
     pub fn with_id(mut self, id: i64) -> Self {
         self.id = Some(id);
         self
     }
+
+    /// Lumens cannot be 0
+    pub fn check(&self) -> Result<(), Error> {
+        if self.lumens == 0 {
+            return Err(Error::ZeroLumens);
+        }
+        Ok(())
+    }
 }
 
 impl TryFrom<(Location, u32, u32)> for Projector {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(value: (Location, u32, u32)) -> Result<Self, Self::Error> {
         let (location, value_isk, lumens) = value;
-        if lumens == 0 { return Err("Lúmens má ekki vera 0".into()); }
-        Ok(Projector::new(location, value_isk, lumens))
+        let projector = Projector::new(location, value_isk, lumens);
+        projector.check()?;
+        Ok(projector)
     }
 }
 
@@ -0,0 +1,46 @@
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Screen resolution in pixels, e.g. 1920×1080
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Resolution {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Resolution {
+    pub fn new(width: u16, height: u16) -> Self {
+        Resolution { width, height }
+    }
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}×{}", self.width, self.height)
+    }
+}
+
+impl TryFrom<&str> for Resolution {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        // `×` as displayed, or `x` as typed on a keyboard
+        let invalid = || Error::InvalidResolution(value.to_string());
+        let (width, height) = value.trim().split_once(['×', 'x', 'X']).ok_or_else(invalid)?;
+        let width: u16 = width.trim().parse().map_err(|_| invalid())?;
+        let height: u16 = height.trim().parse().map_err(|_| invalid())?;
+        if width == 0 || height == 0 {
+            return Err(invalid());
+        }
+        Ok(Resolution::new(width, height))
+    }
+}
+
+impl TryFrom<String> for Resolution {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Resolution::try_from(value.as_str())
+    }
+}
@@ -0,0 +1,86 @@
+use crate::equipment::Equipment;
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Why an item was taken out of service and moved to the recycle bin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RetireReason {
+    Broken,     // Bilað
+    Sold,       // Selt
+    Lost,       // Týnt
+    WrittenOff, // Afskrifað
+    Other,      // Annað
+}
+
+impl RetireReason {
+    pub fn all() -> Vec<RetireReason> {
+        vec![
+            RetireReason::Broken,
+            RetireReason::Sold,
+            RetireReason::Lost,
+            RetireReason::WrittenOff,
+            RetireReason::Other,
+        ]
+    }
+
+    /// Code stored in the `retired_reason` column
+    pub fn to_code(self) -> &'static str {
+        match self {
+            RetireReason::Broken => "Broken",
+            RetireReason::Sold => "Sold",
+            RetireReason::Lost => "Lost",
+            RetireReason::WrittenOff => "WrittenOff",
+            RetireReason::Other => "Other",
+        }
+    }
+}
+
+impl fmt::Display for RetireReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                RetireReason::Broken => "Bilað",
+                RetireReason::Sold => "Selt",
+                RetireReason::Lost => "Týnt",
+                RetireReason::WrittenOff => "Afskrifað",
+                RetireReason::Other => "Annað",
+            }
+        )
+    }
+}
+
+impl TryFrom<&str> for RetireReason {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        // Accept stored codes and Icelandic names, any case
+        match value.trim().to_lowercase().as_str() {
+            "broken" | "bilað" | "bilad" => Ok(RetireReason::Broken),
+            "sold" | "selt" => Ok(RetireReason::Sold),
+            "lost" | "týnt" | "tynt" => Ok(RetireReason::Lost),
+            "writtenoff" | "written-off" | "afskrifað" | "afskrifad" => Ok(RetireReason::WrittenOff),
+            "other" | "annað" | "annad" => Ok(RetireReason::Other),
+            _ => Err(Error::UnknownRetireReason(value.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for RetireReason {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        RetireReason::try_from(value.as_str())
+    }
+}
+
+/// An item in the recycle bin
+#[derive(Debug, Clone)]
+pub struct RetiredEquipment {
+    pub equipment: Equipment,
+    pub reason: RetireReason,
+    /// Local time, `YYYY-MM-DD HH:MM:SS`
+    pub retired_at: String,
+}
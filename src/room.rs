@@ -0,0 +1,52 @@
+use crate::location::Location;
+use crate::room_type::RoomType;
+use std::fmt;
+
+/// A registered room. Equipment can only be placed in rooms in the `rooms` table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Room {
+    pub location: Location,
+    /// Friendly name, e.g. "Tölvuver 3"; may be empty
+    pub name: String,
+    pub room_type: RoomType,
+    /// Number of people the room is meant to seat
+    pub capacity: u16,
+    /// Teacher or staff member responsible for the room; may be empty
+    pub responsible: String,
+}
+
+impl Room {
+    /// Room with no name, capacity or responsible teacher yet
+    pub fn new(location: Location, room_type: RoomType) -> Self {
+        Room {
+            location,
+            name: String::new(),
+            room_type,
+            capacity: 0,
+            responsible: String::new(),
+        }
+    }
+
+    /// Case-insensitive match on the room code, name, type or responsible teacher, for the room picker.
+    /// A complete code in the older form, e.g. `H-212`, finds its room too.
+    pub fn matches_query(&self, query: &str) -> bool {
+        if Location::try_from(query.to_uppercase()).is_ok_and(|loc| loc == self.location) {
+            return true;
+        }
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || [self.location.to_string(), self.name.clone(), self.room_type.to_string(), self.responsible.clone()]
+                .iter()
+                .any(|field| field.to_lowercase().contains(&query))
+    }
+}
+
+impl fmt::Display for Room {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.name.is_empty() {
+            write!(f, "{} ({})", self.location, self.room_type)
+        } else {
+            write!(f, "{} {} ({})", self.location, self.name, self.room_type)
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What a room is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoomType {
+    Classroom,   // Kennslustofa
+    ComputerLab, // Tölvuver
+    Lab,         // Verkleg stofa (raungreinar, rafmagn)
+    LectureHall, // Fyrirlestrasalur
+    Workshop,    // Verkstæði
+    Office,      // Skrifstofa
+    Other,       // Annað
+}
+
+impl RoomType {
+    pub fn all() -> Vec<RoomType> {
+        vec![
+            RoomType::Classroom,
+            RoomType::ComputerLab,
+            RoomType::Lab,
+            RoomType::LectureHall,
+            RoomType::Workshop,
+            RoomType::Office,
+            RoomType::Other,
+        ]
+    }
+
+    /// Code stored in the `rooms.room_type` column
+    pub fn to_code(self) -> &'static str {
+        match self {
+            RoomType::Classroom => "Classroom",
+            RoomType::ComputerLab => "ComputerLab",
+            RoomType::Lab => "Lab",
+            RoomType::LectureHall => "LectureHall",
+            RoomType::Workshop => "Workshop",
+            RoomType::Office => "Office",
+            RoomType::Other => "Other",
+        }
+    }
+}
+
+impl fmt::Display for RoomType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                RoomType::Classroom => "Kennslustofa",
+                RoomType::ComputerLab => "Tölvuver",
+                RoomType::Lab => "Verkleg stofa",
+                RoomType::LectureHall => "Fyrirlestrasalur",
+                RoomType::Workshop => "Verkstæði",
+                RoomType::Office => "Skrifstofa",
+                RoomType::Other => "Annað",
+            }
+        )
+    }
+}
+
+impl TryFrom<&str> for RoomType {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        // Accept stored codes and Icelandic names, any case
+        match value.trim().to_lowercase().as_str() {
+            "classroom" | "kennslustofa" => Ok(RoomType::Classroom),
+            "computerlab" | "tölvuver" | "tolvuver" => Ok(RoomType::ComputerLab),
+            "lab" | "verkleg stofa" | "verklegstofa" => Ok(RoomType::Lab),
+            "lecturehall" | "fyrirlestrasalur" => Ok(RoomType::LectureHall),
+            "workshop" | "verkstæði" | "verkstaedi" => Ok(RoomType::Workshop),
+            "office" | "skrifstofa" => Ok(RoomType::Office),
+            "other" | "annað" | "annad" => Ok(RoomType::Other),
+            _ => Err(Error::UnknownRoomType(value.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for RoomType {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        RoomType::try_from(value.as_str())
+    }
+}
@@ -0,0 +1,206 @@
+//! Room planning: do the chairs in each room match the seats at its tables?
+//!
+//! A room needs one chair per person it is meant for. That is the room's
+//! registered capacity when one is set, otherwise the number of seats at its
+//! tables. Rooms with surplus chairs are paired with rooms that lack them, in
+//! the same building first, so chairs can be moved instead of bought.
+
+use crate::equipment::Equipment;
+use crate::location::Location;
+use crate::room::Room;
+use std::fmt;
+
+/// Seats and chairs in one room
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoomSeating {
+    pub location: Location,
+    /// Room name from the registry, may be empty
+    pub name: String,
+    /// Registered capacity; 0 when not known
+    pub capacity: u16,
+    /// Total `Table::seats` of the tables in the room
+    pub seats: u32,
+    pub chairs: u32,
+}
+
+impl RoomSeating {
+    /// Number of chairs the room should have
+    pub fn needed(&self) -> u32 {
+        if self.capacity > 0 {
+            u32::from(self.capacity)
+        } else {
+            self.seats
+        }
+    }
+
+    pub fn missing_chairs(&self) -> u32 {
+        self.needed().saturating_sub(self.chairs)
+    }
+
+    /// Chairs that could be moved to another room
+    pub fn surplus_chairs(&self) -> u32 {
+        self.chairs.saturating_sub(self.needed())
+    }
+
+    /// Table seats short of the registered capacity; 0 for rooms without tables or capacity
+    pub fn missing_table_seats(&self) -> u32 {
+        if self.seats == 0 {
+            0
+        } else {
+            u32::from(self.capacity).saturating_sub(self.seats)
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.missing_chairs() == 0 && self.surplus_chairs() == 0 && self.missing_table_seats() == 0
+    }
+
+    /// Short Icelandic description, e.g. "Vantar 4 stóla"
+    pub fn status(&self) -> String {
+        let mut notes = Vec::new();
+        match (self.missing_chairs(), self.surplus_chairs()) {
+            (0, 0) => {}
+            (0, surplus) => notes.push(format!("{} {} umfram", surplus, if singular(surplus) { "stóll" } else { "stólar" })),
+            (missing, _) => notes.push(format!("Vantar {} {}", missing, if singular(missing) { "stól" } else { "stóla" })),
+        }
+        if self.missing_table_seats() > 0 {
+            notes.push(format!("Borð rúma {} af {}", self.seats, self.capacity));
+        }
+        if notes.is_empty() {
+            "Í lagi".to_string()
+        } else {
+            notes.join(", ")
+        }
+    }
+}
+
+/// Icelandic nouns take the singular after 1, 21, 31, ... but not after 11
+fn singular(n: u32) -> bool {
+    n % 10 == 1 && n % 100 != 11
+}
+
+/// Suggestion to carry chairs from one room to another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChairMove {
+    pub from: Location,
+    pub to: Location,
+    pub count: u32,
+}
+
+impl fmt::Display for ChairMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let noun = if singular(self.count) { "stól" } else { "stóla" };
+        write!(f, "Flytja {} {} úr {} í {}", self.count, noun, self.from, self.to)
+    }
+}
+
+/// Seating of every room with tables or chairs, and of every registered room with a capacity, in location order
+pub fn seating_report(items: &[Equipment], rooms: &[Room]) -> Vec<RoomSeating> {
+    let mut report: Vec<RoomSeating> = rooms
+        .iter()
+        .filter(|r| r.capacity > 0)
+        .map(|r| RoomSeating { location: r.location.clone(), name: r.name.clone(), capacity: r.capacity, seats: 0, chairs: 0 })
+        .collect();
+    for eq in items {
+        let (seats, chairs) = match eq {
+            Equipment::Table(t) => (u32::from(t.seats), 0),
+            Equipment::Chair(_) => (0, 1),
+            Equipment::Projector(_) | Equipment::Computer(_) | Equipment::Monitor(_) | Equipment::Custom(_) => continue,
+        };
+        let location = eq.get_location();
+        let index = match report.iter().position(|r| &r.location == location) {
+            Some(index) => index,
+            None => {
+                let room = rooms.iter().find(|r| &r.location == location);
+                report.push(RoomSeating {
+                    location: location.clone(),
+                    name: room.map(|r| r.name.clone()).unwrap_or_default(),
+                    capacity: room.map_or(0, |r| r.capacity),
+                    seats: 0,
+                    chairs: 0,
+                });
+                report.len() - 1
+            }
+        };
+        report[index].seats += seats;
+        report[index].chairs += chairs;
+    }
+    report.sort_by(|a, b| a.location.cmp(&b.location));
+    report
+}
+
+/// Pair surplus chairs with rooms that lack them, within a building first and then across buildings
+pub fn suggest_moves(report: &[RoomSeating]) -> Vec<ChairMove> {
+    let mut surplus: Vec<(&Location, u32)> =
+        report.iter().filter(|r| r.surplus_chairs() > 0).map(|r| (&r.location, r.surplus_chairs())).collect();
+    let mut moves = Vec::new();
+    for same_building in [true, false] {
+        for room in report.iter().filter(|r| r.missing_chairs() > 0) {
+            let already: u32 = moves.iter().filter(|m: &&ChairMove| m.to == room.location).map(|m| m.count).sum();
+            let mut missing = room.missing_chairs() - already;
+            for (from, left) in surplus.iter_mut() {
+                if missing == 0 {
+                    break;
+                }
+                if *left == 0 || (same_building && from.building != room.location.building) {
+                    continue;
+                }
+                let count = missing.min(*left);
+                *left -= count;
+                missing -= count;
+                moves.push(ChairMove { from: (*from).clone(), to: room.location.clone(), count });
+            }
+        }
+    }
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chair::Chair;
+    use crate::chair_type::ChairType;
+    use crate::room_type::RoomType;
+    use crate::table::Table;
+
+    fn furnish(location: &str, tables: &[u8], chairs: usize) -> Vec<Equipment> {
+        let loc = Location::try_from(location).unwrap();
+        let mut items: Vec<Equipment> = tables.iter().map(|&s| Equipment::Table(Table::new(loc.clone(), 1000, s))).collect();
+        items.extend((0..chairs).map(|_| Equipment::Chair(Chair::new(loc.clone(), 1000, ChairType::Skolastoll))));
+        items
+    }
+
+    #[test]
+    fn flags_rooms_and_suggests_moves() {
+        let mut items = furnish("H-2.12", &[4, 4], 6);
+        items.extend(furnish("H-2.14", &[2], 5));
+        items.extend(furnish("S-1.01", &[4], 7));
+        let mut lab = Room::new(Location::try_from("H-3.01").unwrap(), RoomType::Lab);
+        lab.capacity = 3;
+        let mut big = Room::new(Location::try_from("H-2.14").unwrap(), RoomType::Classroom);
+        big.capacity = 4;
+
+        let report = seating_report(&items, &[lab, big]);
+        let status: Vec<(String, String)> = report.iter().map(|r| (r.location.to_string(), r.status())).collect();
+        assert_eq!(
+            status,
+            [
+                ("H-2.12".to_string(), "Vantar 2 stóla".to_string()),
+                ("H-2.14".to_string(), "1 stóll umfram, Borð rúma 2 af 4".to_string()),
+                ("H-3.01".to_string(), "Vantar 3 stóla".to_string()),
+                ("S-1.01".to_string(), "3 stólar umfram".to_string()),
+            ]
+        );
+
+        // H-2.14's spare chair stays in H; S-1.01 makes up the rest
+        let moves: Vec<String> = suggest_moves(&report).iter().map(|m| m.to_string()).collect();
+        assert_eq!(
+            moves,
+            [
+                "Flytja 1 stól úr H-2.14 í H-2.12",
+                "Flytja 1 stól úr S-1.01 í H-2.12",
+                "Flytja 2 stóla úr S-1.01 í H-3.01",
+            ]
+        );
+    }
+}
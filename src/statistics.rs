@@ -0,0 +1,125 @@
+use crate::building::BuildingInfo;
+use crate::depreciation::Depreciation;
+use crate::equipment::Equipment;
+use chrono::NaiveDate;
+
+/// Count and total value of one group of equipment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub count: usize,
+    /// Purchase price in ISK; u64 so large inventories cannot overflow
+    pub value: u64,
+    /// ISK on the books on the date the statistics are for
+    pub book_value: u64,
+}
+
+impl Tally {
+    fn add(&mut self, equipment: &Equipment, book_value: u32) {
+        self.count += 1;
+        self.value += u64::from(equipment.get_value());
+        self.book_value += u64::from(book_value);
+    }
+}
+
+/// Totals by type and by building, as shown under "Tölfræði" and in the XLSX summary
+#[derive(Debug, Clone, Default)]
+pub struct Statistics {
+    pub total: Tally,
+    /// Keyed by `Equipment::get_type_name`: the built-in types, then categories in the order they turn up
+    pub by_type: Vec<(String, Tally)>,
+    /// Every building in `buildings`, in that order, then any code that is not in it
+    pub by_building: Vec<(BuildingInfo, Tally)>,
+}
+
+impl Statistics {
+    /// Totals at purchase price; the book value is the same
+    pub fn new(items: &[Equipment], buildings: &[BuildingInfo]) -> Self {
+        Statistics::as_of(items, buildings, &Depreciation::default(), NaiveDate::MAX)
+    }
+
+    /// Totals with each item's book value on `date`
+    pub fn as_of(items: &[Equipment], buildings: &[BuildingInfo], depreciation: &Depreciation, date: NaiveDate) -> Self {
+        let mut stats = Statistics {
+            total: Tally::default(),
+            by_type: ["Borð", "Stóll", "Skjávarpi", "Tölva", "Skjár"].iter().map(|t| (t.to_string(), Tally::default())).collect(),
+            by_building: buildings.iter().map(|b| (b.clone(), Tally::default())).collect(),
+        };
+        for eq in items {
+            let book_value = depreciation.book_value(eq, date);
+            stats.total.add(eq, book_value);
+            match stats.by_type.iter_mut().find(|(t, _)| t == eq.get_type_name()) {
+                Some((_, tally)) => tally.add(eq, book_value),
+                None => {
+                    let mut tally = Tally::default();
+                    tally.add(eq, book_value);
+                    stats.by_type.push((eq.get_type_name().to_string(), tally));
+                }
+            }
+            let building = &eq.get_location().building;
+            match stats.by_building.iter_mut().find(|(b, _)| &b.building == building) {
+                Some((_, tally)) => tally.add(eq, book_value),
+                None => {
+                    let info = BuildingInfo { building: building.clone(), name: building.to_string(), address: String::new() };
+                    let mut tally = Tally::default();
+                    tally.add(eq, book_value);
+                    stats.by_building.push((info, tally));
+                }
+            }
+        }
+        stats
+    }
+
+    /// Percentage of all items, 0 when there are none
+    pub fn share(&self, tally: Tally) -> f32 {
+        if self.total.count == 0 {
+            0.0
+        } else {
+            tally.count as f32 / self.total.count as f32 * 100.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::building::Building;
+    use crate::chair::Chair;
+    use crate::chair_type::ChairType;
+    use crate::location::Location;
+    use crate::table::Table;
+
+    #[test]
+    fn tallies_by_type_and_building() {
+        let items = vec![
+            Equipment::Table(Table::new(Location::new(Building::new("H").unwrap(), 2, 12), 45000, 4)),
+            Equipment::Chair(Chair::new(Location::new(Building::new("H").unwrap(), 2, 12), 9000, ChairType::Annad)),
+            Equipment::Chair(Chair::new(Location::new(Building::new("HA").unwrap(), 1, 1), 4_000_000_000, ChairType::Annad)),
+        ];
+        let buildings = [
+            BuildingInfo { building: Building::new("H").unwrap(), name: "Háteigsvegur".into(), address: String::new() },
+            BuildingInfo { building: Building::new("S").unwrap(), name: "Skólavörðuholt".into(), address: String::new() },
+        ];
+        let stats = Statistics::new(&items, &buildings);
+        assert_eq!(stats.total, Tally { count: 3, value: 4_000_054_000, book_value: 4_000_054_000 });
+        assert_eq!(stats.by_type[1], ("Stóll".to_string(), Tally { count: 2, value: 4_000_009_000, book_value: 4_000_009_000 }));
+        assert_eq!(stats.by_building[0], (buildings[0].clone(), Tally { count: 2, value: 54000, book_value: 54000 }));
+        assert_eq!(stats.by_building[1].1, Tally::default());
+        // A code missing from the list still gets counted, under its code
+        assert_eq!(stats.by_building[2].0.name, "HA");
+        assert_eq!(Statistics::new(&[], &[]).share(Tally::default()), 0.0);
+
+        // Chairs written off over 5 years, the table not at all
+        let mut items = items;
+        for eq in &mut items {
+            let mut info = eq.info().clone();
+            info.purchase_date = NaiveDate::from_ymd_opt(2020, 1, 1);
+            eq.set_info(info);
+        }
+        let mut depreciation = Depreciation::default();
+        depreciation.years.insert("Chair".into(), 5);
+        let stats = Statistics::as_of(&items, &buildings, &depreciation, NaiveDate::from_ymd_opt(2025, 6, 1).unwrap());
+        assert_eq!(stats.by_type[0].1.book_value, 45000);
+        assert_eq!(stats.by_type[1].1.book_value, 0);
+        assert_eq!(stats.total.value, 4_000_054_000);
+    }
+}
@@ -1,33 +1,46 @@
+use crate::equipment_info::EquipmentInfo;
+use crate::error::Error;
 use crate::location::Location;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Table {
     pub id: Option<i64>,
     pub location: Location,
     pub value: u32, // Value in ISK
     pub seats: u8,  // Number of seats
+    #[serde(flatten)]
+    pub info: EquipmentInfo,
 }
 
 impl Table {
     pub fn new(location: Location, value: u32, seats: u8) -> Self {
-        Table { id: None, location, value, seats }
+        Table { id: None, location, value, seats, info: EquipmentInfo::default() }
     }
 
     pub fn with_id(mut self, id: i64) -> Self {
         self.id = Some(id);
         self
     }
+
+    /// A table has at least one seat
+    pub fn check(&self) -> Result<(), Error> {
+        if self.seats == 0 {
+            return Err(Error::ZeroSeats);
+        }
+        Ok(())
+    }
 }
 
 impl TryFrom<(Location, u32, u8)> for Table {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(value: (Location, u32, u8)) -> Result<Self, Self::Error> {
         let (location, value_isk, seats) = value;
-        if seats == 0 { return Err("Fjöldi sæta má ekki vera 0".into()); }
-        Ok(Table::new(location, value_isk, seats))
+        let table = Table::new(location, value_isk, seats);
+        table.check()?;
+        Ok(table)
     }
 }
 
@@ -0,0 +1,184 @@
+//! Undo/redo of changes made through `Database`.
+//!
+//! Each `Change` records enough to reverse itself using the normal `Database`
+//! methods, so undoing also shows up in the history log.
+
+use crate::database::Database;
+use crate::equipment::Equipment;
+use crate::error::Result;
+use crate::retire_reason::RetireReason;
+
+/// Number of changes kept on the undo stack
+const MAX_UNDO: usize = 50;
+
+#[derive(Debug, Clone)]
+pub enum Change {
+    /// New item; undo removes it permanently. Items are boxed to keep `Change` small.
+    Registered { id: i64, equipment: Box<Equipment> },
+    /// Fields and/or location changed from `before` to `after`
+    Edited { id: i64, before: Box<Equipment>, after: Box<Equipment> },
+    /// Moved to the recycle bin
+    Retired { id: i64, reason: RetireReason },
+    /// Brought back from the recycle bin, where it had been for `reason`
+    Restored { id: i64, reason: RetireReason },
+    /// Several changes undone and redone together (e.g. a JSON import)
+    Batch { label: String, changes: Vec<Change> },
+}
+
+impl Change {
+    pub fn undo(&self, db: &Database) -> Result<()> {
+        match self {
+            Change::Registered { id, .. } => db.delete_equipment(*id),
+            Change::Edited { id, before, after } => apply_version(db, *id, after, before),
+            Change::Retired { id, .. } => db.restore_equipment(*id),
+            Change::Restored { id, reason } => db.retire_equipment(*id, *reason),
+            Change::Batch { changes, .. } => db.transaction(|db| {
+                for change in changes.iter().rev() {
+                    change.undo(db)?;
+                }
+                Ok(())
+            }),
+        }
+    }
+
+    pub fn redo(&self, db: &Database) -> Result<()> {
+        match self {
+            Change::Registered { id, equipment } => db.insert_equipment_with_id(*id, equipment),
+            Change::Edited { id, before, after } => apply_version(db, *id, before, after),
+            Change::Retired { id, reason } => db.retire_equipment(*id, *reason),
+            Change::Restored { id, .. } => db.restore_equipment(*id),
+            Change::Batch { changes, .. } => db.transaction(|db| {
+                for change in changes {
+                    change.redo(db)?;
+                }
+                Ok(())
+            }),
+        }
+    }
+
+    /// Short Icelandic description for the list of recent actions
+    pub fn describe(&self) -> String {
+        match self {
+            Change::Registered { id, equipment } => {
+                format!("Skráði {} (ID {}) í {}", equipment.get_type_name(), id, equipment.get_location())
+            }
+            Change::Edited { id, before, after } if is_move(before, after) => {
+                format!("Flutti ID {}: {} → {}", id, before.get_location(), after.get_location())
+            }
+            Change::Edited { id, .. } => format!("Breytti ID {}", id),
+            Change::Retired { id, reason } => format!("Afskráði ID {} ({})", id, reason),
+            Change::Restored { id, .. } => format!("Endurheimti ID {}", id),
+            Change::Batch { label, changes } => format!("{} ({} breytingar)", label, changes.len()),
+        }
+    }
+}
+
+/// True if only the location differs between the two versions
+fn is_move(before: &Equipment, after: &Equipment) -> bool {
+    let mut moved = before.clone();
+    moved.set_location(after.get_location().clone());
+    moved == *after && before.get_location() != after.get_location()
+}
+
+/// Go from `from` to `to`, as a move when only the location differs
+fn apply_version(db: &Database, id: i64, from: &Equipment, to: &Equipment) -> Result<()> {
+    if is_move(from, to) {
+        db.update_location(id, to.get_location())
+    } else {
+        db.update_equipment(id, to)
+    }
+}
+
+/// Undo and redo stacks; recording a new change clears the redo stack
+#[derive(Debug, Default)]
+pub struct UndoStack {
+    done: Vec<Change>,
+    undone: Vec<Change>,
+}
+
+impl UndoStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, change: Change) {
+        self.done.push(change);
+        if self.done.len() > MAX_UNDO {
+            self.done.remove(0);
+        }
+        self.undone.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Undo the latest change. Returns its description, or `None` if there was nothing to undo.
+    /// On error the stacks are left unchanged.
+    pub fn undo(&mut self, db: &Database) -> Result<Option<String>> {
+        let Some(change) = self.done.last() else { return Ok(None) };
+        change.undo(db)?;
+        let change = self.done.pop().unwrap();
+        let description = change.describe();
+        self.undone.push(change);
+        Ok(Some(description))
+    }
+
+    pub fn redo(&mut self, db: &Database) -> Result<Option<String>> {
+        let Some(change) = self.undone.last() else { return Ok(None) };
+        change.redo(db)?;
+        let change = self.undone.pop().unwrap();
+        let description = change.describe();
+        self.done.push(change);
+        Ok(Some(description))
+    }
+
+    /// Recorded changes, newest first
+    pub fn done(&self) -> impl Iterator<Item = &Change> {
+        self.done.iter().rev()
+    }
+
+    /// Undone changes that can be redone, next-to-redo first
+    pub fn undone(&self) -> impl Iterator<Item = &Change> {
+        self.undone.iter().rev()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::building::Building;
+    use crate::location::Location;
+    use crate::table::Table;
+
+    #[test]
+    fn undo_and_redo_move_and_register() {
+        let db = Database::with_rooms(&["H-22", "S-310"]);
+        let mut stack = UndoStack::new();
+        let before = Equipment::Table(Table::new(Location::new(Building::new("H").unwrap(), 2, 2), 45000, 4));
+        let id = db.insert_equipment(&before).unwrap();
+        let mut before = before;
+        before.set_id(id);
+        stack.record(Change::Registered { id, equipment: Box::new(before.clone()) });
+
+        let mut after = before.clone();
+        after.set_location(Location::new(Building::new("S").unwrap(), 3, 10));
+        db.update_location(id, after.get_location()).unwrap();
+        stack.record(Change::Edited { id, before: Box::new(before.clone()), after: Box::new(after) });
+
+        stack.undo(&db).unwrap();
+        assert_eq!(db.get_equipment_by_id(id).unwrap(), Some(before.clone()));
+        stack.undo(&db).unwrap();
+        assert_eq!(db.get_equipment_by_id(id).unwrap(), None);
+        assert!(!stack.can_undo());
+
+        stack.redo(&db).unwrap();
+        stack.redo(&db).unwrap();
+        let loc = db.get_equipment_by_id(id).unwrap().unwrap().get_location().clone();
+        assert_eq!(loc, Location::new(Building::new("S").unwrap(), 3, 10));
+    }
+}
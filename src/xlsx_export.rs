@@ -0,0 +1,210 @@
+//! Excel workbook of the whole inventory: a summary sheet followed by one sheet per building.
+
+use crate::building::BuildingInfo;
+use crate::equipment::Equipment;
+use crate::error::Result;
+use crate::statistics::{Statistics, Tally};
+use rust_xlsxwriter::{Format, FormatBorder, Formula, Workbook, Worksheet};
+
+const ISK: &str = "#,##0 \"kr.\"";
+
+const COLUMNS: [(&str, f64); 24] = [
+    ("ID", 8.0),
+    ("Tegund", 14.0),
+    ("Staðsetning", 14.0),
+    ("Hæð", 8.0),
+    ("Herbergi", 10.0),
+    ("Verðmæti", 16.0),
+    ("Sæti", 8.0),
+    ("Stólategund", 18.0),
+    ("Lúmens", 10.0),
+    ("Eiginleikar", 36.0),
+    ("Tegund tölvu", 12.0),
+    ("Örgjörvi", 22.0),
+    ("Vinnsluminni (GB)", 10.0),
+    ("Skjástærð", 10.0),
+    ("Upplausn", 12.0),
+    ("Eignanúmer", 14.0),
+    ("Raðnúmer", 18.0),
+    ("Framleiðandi", 14.0),
+    ("Gerð", 18.0),
+    ("Innkaupsdagur", 12.0),
+    ("Birgir", 16.0),
+    ("Reikningsnúmer", 14.0),
+    ("Ástand", 14.0),
+    ("Athugasemdir", 36.0),
+];
+
+/// Build the workbook in memory and return the .xlsx bytes. Each building in
+/// `buildings` gets a sheet, as does any other building an item is in.
+pub fn workbook_bytes(items: &[Equipment], buildings: &[BuildingInfo]) -> Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let header = Format::new().set_bold().set_background_color("#ADD8E6").set_border_bottom(FormatBorder::Thin);
+    let money = Format::new().set_num_format(ISK);
+    let total = Format::new().set_bold().set_border_top(FormatBorder::Thin);
+    let total_money = total.clone().set_num_format(ISK);
+
+    let stats = Statistics::new(items, buildings);
+    write_summary(workbook.add_worksheet(), &stats, &header, &total, &total_money)?;
+
+    for (info, _) in &stats.by_building {
+        let building = &info.building;
+        let sheet = workbook.add_worksheet();
+        sheet.set_name(sheet_name(info))?;
+        for (col, (title, width)) in COLUMNS.iter().enumerate() {
+            sheet.write_string_with_format(0, col as u16, *title, &header)?;
+            sheet.set_column_width(col as u16, *width)?;
+        }
+        sheet.set_freeze_panes(1, 0)?;
+
+        let mut row = 1;
+        for eq in items.iter().filter(|e| &e.get_location().building == building) {
+            let location = eq.get_location();
+            sheet.write_number(row, 0, eq.get_id().unwrap_or(0) as f64)?;
+            sheet.write_string(row, 1, eq.get_type_name())?;
+            sheet.write_string(row, 2, location.to_string())?;
+            sheet.write_number(row, 3, location.floor)?;
+            sheet.write_number(row, 4, location.room)?;
+            sheet.write_number_with_format(row, 5, eq.get_value(), &money)?;
+            match eq {
+                Equipment::Table(t) => sheet.write_number(row, 6, t.seats)?,
+                Equipment::Chair(c) => sheet.write_string(row, 7, c.chair_type.to_string())?,
+                Equipment::Projector(p) => sheet.write_number(row, 8, p.lumens)?,
+                Equipment::Custom(x) => {
+                    let details: Vec<String> = x.details().iter().map(|(label, value)| format!("{}: {}", label, value)).collect();
+                    sheet.write_string(row, 9, details.join("; "))?
+                }
+                Equipment::Computer(c) => {
+                    sheet.write_string(row, 10, c.kind.to_string())?;
+                    sheet.write_string(row, 11, &c.cpu)?;
+                    match c.ram_gb {
+                        Some(ram) => sheet.write_number(row, 12, ram)?,
+                        None => sheet,
+                    }
+                }
+                Equipment::Monitor(m) => {
+                    sheet.write_number(row, 13, m.screen_size)?;
+                    sheet.write_string(row, 14, m.resolution.to_string())?
+                }
+            };
+            for (offset, (_, value)) in eq.info().fields().into_iter().enumerate() {
+                sheet.write_string(row, 15 + offset as u16, value)?;
+            }
+            row += 1;
+        }
+        if row > 1 {
+            sheet.autofilter(0, 0, row - 1, COLUMNS.len() as u16 - 1)?;
+        }
+
+        // Live formulas so the totals follow edits made in Excel; the cached
+        // result is for viewers that do not recalculate
+        let building_total: u64 = items
+            .iter()
+            .filter(|e| &e.get_location().building == building)
+            .map(|e| u64::from(e.get_value()))
+            .sum();
+        sheet.write_string_with_format(row, 0, "Samtals", &total)?;
+        match (column_total("COUNTA", 'B', row), column_total("SUM", 'F', row)) {
+            (Some(count), Some(sum)) => {
+                sheet.write_formula_with_format(row, 1, Formula::new(count).set_result((row - 1).to_string()), &total)?;
+                sheet.write_formula_with_format(row, 5, Formula::new(sum).set_result(building_total.to_string()), &total_money)?;
+            }
+            // No items: a formula over no rows would refer to its own cell
+            _ => {
+                sheet.write_number_with_format(row, 1, 0, &total)?;
+                sheet.write_number_with_format(row, 5, 0, &total_money)?;
+            }
+        }
+    }
+
+    Ok(workbook.save_to_buffer()?)
+}
+
+/// Formula over the item rows of `column` for the totals row at `row` (0-based), e.g.
+/// `=SUM(F2:F13)`; `None` when the sheet has no item rows
+fn column_total(function: &str, column: char, row: u32) -> Option<String> {
+    (row > 1).then(|| format!("={}({}2:{}{})", function, column, column, row))
+}
+
+/// Write the workbook to `path`
+pub fn write_xlsx(path: &std::path::Path, items: &[Equipment], buildings: &[BuildingInfo]) -> Result<()> {
+    std::fs::write(path, workbook_bytes(items, buildings)?)?;
+    Ok(())
+}
+
+/// Excel limits sheet names to 31 characters and forbids `[]:*?/\`; the code is
+/// added so two buildings with similar names still get different sheets
+fn sheet_name(info: &BuildingInfo) -> String {
+    let name: String = info.name.chars().filter(|c| !"[]:*?/\\".contains(*c)).collect();
+    let suffix = format!(" ({})", info.building);
+    let name: String = name.trim().chars().take(31 - suffix.chars().count()).collect();
+    format!("{}{}", name.trim_end(), suffix)
+}
+
+fn write_summary(sheet: &mut Worksheet, stats: &Statistics, header: &Format, total: &Format, total_money: &Format) -> Result<()> {
+    sheet.set_name("Yfirlit")?;
+    let money = Format::new().set_num_format(ISK);
+    let percent = Format::new().set_num_format("0.0%");
+    sheet.set_column_width(0, 20)?;
+    sheet.set_column_width(1, 10)?;
+    sheet.set_column_width(2, 10)?;
+    sheet.set_column_width(3, 18)?;
+    sheet.write_string_with_format(0, 0, "Búnaðarlisti Tækniskólans", &Format::new().set_bold().set_font_size(14))?;
+
+    let mut write_group = |start: u32, title: &str, rows: Vec<(String, Tally)>| -> Result<u32> {
+        for (col, label) in [title, "Fjöldi", "Hlutfall", "Verðmæti"].iter().enumerate() {
+            sheet.write_string_with_format(start, col as u16, *label, header)?;
+        }
+        let mut row = start + 1;
+        for (label, tally) in rows {
+            sheet.write_string(row, 0, label)?;
+            sheet.write_number(row, 1, tally.count as f64)?;
+            sheet.write_number_with_format(row, 2, stats.share(tally) as f64 / 100.0, &percent)?;
+            sheet.write_number_with_format(row, 3, tally.value as f64, &money)?;
+            row += 1;
+        }
+        sheet.write_string_with_format(row, 0, "Samtals", total)?;
+        sheet.write_number_with_format(row, 1, stats.total.count as f64, total)?;
+        sheet.write_number_with_format(row, 3, stats.total.value as f64, total_money)?;
+        Ok(row + 2)
+    };
+
+    let by_type = stats.by_type.iter().map(|(t, tally)| (t.to_string(), *tally)).collect();
+    let next = write_group(2, "Tegund", by_type)?;
+    let by_building = stats.by_building.iter().map(|(b, tally)| (b.to_string(), *tally)).collect();
+    write_group(next, "Hús", by_building)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::building::Building;
+    use crate::location::Location;
+    use crate::table::Table;
+
+    #[test]
+    fn writes_a_zip_container() {
+        let items = vec![Equipment::Table(Table::new(Location::new(Building::new("H").unwrap(), 2, 12), 45000, 4))];
+        let buildings = [BuildingInfo {
+            building: Building::new("H").unwrap(),
+            name: "Háteigsvegur: [aðalbygging] með mjög löngu nafni".into(),
+            address: String::new(),
+        }];
+        assert_eq!(sheet_name(&buildings[0]), "Háteigsvegur aðalbygging me (H)");
+        let bytes = workbook_bytes(&items, &buildings).unwrap();
+        // .xlsx files are zip archives
+        assert_eq!(&bytes[..2], b"PK");
+    }
+
+    #[test]
+    fn empty_buildings_get_constant_totals() {
+        let buildings = [BuildingInfo { building: Building::new("HA").unwrap(), name: "Hafnarfjörður".into(), address: String::new() }];
+        let bytes = workbook_bytes(&[], &buildings).unwrap();
+        assert_eq!(&bytes[..2], b"PK");
+        // Totals on Excel row 2 with no items above them
+        assert_eq!(column_total("SUM", 'F', 1), None);
+        assert_eq!(column_total("SUM", 'F', 2).as_deref(), Some("=SUM(F2:F2)"));
+        assert_eq!(column_total("COUNTA", 'B', 13).as_deref(), Some("=COUNTA(B2:B13)"));
+    }
+}